@@ -53,6 +53,15 @@ pub struct BalanceInfo {
 	pub amount: Balance,
 }
 
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
+pub struct PoolSupplyCapData {
+	#[cfg_attr(feature = "std", serde(serialize_with = "serialize_as_string"))]
+	#[cfg_attr(feature = "std", serde(deserialize_with = "deserialize_from_string"))]
+	pub pool_total_supply_in_usd: Balance,
+	pub supply_cap: Option<BalanceInfo>,
+}
+
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Eq, PartialEq, Encode, Decode, Default, RuntimeDebug)]
 pub struct ProtocolTotalValue {
@@ -139,5 +148,9 @@ sp_api::decl_runtime_apis! {
 		fn pool_exists(underlying_asset_id: CurrencyId) -> bool;
 
 		fn get_user_total_supply_borrow_and_net_apy(account_id: AccountId) -> Option<(Interest, Interest, Interest)>;
+
+		fn get_pool_bad_debt(pool_id: CurrencyId) -> Option<BalanceInfo>;
+
+		fn get_pool_supply_cap(pool_id: CurrencyId) -> Option<PoolSupplyCapData>;
 	}
 }
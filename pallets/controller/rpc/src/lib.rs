@@ -3,7 +3,8 @@
 use codec::Codec;
 use controller_rpc_runtime_api::UserData;
 pub use controller_rpc_runtime_api::{
-	BalanceInfo, ControllerRuntimeApi, HypotheticalLiquidityData, PoolState, ProtocolTotalValue, UserPoolBalanceData,
+	BalanceInfo, ControllerRuntimeApi, HypotheticalLiquidityData, PoolState, PoolSupplyCapData, ProtocolTotalValue,
+	UserPoolBalanceData,
 };
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
@@ -169,6 +170,27 @@ pub trait ControllerRpcApi<BlockHash, AccountId> {
 	///   user_total_supply_apy, user_total_borrow_apy, user_net_apy)
 	#[rpc(name = "controller_getUserData")]
 	fn get_user_data(&self, account_id: AccountId, at: Option<BlockHash>) -> Result<Option<UserData>>;
+
+	/// Returns the written off borrows of the pool that are not covered yet.
+	///
+	///  - `&self` :  Self reference
+	///  - `pool_id`: target pool id.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	/// Return:
+	/// - bad_debt: the uncovered amount of the underlying asset.
+	#[rpc(name = "controller_getPoolBadDebt")]
+	fn get_pool_bad_debt(&self, pool_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<BalanceInfo>>;
+
+	/// Returns the supply cap of the pool together with the current total supply.
+	///
+	///  - `&self` :  Self reference
+	///  - `pool_id`: target pool id.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	/// Return:
+	/// - pool_total_supply_in_usd: total supply of the pool in usd, including interest.
+	/// - supply_cap: maximum total supply of the pool in usd. No value means infinite supply cap.
+	#[rpc(name = "controller_getPoolSupplyCap")]
+	fn get_pool_supply_cap(&self, pool_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<PoolSupplyCapData>>;
 }
 
 /// A struct that implements the [`ControllerApi`].
@@ -385,4 +407,36 @@ where
 				data: Some(format!("{:?}", e).into()),
 			})
 	}
+
+	fn get_pool_bad_debt(
+		&self,
+		pool_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<BalanceInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_pool_bad_debt(&at, pool_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool bad debt.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_pool_supply_cap(
+		&self,
+		pool_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<PoolSupplyCapData>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+		api.get_pool_supply_cap(&at, pool_id).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get pool supply cap.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
 }
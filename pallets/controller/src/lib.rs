@@ -5,7 +5,21 @@
 //! Contains protocol settings and helper functions related to interest calculations.
 //! Also it is managing paused operations and whitelist mode. These are related to protocol
 //! security. In case of emergency some of protocol operations can be paused by authorized users.
+//! The whole protocol can be frozen with a single call by the pause guardian origin, and pauses
+//! can be given an expiry block at which they are resumed automatically. The emergency origin may
+//! lower caps and restrict the usage of pools, while raising them requires the risk parameter
+//! origin.
 //! When Whitelist mode is enabled, protocol interaction is restricted to whitelist members only.
+//! Each pool can be restricted to be used as collateral only or to be borrowed only. A pool can
+//! also be isolated: a user who enables it as collateral cannot use any other collateral, can only
+//! borrow the assets approved for isolation mode, and the total debt backed by the isolated
+//! collateral is limited by a debt ceiling in USD.
+//! Pools of correlated assets can be grouped into efficiency mode (e-mode) categories. A user who
+//! opts in to a category can only use the assets of the category as collateral and borrow, but
+//! gets the higher collateral factor of the category. The liquidation threshold of the category,
+//! which is the step of partial liquidations, is applied to such users as well.
+//! Anyone can accrue the interest in the pools. A keeper who accrues the interest in a pool that
+//! has not been touched for a while is paid a small MNT reward.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -16,11 +30,14 @@ use frame_support::{ensure, pallet_prelude::*, transactional};
 use frame_system::pallet_prelude::*;
 use liquidity_pools::{Pool, PoolUserData};
 use minterest_primitives::{
-	arithmetic::sum_with_mult_result,
+	arithmetic::{checked_pow, sum_with_mult_result},
 	constants::time::BLOCKS_PER_YEAR,
-	currency::CurrencyType::{UnderlyingAsset, WrappedToken},
+	currency::{
+		CurrencyType::{UnderlyingAsset, WrappedToken},
+		MNT,
+	},
 };
-use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Rate};
+use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Price, Rate};
 pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
@@ -30,12 +47,13 @@ use pallet_traits::{
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
 use sp_runtime::{
-	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Zero},
+	traits::{BadOrigin, CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero},
 	DispatchError, DispatchResult, FixedPointNumber, FixedU128, RuntimeDebug,
 };
 use sp_std::{cmp::Ordering, convert::TryInto, prelude::Vec, result};
 pub use weights::WeightInfo;
 
+pub mod migrations;
 #[cfg(test)]
 mod tests;
 pub mod weights;
@@ -62,6 +80,47 @@ pub struct ControllerData<BlockNumber> {
 
 	/// Minimum protocol interest needed to transfer it to liquidation pool
 	pub protocol_interest_threshold: Balance,
+
+	/// Maximum total supply amount per pool in usd. No value means infinite supply cap.
+	pub supply_cap: Option<Balance>,
+
+	/// Defines how the borrow interest is accrued over the blocks elapsed since the last accrual.
+	pub interest_accrual_mode: InterestAccrualMode,
+}
+
+/// The way the borrow interest is accrued over several blocks.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub enum InterestAccrualMode {
+	/// `interest_factor = borrow_rate * block_delta`. Under-accrues compared to per-block
+	/// compounding when the pool is not touched for a long time.
+	Simple,
+	/// `interest_factor = (1 + borrow_rate) ^ block_delta - 1`. The accrued interest doesn't
+	/// depend on how often the pool is touched.
+	Compound,
+}
+
+impl Default for InterestAccrualMode {
+	fn default() -> Self {
+		InterestAccrualMode::Simple
+	}
+}
+
+/// The version of the controller storage, used to run each storage migration only once.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, Ord, PartialOrd)]
+pub enum Releases {
+	/// `ControllerData` without the supply cap and the interest accrual mode.
+	V1_0_0,
+	/// The supply cap is added to `ControllerData`.
+	V2_0_0,
+	/// The interest accrual mode is added to `ControllerData`.
+	V3_0_0,
+}
+
+impl Default for Releases {
+	fn default() -> Self {
+		Releases::V1_0_0
+	}
 }
 
 /// The Root or half MinterestCouncil can pause certain actions as a safety mechanism.
@@ -101,6 +160,61 @@ impl PauseKeeper {
 	}
 }
 
+/// Restrictions on how the pool can be used.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq)]
+pub struct UsageParams {
+	/// The pool can be enabled as collateral.
+	pub can_be_collateral: bool,
+
+	/// The underlying asset of the pool can be borrowed.
+	pub can_be_borrowed: bool,
+
+	/// The underlying asset of the pool can be borrowed against an isolated collateral.
+	pub borrowable_in_isolation: bool,
+
+	/// Maximum total debt in usd backed by the pool used as collateral. No value means the pool
+	/// is not isolated.
+	pub isolation_debt_ceiling: Option<Balance>,
+}
+
+impl Default for UsageParams {
+	fn default() -> Self {
+		UsageParams {
+			can_be_collateral: true,
+			can_be_borrowed: true,
+			borrowable_in_isolation: false,
+			isolation_debt_ceiling: None,
+		}
+	}
+}
+
+/// Risk parameters of an efficiency mode category of correlated assets.
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct EModeCategory {
+	/// Collateral factor applied instead of the pool collateral factor to the users of the
+	/// category. Must be between 0 and 1 and not lower than the collateral factors of the pools
+	/// of the category.
+	pub collateral_factor: Rate,
+
+	/// Liquidation threshold applied instead of the pool liquidation threshold to the users of
+	/// the category. Must not exceed `1 - collateral_factor`.
+	/// The liquidation threshold is the step of partial liquidations: a partial liquidation
+	/// repays `shortfall * (1 + liquidation_threshold)`. So a higher value makes the liquidations
+	/// of the users of the category larger, not rarer.
+	pub liquidation_threshold: Rate,
+}
+
+/// All the operations that can be paused in a pool.
+pub const ALL_OPERATIONS: [Operation; 5] = [
+	Operation::Deposit,
+	Operation::Redeem,
+	Operation::Borrow,
+	Operation::Repay,
+	Operation::Transfer,
+];
+
 pub struct GetAllPaused;
 impl frame_support::traits::Get<PauseKeeper> for GetAllPaused {
 	fn get() -> PauseKeeper {
@@ -141,15 +255,49 @@ pub mod module {
 		/// Maximum total borrow amount per pool in usd.
 		type MaxBorrowCap: Get<Balance>;
 
-		/// The origin which may update controller parameters. Root or
-		/// Half Minterest Council can always do this.
+		#[pallet::constant]
+		/// Maximum total supply amount per pool in usd.
+		type MaxSupplyCap: Get<Balance>;
+
+		#[pallet::constant]
+		/// The maximum portion of a borrow that can be repaid in a single liquidation.
+		type CloseFactor: Get<Rate>;
+
+		/// The origin which may resume operations and update the controller parameters that
+		/// don't change the risk of the loans. Root or Half Minterest Council can always do this.
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The origin which may update the risk parameters: collateral factors, caps, maximum
+		/// borrow rates, e-mode categories, usage params and interest accrual modes. Expected to be
+		/// root, so that the changes are delayed by the timelock.
+		type RiskParameterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may lower caps and restrict the usage of pools, but not raise the
+		/// risk. Should require a smaller quorum than `RiskParameterOrigin`, so that the protocol
+		/// can be protected quickly in an incident.
+		type EmergencyOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may pause operations, but not resume them or change any parameters.
+		type PauseGuardianOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for the extrinsics.
 		type ControllerWeightInfo: WeightInfo;
 
 		/// Provides MNT token distribution functionality.
 		type MntManager: MntManager<Self::AccountId>;
+
+		/// The account from which the keeper reward is paid.
+		type MntTokenAccountId: Get<Self::AccountId>;
+
+		#[pallet::constant]
+		/// The amount of MNT paid to the account that accrued the interest in a pool that
+		/// nobody has touched for `KeeperRewardMinAccrualAge` blocks. Zero disables the reward.
+		type KeeperReward: Get<Balance>;
+
+		#[pallet::constant]
+		/// The number of blocks since the last interest accrual in a pool after which the keeper
+		/// reward is paid.
+		type KeeperRewardMinAccrualAge: Get<Self::BlockNumber>;
 	}
 
 	#[pallet::error]
@@ -191,6 +339,40 @@ pub mod module {
 		NotValidWrappedTokenId,
 		/// The currency is not enabled in protocol.
 		NotValidUnderlyingAssetId,
+		/// The borrower has no shortfall, the loan cannot be liquidated.
+		InsufficientShortfall,
+		/// The repay amount exceeds the maximum allowed by the close factor.
+		TooMuchRepay,
+		/// The pool is not enabled as collateral for the borrower.
+		PoolIsNotCollateral,
+		/// The pause expiry block must be in the future.
+		InvalidPauseExpiry,
+		/// Supply cap is reached
+		SupplyCapReached,
+		/// Invalid supply cap. Supply cap must be in range [0..MAX_SUPPLY_CAP].
+		InvalidSupplyCap,
+		/// Invalid isolation debt ceiling. The ceiling must be in range [0..MAX_BORROW_CAP].
+		InvalidIsolationDebtCeiling,
+		/// The pool cannot be used as collateral.
+		CollateralNotAllowed,
+		/// The underlying asset of the pool cannot be borrowed.
+		BorrowNotAllowed,
+		/// An isolated collateral cannot be combined with other collaterals.
+		IsolatedCollateralCannotBeCombined,
+		/// The underlying asset cannot be borrowed against an isolated collateral.
+		NotBorrowableInIsolation,
+		/// The debt ceiling of the isolated collateral is reached.
+		IsolationDebtCeilingReached,
+		/// Invalid e-mode category. Collateral factor must be in range (0..1] and liquidation
+		/// threshold must not exceed `1 - collateral_factor`.
+		InvalidEModeCategory,
+		/// E-mode category not found.
+		EModeCategoryNotFound,
+		/// The pool doesn't belong to the e-mode category of the user.
+		EModeCategoryMismatch,
+		/// The collateral factor of the e-mode category is lower than the collateral factor of
+		/// one of its pools.
+		EModeCollateralFactorTooLow,
 	}
 
 	#[pallet::event]
@@ -206,12 +388,38 @@ pub mod module {
 		OperationIsPaused(CurrencyId, Operation),
 		/// The operation is unpaused: \[pool_id, operation\]
 		OperationIsUnPaused(CurrencyId, Operation),
+		/// All operations in all pools are paused.
+		AllOperationsPaused,
+		/// All operations in all pools are unpaused.
+		AllOperationsUnPaused,
 		/// Borrow cap changed: \[pool_id, new_cap\]
 		BorrowCapChanged(CurrencyId, Option<Balance>),
 		/// Protocol operation mode switched: \[is_whitelist_mode\]
 		ProtocolOperationModeSwitched(bool),
 		/// Protocol interest threshold changed: \[pool_id, new_value\]
 		ProtocolInterestThresholdChanged(CurrencyId, Balance),
+		/// Supply cap changed: \[pool_id, new_cap\]
+		SupplyCapChanged(CurrencyId, Option<Balance>),
+		/// Pool usage params changed: \[pool_id, usage_params\]
+		UsageParamsChanged(CurrencyId, UsageParams),
+		/// E-mode category parameters changed: \[category_id, category\]
+		EModeCategoryChanged(u8, EModeCategory),
+		/// Pool e-mode category changed: \[pool_id, category_id\]
+		PoolEModeCategoryChanged(CurrencyId, Option<u8>),
+		/// Interest accrual mode changed: \[pool_id, interest_accrual_mode\]
+		InterestAccrualModeChanged(CurrencyId, InterestAccrualMode),
+		/// Borrow rate clamping mode switched: \[pool_id, is_enabled\]
+		BorrowRateClampingSwitched(CurrencyId, bool),
+		/// The interest is accrued at the maximum borrow rate, because the borrow rate given by
+		/// the interest rate model exceeds it: \[pool_id, model_borrow_rate\]
+		BorrowRateClamped(CurrencyId, Rate),
+		/// The interest is accrued in the pool by a keeper: \[pool_id\]
+		InterestAccrued(CurrencyId),
+		/// The keeper reward is paid for the interest accrual in the pool: \[pool_id, mnt_amount\]
+		KeeperRewardPaid(CurrencyId, Balance),
+		/// The interest accrual in the pool failed, so the pool is skipped by the keeper:
+		/// \[pool_id, error\]
+		InterestAccrualFailed(CurrencyId, DispatchError),
 	}
 
 	/// Controller data information: `(timestamp, protocol_interest_factor, collateral_factor,
@@ -227,6 +435,58 @@ pub mod module {
 	pub(crate) type PauseKeepers<T: Config> =
 		StorageMap<_, Twox64Concat, CurrencyId, PauseKeeper, ValueQuery, GetAllPaused>;
 
+	/// Restrictions on how the pools can be used.
+	#[pallet::storage]
+	#[pallet::getter(fn usage_params)]
+	pub type PoolUsageParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, UsageParams, ValueQuery>;
+
+	/// Total debt in usd backed by the isolated collateral: `pool_id -> debt_in_usd`.
+	/// The debt is recorded at the price of the borrowed asset at the time of the borrow.
+	#[pallet::storage]
+	#[pallet::getter(fn isolation_mode_total_debt)]
+	pub type IsolationModeTotalDebt<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
+	/// The part of `IsolationModeTotalDebt` recorded for the loan of the borrower:
+	/// `(borrower, borrowed_pool_id) -> (isolated_pool_id, debt_in_usd)`.
+	/// Repays release the same share of the recorded debt as of the loan, so they don't need a
+	/// price.
+	#[pallet::storage]
+	#[pallet::getter(fn isolation_mode_user_debt)]
+	pub type IsolationModeUserDebt<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, T::AccountId, Twox64Concat, CurrencyId, (CurrencyId, Balance), OptionQuery>;
+
+	/// Efficiency mode categories: `category_id -> EModeCategory`.
+	#[pallet::storage]
+	#[pallet::getter(fn emode_categories)]
+	pub type EModeCategories<T: Config> = StorageMap<_, Twox64Concat, u8, EModeCategory, OptionQuery>;
+
+	/// The e-mode category the pool belongs to: `pool_id -> category_id`.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_emode_category)]
+	pub type PoolEModeCategory<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u8, OptionQuery>;
+
+	/// The e-mode category the user opted in to: `user -> category_id`.
+	#[pallet::storage]
+	#[pallet::getter(fn user_emode_category)]
+	pub type UserEModeCategory<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u8, OptionQuery>;
+
+	/// Pools in which the borrow rate exceeding the maximum borrow rate is clamped to it instead
+	/// of failing the interest accrual.
+	#[pallet::storage]
+	#[pallet::getter(fn borrow_rate_clamping)]
+	pub type BorrowRateClamping<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// The block at which a paused operation is resumed automatically.
+	#[pallet::storage]
+	#[pallet::getter(fn paused_until)]
+	pub(crate) type PausedUntil<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, Operation, T::BlockNumber, OptionQuery>;
+
+	/// The version of the storage. Chains started before the version was tracked are on
+	/// `V1_0_0`.
+	#[pallet::storage]
+	pub type StorageVersion<T: Config> = StorageValue<_, Releases, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[allow(clippy::type_complexity)]
@@ -255,6 +515,7 @@ pub mod module {
 			self.pause_keepers.iter().for_each(|(currency_id, pause_keeper)| {
 				PauseKeepers::<T>::insert(currency_id, PauseKeeper { ..*pause_keeper })
 			});
+			StorageVersion::<T>::put(Releases::V3_0_0);
 		}
 	}
 
@@ -262,14 +523,46 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Adds the supply cap and the interest accrual mode to the controller data of every pool,
+		/// if the storage has not been migrated yet.
+		fn on_runtime_upgrade() -> Weight {
+			let storage_version = StorageVersion::<T>::get();
+			let mut weight = T::DbWeight::get().reads(1);
+			if storage_version < Releases::V2_0_0 {
+				weight = weight.saturating_add(migrations::migrate_controller_data_add_supply_cap::<T>());
+			}
+			if storage_version < Releases::V3_0_0 {
+				weight = weight.saturating_add(migrations::migrate_controller_data_add_interest_accrual_mode::<T>());
+				StorageVersion::<T>::put(Releases::V3_0_0);
+				weight = weight.saturating_add(T::DbWeight::get().writes(1));
+			}
+			weight
+		}
+
+		/// Resumes the operations whose pause has expired.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let mut scheduled_count = 0u32;
+			let expired: Vec<(CurrencyId, Operation)> = PausedUntil::<T>::iter()
+				.filter_map(|(pool_id, operation, until)| {
+					scheduled_count = scheduled_count.saturating_add(1);
+					(until <= now).then(|| (pool_id, operation))
+				})
+				.collect();
+			expired
+				.into_iter()
+				.for_each(|(pool_id, operation)| Self::do_resume_operation(pool_id, operation));
+			T::ControllerWeightInfo::on_initialize(scheduled_count)
+		}
+	}
 
 	// Admin functions
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		/// Pause specific operation (deposit, redeem, borrow, repay) with the pool.
+		/// The operation stays paused until it is resumed explicitly.
 		///
-		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		/// The dispatch origin of this call must be 'UpdateOrigin' or 'PauseGuardianOrigin'.
 		#[pallet::weight(T::ControllerWeightInfo::pause_operation())]
 		#[transactional]
 		pub fn pause_operation(
@@ -277,22 +570,40 @@ pub mod module {
 			pool_id: CurrencyId,
 			operation: Operation,
 		) -> DispatchResultWithPostInfo {
-			T::UpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_pause_origin(origin)?;
 			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
 			ensure!(
 				T::LiquidityPoolsManager::pool_exists(&pool_id),
 				Error::<T>::PoolNotFound
 			);
 
-			PauseKeepers::<T>::mutate(pool_id, |pool| match operation {
-				Operation::Deposit => pool.deposit_paused = true,
-				Operation::Redeem => pool.redeem_paused = true,
-				Operation::Borrow => pool.borrow_paused = true,
-				Operation::Repay => pool.repay_paused = true,
-				Operation::Transfer => pool.transfer_paused = true,
-			});
+			Self::do_pause_operation(pool_id, operation, None);
+			Ok(().into())
+		}
 
-			Self::deposit_event(Event::OperationIsPaused(pool_id, operation));
+		/// Pause specific operation (deposit, redeem, borrow, repay) with the pool until the
+		/// given block. An existing pause is never shortened: if the operation is already paused
+		/// for longer, or without an expiry, the existing pause is kept.
+		/// - `until`: the block at which the operation is resumed automatically.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin' or 'PauseGuardianOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::pause_operation_until())]
+		#[transactional]
+		pub fn pause_operation_until(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			operation: Operation,
+			until: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			Self::ensure_pause_origin(origin)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+			Self::ensure_valid_pause_expiry(Some(until))?;
+
+			Self::do_pause_operation(pool_id, operation, Some(until));
 			Ok(().into())
 		}
 
@@ -313,18 +624,93 @@ pub mod module {
 				Error::<T>::PoolNotFound
 			);
 
-			PauseKeepers::<T>::mutate(pool_id, |pool| match operation {
-				Operation::Deposit => pool.deposit_paused = false,
-				Operation::Redeem => pool.redeem_paused = false,
-				Operation::Borrow => pool.borrow_paused = false,
-				Operation::Repay => pool.repay_paused = false,
-				Operation::Transfer => pool.transfer_paused = false,
+			Self::do_resume_operation(pool_id, operation);
+			Ok(().into())
+		}
+
+		/// Pause all operations in all pools.
+		/// - `until`: the block at which the operations are resumed automatically. No value means
+		/// the operations stay paused until they are resumed explicitly. Existing pauses that
+		/// last longer are kept.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin' or 'PauseGuardianOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::pause_all())]
+		#[transactional]
+		pub fn pause_all(origin: OriginFor<T>, until: Option<T::BlockNumber>) -> DispatchResultWithPostInfo {
+			Self::ensure_pause_origin(origin)?;
+			Self::ensure_valid_pause_expiry(until)?;
+
+			Self::existing_pools().into_iter().for_each(|pool_id| {
+				ALL_OPERATIONS
+					.iter()
+					.for_each(|&operation| Self::do_pause_operation(pool_id, operation, until))
 			});
+			Self::deposit_event(Event::AllOperationsPaused);
+			Ok(().into())
+		}
 
-			Self::deposit_event(Event::OperationIsUnPaused(pool_id, operation));
+		/// Unpause all operations in all pools.
+		///
+		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::resume_all())]
+		#[transactional]
+		pub fn resume_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			Self::existing_pools().into_iter().for_each(|pool_id| {
+				ALL_OPERATIONS
+					.iter()
+					.for_each(|&operation| Self::do_resume_operation(pool_id, operation))
+			});
+			Self::deposit_event(Event::AllOperationsUnPaused);
 			Ok(().into())
 		}
 
+		/// Accrues the interest in the pool, so that its rates and exchange rate are up to date.
+		/// Pays the keeper reward to the caller if the interest has not been accrued for
+		/// `KeeperRewardMinAccrualAge` blocks.
+		/// - `pool_id`: PoolID in which the interest is accrued.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[pallet::weight(T::ControllerWeightInfo::accrue_interest())]
+		#[transactional]
+		pub fn accrue_interest(origin: OriginFor<T>, pool_id: CurrencyId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			Self::do_accrue_interest(&who, pool_id)?;
+			Ok(().into())
+		}
+
+		/// Accrues the interest in all the pools. The pools in which the interest has already been
+		/// accrued in the current block or the accrual fails are skipped. The changes made by a
+		/// failed accrual are reverted and `InterestAccrualFailed` is emitted for the pool.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[pallet::weight(T::ControllerWeightInfo::accrue_interest_all(
+			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset).len() as u32
+		))]
+		#[transactional]
+		pub fn accrue_interest_all(origin: OriginFor<T>) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			let processed_count = Self::existing_pools()
+				.into_iter()
+				.filter(|&pool_id| match Self::do_accrue_interest(&who, pool_id) {
+					Ok(is_accrued) => is_accrued,
+					Err(error) => {
+						Self::deposit_event(Event::InterestAccrualFailed(pool_id, error));
+						false
+					}
+				})
+				.count();
+			Ok(Some(T::ControllerWeightInfo::accrue_interest_all(processed_count as u32)).into())
+		}
+
 		/// Set interest factor.
 		/// - `pool_id`: PoolID for which the parameter value is being set.
 		/// - `protocol_interest_factor`: new value for interest factor.
@@ -353,7 +739,7 @@ pub mod module {
 		/// - `pool_id`: PoolID for which the parameter value is being set.
 		/// - `max_borrow_rate`: new value for maximum borrow rate.
 		///
-		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
 		#[pallet::weight(T::ControllerWeightInfo::set_max_borrow_rate())]
 		#[transactional]
 		pub fn set_max_borrow_rate(
@@ -361,7 +747,7 @@ pub mod module {
 			pool_id: CurrencyId,
 			max_borrow_rate: Rate,
 		) -> DispatchResultWithPostInfo {
-			T::UpdateOrigin::ensure_origin(origin)?;
+			T::RiskParameterOrigin::ensure_origin(origin)?;
 			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
 			ensure!(
 				T::LiquidityPoolsManager::pool_exists(&pool_id),
@@ -381,7 +767,7 @@ pub mod module {
 		/// - `pool_id`: PoolID for which the parameter value is being set.
 		/// - `collateral_factor`: new value for collateral factor.
 		///
-		/// The dispatch origin of this call must be 'UpdateOrigin'.
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
 		#[pallet::weight(T::ControllerWeightInfo::set_collateral_factor())]
 		#[transactional]
 		pub fn set_collateral_factor(
@@ -389,7 +775,7 @@ pub mod module {
 			pool_id: CurrencyId,
 			collateral_factor: Rate,
 		) -> DispatchResultWithPostInfo {
-			T::UpdateOrigin::ensure_origin(origin)?;
+			T::RiskParameterOrigin::ensure_origin(origin)?;
 			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
 			ensure!(
 				T::LiquidityPoolsManager::pool_exists(&pool_id),
@@ -399,6 +785,12 @@ pub mod module {
 				Self::is_valid_collateral_factor(collateral_factor),
 				Error::<T>::CollateralFactorIncorrectValue
 			);
+			if let Some(category) = Self::pool_emode_category(pool_id).and_then(Self::emode_categories) {
+				ensure!(
+					collateral_factor <= category.collateral_factor,
+					Error::<T>::EModeCollateralFactorTooLow
+				);
+			}
 
 			ControllerParams::<T>::mutate(pool_id, |data| data.collateral_factor = collateral_factor);
 			Self::deposit_event(Event::CollateralFactorChanged);
@@ -407,7 +799,8 @@ pub mod module {
 
 		/// Set borrow cap.
 		///
-		/// The dispatch origin of this call must be Administrator.
+		/// The dispatch origin of this call must be 'RiskParameterOrigin' or, if the value is lowered,
+		/// 'EmergencyOrigin'.
 		/// Borrow cap value must be in range 0..1_000_000_000_000_000_000_000_000
 		#[pallet::weight(T::ControllerWeightInfo::set_borrow_cap())]
 		#[transactional]
@@ -416,7 +809,8 @@ pub mod module {
 			pool_id: CurrencyId,
 			borrow_cap: Option<Balance>,
 		) -> DispatchResultWithPostInfo {
-			T::UpdateOrigin::ensure_origin(origin)?;
+			let lowers_risk = Self::is_cap_lowered(Self::controller_params(pool_id).borrow_cap, borrow_cap);
+			Self::ensure_risk_parameter_origin(origin, lowers_risk)?;
 			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
 			ensure!(
 				T::LiquidityPoolsManager::pool_exists(&pool_id),
@@ -429,6 +823,185 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Set supply cap.
+		/// The supply cap is set in usd. While the price feed of the underlying asset is stale,
+		/// deposits into a capped pool are checked against the last accepted price.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin' or, if the value is lowered,
+		/// 'EmergencyOrigin'.
+		/// Supply cap value must be in range 0..1_000_000_000_000_000_000_000_000
+		#[pallet::weight(T::ControllerWeightInfo::set_supply_cap())]
+		#[transactional]
+		pub fn set_supply_cap(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			supply_cap: Option<Balance>,
+		) -> DispatchResultWithPostInfo {
+			let lowers_risk = Self::is_cap_lowered(Self::controller_params(pool_id).supply_cap, supply_cap);
+			Self::ensure_risk_parameter_origin(origin, lowers_risk)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			ensure!(Self::is_valid_supply_cap(supply_cap), Error::<T>::InvalidSupplyCap);
+			ControllerParams::<T>::mutate(pool_id, |data| data.supply_cap = supply_cap);
+			Self::deposit_event(Event::SupplyCapChanged(pool_id, supply_cap));
+			Ok(().into())
+		}
+
+		/// Set the way the borrow interest of the pool is accrued. The interest accrued so far is
+		/// applied with the previous mode.
+		/// - `pool_id`: PoolID for which the parameter value is being set.
+		/// - `interest_accrual_mode`: simple or compound interest.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::set_interest_accrual_mode())]
+		#[transactional]
+		pub fn set_interest_accrual_mode(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			interest_accrual_mode: InterestAccrualMode,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			<Self as ControllerManager<T::AccountId>>::accrue_interest_rate(pool_id)?;
+			ControllerParams::<T>::mutate(pool_id, |data| data.interest_accrual_mode = interest_accrual_mode);
+			Self::deposit_event(Event::InterestAccrualModeChanged(pool_id, interest_accrual_mode));
+			Ok(().into())
+		}
+
+		/// Enable or disable the borrow rate clamping mode of the pool. When enabled, the interest
+		/// is accrued at `max_borrow_rate` if the borrow rate given by the interest rate model
+		/// exceeds it, and only new borrows are blocked. Otherwise all the operations in the pool
+		/// fail until the rate goes down.
+		/// - `pool_id`: PoolID for which the parameter value is being set.
+		/// - `is_enabled`: whether the borrow rate is clamped.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::switch_borrow_rate_clamping())]
+		#[transactional]
+		pub fn switch_borrow_rate_clamping(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			is_enabled: bool,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+
+			BorrowRateClamping::<T>::insert(pool_id, is_enabled);
+			Self::deposit_event(Event::BorrowRateClampingSwitched(pool_id, is_enabled));
+			Ok(().into())
+		}
+
+		/// Set the restrictions on how the pool can be used.
+		/// Switching `can_be_collateral` off removes the pool from the collateral of every user
+		/// immediately, so the loans backed by it may become liquidatable in the same block. To wind
+		/// down a collateral gradually, lower its collateral factor first.
+		/// - `pool_id`: PoolID for which the parameter value is being set.
+		/// - `usage_params`: collateral / borrow flags and the isolation debt ceiling.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin' or, if no usage is allowed
+		/// and the debt ceiling isn't raised, 'EmergencyOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::set_usage_params())]
+		#[transactional]
+		pub fn set_usage_params(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			usage_params: UsageParams,
+		) -> DispatchResultWithPostInfo {
+			let lowers_risk = Self::is_usage_restricted(Self::usage_params(pool_id), usage_params);
+			Self::ensure_risk_parameter_origin(origin, lowers_risk)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+			ensure!(
+				Self::is_valid_borrow_cap(usage_params.isolation_debt_ceiling),
+				Error::<T>::InvalidIsolationDebtCeiling
+			);
+
+			PoolUsageParams::<T>::insert(pool_id, usage_params);
+			Self::deposit_event(Event::UsageParamsChanged(pool_id, usage_params));
+			Ok(().into())
+		}
+
+		/// Set the risk parameters of an e-mode category. Creates the category if it doesn't exist.
+		/// The new parameters apply to the users of the category immediately: lowering the
+		/// collateral factor may make their loans liquidatable in the same block.
+		/// - `category_id`: ID of the e-mode category.
+		/// - `category`: collateral factor and liquidation threshold of the category.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::set_emode_category())]
+		#[transactional]
+		pub fn set_emode_category(
+			origin: OriginFor<T>,
+			category_id: u8,
+			category: EModeCategory,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::is_valid_emode_category(category),
+				Error::<T>::InvalidEModeCategory
+			);
+			ensure!(
+				Self::existing_pools()
+					.into_iter()
+					.filter(|&pool_id| Self::pool_emode_category(pool_id) == Some(category_id))
+					.all(|pool_id| Self::controller_params(pool_id).collateral_factor <= category.collateral_factor),
+				Error::<T>::EModeCollateralFactorTooLow
+			);
+
+			EModeCategories::<T>::insert(category_id, category);
+			Self::deposit_event(Event::EModeCategoryChanged(category_id, category));
+			Ok(().into())
+		}
+
+		/// Add the pool to an e-mode category or remove it from its category.
+		/// Removing the pool from its category applies the pool collateral factor to the users of
+		/// the category immediately, which may make their loans liquidatable in the same block.
+		/// - `pool_id`: PoolID for which the parameter value is being set.
+		/// - `category_id`: ID of the e-mode category. `None` removes the pool from its category.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::ControllerWeightInfo::set_pool_emode_category())]
+		#[transactional]
+		pub fn set_pool_emode_category(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			category_id: Option<u8>,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
+			ensure!(pool_id.is_supported_underlying_asset(), Error::<T>::PoolNotFound);
+			ensure!(
+				T::LiquidityPoolsManager::pool_exists(&pool_id),
+				Error::<T>::PoolNotFound
+			);
+			if let Some(category_id) = category_id {
+				let category = Self::emode_categories(category_id).ok_or(Error::<T>::EModeCategoryNotFound)?;
+				ensure!(
+					Self::controller_params(pool_id).collateral_factor <= category.collateral_factor,
+					Error::<T>::EModeCollateralFactorTooLow
+				);
+			}
+
+			PoolEModeCategory::<T>::mutate_exists(pool_id, |pool_category_id| *pool_category_id = category_id);
+			Self::deposit_event(Event::PoolEModeCategoryChanged(pool_id, category_id));
+			Ok(().into())
+		}
+
 		/// Set protocol interest threshold.
 		///
 		/// The dispatch origin of this call must be Administrator.
@@ -472,6 +1045,12 @@ impl<T: Config> Pallet<T> {
 		let exchange_rate: Rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id).ok()?;
 		let borrow_rate: Rate =
 			T::MinterestModelManager::calculate_borrow_interest_rate(pool_id, utilization_rate).ok()?;
+		// The interest is accrued at the maximum borrow rate while the borrow rate is clamped.
+		let borrow_rate = if Self::borrow_rate_clamping(pool_id) {
+			borrow_rate.min(Self::controller_params(pool_id).max_borrow_rate)
+		} else {
+			borrow_rate
+		};
 		// supply_interest_rate = utilization_rate * borrow_rate * (1 - protocol_interest_factor)
 		let supply_rate: Rate = Rate::one()
 			.checked_sub(&pool_interest_factor)
@@ -483,6 +1062,13 @@ impl<T: Config> Pallet<T> {
 		Some((exchange_rate, borrow_rate, supply_rate))
 	}
 
+	/// Gets the total supply of the pool in usd. The value is calculated for the current block.
+	pub fn get_pool_total_supply_in_usd(pool_id: CurrencyId) -> Option<Balance> {
+		Self::accrue_interest_rate(pool_id).ok()?;
+		let oracle_price = T::PriceSource::get_underlying_price(pool_id)?;
+		Self::pool_total_supply_in_usd(pool_id, Balance::zero(), oracle_price).ok()
+	}
+
 	/// Gets current utilization rate of the pool. The rate is calculated for the current block.
 	pub fn get_utilization_rate(pool_id: CurrencyId) -> Option<Rate> {
 		Self::accrue_interest_rate(pool_id).ok()?;
@@ -522,14 +1108,18 @@ impl<T: Config> Pallet<T> {
 
 					if has_user_supply_wrap_balance {
 						let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
-						let user_supply_in_usd =
-							T::LiquidityPoolsManager::wrapped_to_usd(user_supply_wrap, exchange_rate, oracle_price)?;
+						let user_supply_in_usd = T::LiquidityPoolsManager::wrapped_to_usd(
+							pool_id,
+							user_supply_wrap,
+							exchange_rate,
+							oracle_price,
+						)?;
 						acc_user_total_supply_in_usd += user_supply_in_usd
 					}
 					if has_user_borrow_underlying_balance {
 						let user_borrow_underlying = Self::borrow_balance_stored(&who, pool_id)?;
 						let user_borrow_in_usd =
-							T::LiquidityPoolsManager::underlying_to_usd(user_borrow_underlying, oracle_price)?;
+							T::LiquidityPoolsManager::underlying_to_usd(pool_id, user_borrow_underlying, oracle_price)?;
 						acc_user_total_borrow_in_usd += user_borrow_in_usd
 					}
 					Ok((acc_user_total_supply_in_usd, acc_user_total_borrow_in_usd))
@@ -571,13 +1161,20 @@ impl<T: Config> Pallet<T> {
 						T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 
 					let pool_supply_in_usd =
-						T::LiquidityPoolsManager::underlying_to_usd(pool_supply_underlying, oracle_price)?;
-					let pool_tvl_in_usd =
-						T::LiquidityPoolsManager::wrapped_to_usd(pool_supply_wrap, exchange_rate, oracle_price)?;
+						T::LiquidityPoolsManager::underlying_to_usd(pool_id, pool_supply_underlying, oracle_price)?;
+					let pool_tvl_in_usd = T::LiquidityPoolsManager::wrapped_to_usd(
+						pool_id,
+						pool_supply_wrap,
+						exchange_rate,
+						oracle_price,
+					)?;
 					let pool_borrow_in_usd =
-						T::LiquidityPoolsManager::underlying_to_usd(pool_data.borrowed, oracle_price)?;
-					let pool_protocol_interest_in_usd =
-						T::LiquidityPoolsManager::underlying_to_usd(pool_data.protocol_interest, oracle_price)?;
+						T::LiquidityPoolsManager::underlying_to_usd(pool_id, pool_data.borrowed, oracle_price)?;
+					let pool_protocol_interest_in_usd = T::LiquidityPoolsManager::underlying_to_usd(
+						pool_id,
+						pool_data.protocol_interest,
+						oracle_price,
+					)?;
 
 					Ok((
 						pool_total_supply_usd
@@ -608,7 +1205,7 @@ impl<T: Config> Pallet<T> {
 				let pool_collateral_factor = Self::controller_params(pool_id).collateral_factor;
 				let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 				let user_supply_usd =
-					T::LiquidityPoolsManager::underlying_to_usd(user_supply_underlying, oracle_price)?;
+					T::LiquidityPoolsManager::underlying_to_usd(pool_id, user_supply_underlying, oracle_price)?;
 				let user_collateral_in_usd = Rate::from_inner(user_supply_usd)
 					.checked_mul(&pool_collateral_factor)
 					.map(|x| x.into_inner())
@@ -714,9 +1311,9 @@ impl<T: Config> Pallet<T> {
 					let oracle_price =
 						T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 					let user_supply_in_usd =
-						T::LiquidityPoolsManager::underlying_to_usd(user_supply_underlying, oracle_price)?;
+						T::LiquidityPoolsManager::underlying_to_usd(pool_id, user_supply_underlying, oracle_price)?;
 					let user_borrow_in_usd =
-						T::LiquidityPoolsManager::underlying_to_usd(user_borrow_underlying, oracle_price)?;
+						T::LiquidityPoolsManager::underlying_to_usd(pool_id, user_borrow_underlying, oracle_price)?;
 
 					let (_, borrow_rate, supply_rate) =
 						Self::get_pool_exchange_borrow_and_supply_rates(pool_id).ok_or(Error::<T>::NumOverflow)?;
@@ -788,6 +1385,241 @@ impl<T: Config> Pallet<T> {
 
 // Private methods
 impl<T: Config> Pallet<T> {
+	/// Ensures that the origin may pause operations.
+	fn ensure_pause_origin(origin: OriginFor<T>) -> DispatchResult {
+		T::PauseGuardianOrigin::try_origin(origin)
+			.map(|_| ())
+			.or_else(|origin| T::UpdateOrigin::ensure_origin(origin).map(|_| ()))?;
+		Ok(())
+	}
+
+	/// Ensures that the origin may change a risk parameter. `EmergencyOrigin` may only make the
+	/// changes that lower the risk.
+	fn ensure_risk_parameter_origin(origin: OriginFor<T>, lowers_risk: bool) -> DispatchResult {
+		T::RiskParameterOrigin::try_origin(origin)
+			.map(|_| ())
+			.or_else(|origin| -> DispatchResult {
+				T::EmergencyOrigin::ensure_origin(origin)?;
+				ensure!(lowers_risk, BadOrigin);
+				Ok(())
+			})
+	}
+
+	/// Returns true if the new usage params don't allow anything the current ones forbid and the
+	/// isolation debt ceiling isn't raised.
+	fn is_usage_restricted(current: UsageParams, new: UsageParams) -> bool {
+		(current.can_be_collateral || !new.can_be_collateral)
+			&& (current.can_be_borrowed || !new.can_be_borrowed)
+			&& (current.borrowable_in_isolation || !new.borrowable_in_isolation)
+			&& Self::is_cap_lowered(current.isolation_debt_ceiling, new.isolation_debt_ceiling)
+	}
+
+	/// Returns true if the new cap doesn't exceed the current one. `None` means no cap.
+	fn is_cap_lowered(current_cap: Option<Balance>, new_cap: Option<Balance>) -> bool {
+		new_cap.map_or(current_cap.is_none(), |new_cap| {
+			current_cap.map_or(true, |current_cap| new_cap <= current_cap)
+		})
+	}
+
+	/// Ensures that the pause expiry, if any, is in the future.
+	fn ensure_valid_pause_expiry(until: Option<T::BlockNumber>) -> DispatchResult {
+		if let Some(until) = until {
+			ensure!(
+				until > frame_system::Pallet::<T>::block_number(),
+				Error::<T>::InvalidPauseExpiry
+			);
+		}
+		Ok(())
+	}
+
+	/// Accrues the interest in the pool and pays the keeper reward, if the interest has not been
+	/// accrued for `KeeperRewardMinAccrualAge` blocks.
+	///
+	/// Returns false if the interest has already been accrued in the current block.
+	/// The changes are reverted if the accrual fails.
+	#[transactional]
+	fn do_accrue_interest(who: &T::AccountId, pool_id: CurrencyId) -> result::Result<bool, DispatchError> {
+		let current_block_number = frame_system::Pallet::<T>::block_number();
+		let last_interest_accrued_block = Self::controller_params(pool_id).last_interest_accrued_block;
+		if current_block_number == last_interest_accrued_block {
+			return Ok(false);
+		}
+
+		<Self as ControllerManager<T::AccountId>>::accrue_interest_rate(pool_id)?;
+		Self::deposit_event(Event::InterestAccrued(pool_id));
+
+		let keeper_reward = T::KeeperReward::get();
+		let accrual_age = current_block_number.saturating_sub(last_interest_accrued_block);
+		// The reward is not paid if the MNT treasury is exhausted, the accrual still succeeds.
+		if !keeper_reward.is_zero()
+			&& accrual_age >= T::KeeperRewardMinAccrualAge::get()
+			&& T::MultiCurrency::transfer(MNT, &T::MntTokenAccountId::get(), who, keeper_reward).is_ok()
+		{
+			Self::deposit_event(Event::KeeperRewardPaid(pool_id, keeper_reward));
+		}
+		Ok(true)
+	}
+
+	/// Returns the underlying assets for which the pools are created.
+	fn existing_pools() -> Vec<CurrencyId> {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|pool_id| T::LiquidityPoolsManager::pool_exists(pool_id))
+			.collect()
+	}
+
+	/// Pauses the operation until the `until` block, or indefinitely if `until` is `None`.
+	/// An existing pause is never shortened: the later of the two expiries is kept, and no expiry
+	/// counts as the latest one.
+	fn do_pause_operation(pool_id: CurrencyId, operation: Operation, until: Option<T::BlockNumber>) {
+		let until = if Self::is_operation_allowed(pool_id, operation) {
+			until
+		} else {
+			match (Self::paused_until(pool_id, operation), until) {
+				(Some(current), Some(until)) => Some(current.max(until)),
+				_ => None,
+			}
+		};
+
+		PauseKeepers::<T>::mutate(pool_id, |pool| match operation {
+			Operation::Deposit => pool.deposit_paused = true,
+			Operation::Redeem => pool.redeem_paused = true,
+			Operation::Borrow => pool.borrow_paused = true,
+			Operation::Repay => pool.repay_paused = true,
+			Operation::Transfer => pool.transfer_paused = true,
+		});
+		PausedUntil::<T>::mutate(pool_id, operation, |paused_until| *paused_until = until);
+
+		Self::deposit_event(Event::OperationIsPaused(pool_id, operation));
+	}
+
+	fn do_resume_operation(pool_id: CurrencyId, operation: Operation) {
+		PauseKeepers::<T>::mutate(pool_id, |pool| match operation {
+			Operation::Deposit => pool.deposit_paused = false,
+			Operation::Redeem => pool.redeem_paused = false,
+			Operation::Borrow => pool.borrow_paused = false,
+			Operation::Repay => pool.repay_paused = false,
+			Operation::Transfer => pool.transfer_paused = false,
+		});
+		PausedUntil::<T>::remove(pool_id, operation);
+
+		Self::deposit_event(Event::OperationIsUnPaused(pool_id, operation));
+	}
+
+	/// Calculates the total supply of the pool in usd after `deposit_amount` of the underlying
+	/// asset is deposited:
+	/// `pool_total_supply_in_usd = (pool_supply_wrap * exchange_rate + deposit_amount) *
+	/// oracle_price`
+	fn pool_total_supply_in_usd(pool_id: CurrencyId, deposit_amount: Balance, oracle_price: Price) -> BalanceResult {
+		let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		let pool_supply_wrap = T::MultiCurrency::total_issuance(wrapped_id);
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
+		let pool_supply_underlying = T::LiquidityPoolsManager::wrapped_to_underlying(pool_supply_wrap, exchange_rate)?;
+
+		let new_pool_supply_underlying = pool_supply_underlying
+			.checked_add(deposit_amount)
+			.ok_or(Error::<T>::BalanceOverflow)?;
+		T::LiquidityPoolsManager::underlying_to_usd(pool_id, new_pool_supply_underlying, oracle_price)
+	}
+
+	/// Returns the isolated pool the user has enabled as collateral, if any.
+	fn get_isolated_collateral(who: &T::AccountId) -> Option<CurrencyId> {
+		Self::existing_pools().into_iter().find(|&pool_id| {
+			Self::usage_params(pool_id).isolation_debt_ceiling.is_some()
+				&& T::LiquidityPoolsManager::is_pool_collateral(who, pool_id)
+		})
+	}
+
+	/// Converts the amount of the underlying asset to usd using the current oracle price.
+	fn underlying_amount_to_usd(underlying_asset: CurrencyId, amount: Balance) -> BalanceResult {
+		let oracle_price =
+			T::PriceSource::get_underlying_price(underlying_asset).ok_or(Error::<T>::InvalidFeedPrice)?;
+		T::LiquidityPoolsManager::underlying_to_usd(underlying_asset, amount, oracle_price)
+	}
+
+	/// Checks the isolation mode restrictions, if the user borrows against an isolated collateral.
+	fn isolation_mode_borrow_allowed(
+		underlying_asset: CurrencyId,
+		who: &T::AccountId,
+		borrow_amount: Balance,
+	) -> DispatchResult {
+		let isolated_pool_id = match Self::get_isolated_collateral(who) {
+			Some(pool_id) => pool_id,
+			None => return Ok(()),
+		};
+		ensure!(
+			Self::usage_params(underlying_asset).borrowable_in_isolation,
+			Error::<T>::NotBorrowableInIsolation
+		);
+
+		let debt_ceiling = Self::usage_params(isolated_pool_id)
+			.isolation_debt_ceiling
+			.unwrap_or_else(Balance::zero);
+		let new_total_debt = Self::underlying_amount_to_usd(underlying_asset, borrow_amount)?
+			.checked_add(Self::isolation_mode_total_debt(isolated_pool_id))
+			.ok_or(Error::<T>::BalanceOverflow)?;
+		ensure!(new_total_debt <= debt_ceiling, Error::<T>::IsolationDebtCeilingReached);
+		Ok(())
+	}
+
+	/// Returns the e-mode category parameters applied to the user for the pool, if the user
+	/// opted in to the category of the pool.
+	fn get_user_emode_category_for_pool(who: &T::AccountId, pool_id: CurrencyId) -> Option<EModeCategory> {
+		Self::user_emode_category(who)
+			.filter(|&category_id| Self::pool_emode_category(pool_id) == Some(category_id))
+			.and_then(Self::emode_categories)
+	}
+
+	/// Checks that the pool belongs to the e-mode category of the user, if the user opted in to
+	/// e-mode.
+	fn ensure_pool_in_user_emode_category(who: &T::AccountId, pool_id: CurrencyId) -> DispatchResult {
+		if let Some(category_id) = Self::user_emode_category(who) {
+			ensure!(
+				Self::pool_emode_category(pool_id) == Some(category_id),
+				Error::<T>::EModeCategoryMismatch
+			);
+		}
+		Ok(())
+	}
+
+	/// Checks if the borrow rate given by the interest rate model for the utilization of the pool
+	/// after `borrow_amount` is borrowed exceeds the maximum borrow rate.
+	fn is_borrow_rate_too_high(pool_id: CurrencyId, borrow_amount: Balance) -> Result<bool, DispatchError> {
+		let pool_supply_underlying =
+			T::LiquidityPoolsManager::get_pool_available_liquidity(pool_id).saturating_sub(borrow_amount);
+		let pool_data = T::LiquidityPoolsManager::get_pool_data(pool_id);
+		let pool_borrow_underlying = pool_data
+			.borrowed
+			.checked_add(borrow_amount)
+			.ok_or(Error::<T>::BalanceOverflow)?;
+		let utilization_rate = Self::calculate_utilization_rate(
+			pool_supply_underlying,
+			pool_borrow_underlying,
+			pool_data.protocol_interest,
+		)?;
+		let borrow_rate = T::MinterestModelManager::calculate_borrow_interest_rate(pool_id, utilization_rate)?;
+
+		Ok(borrow_rate > Self::controller_params(pool_id).max_borrow_rate)
+	}
+
+	/// Checks if supply cap is reached.
+	/// While the price feed is stale the last accepted price of the underlying asset is used,
+	/// so deposits keep working. Returns `InvalidFeedPrice` only if the asset has never been
+	/// priced.
+	///
+	/// Return true if pool supply will exceed supply cap, otherwise false.
+	fn is_supply_cap_reached(pool_id: CurrencyId, deposit_amount: Balance) -> Result<bool, DispatchError> {
+		if let Some(supply_cap) = Self::controller_params(pool_id).supply_cap {
+			let oracle_price =
+				T::PriceSource::get_last_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+			let new_supply_balance_in_usd = Self::pool_total_supply_in_usd(pool_id, deposit_amount, oracle_price)?;
+
+			Ok(new_supply_balance_in_usd >= supply_cap)
+		} else {
+			Ok(false)
+		}
+	}
+
 	/// Checks if borrow cap is reached.
 	///
 	/// Return true if pool borrow underlying will exceed borrow cap, otherwise false.
@@ -801,7 +1633,7 @@ impl<T: Config> Pallet<T> {
 				.checked_add(borrow_amount)
 				.ok_or(Error::<T>::BalanceOverflow)?;
 			let new_borrow_balance_in_usd =
-				T::LiquidityPoolsManager::underlying_to_usd(new_pool_borrows, oracle_price)?;
+				T::LiquidityPoolsManager::underlying_to_usd(pool_id, new_pool_borrows, oracle_price)?;
 
 			Ok(new_borrow_balance_in_usd >= borrow_cap)
 		} else {
@@ -906,6 +1738,28 @@ impl<T: Config> Pallet<T> {
 		Ok(interest_factor)
 	}
 
+	/// Calculates the compound interest factor.
+	/// - `current_borrow_interest_rate`: Current interest rate that users pay for lending assets.
+	/// - `block_delta`: The number of blocks elapsed since the last accrual.
+	///
+	/// returns `interest_factor = (1 + current_borrow_interest_rate) ^ block_delta - 1`.
+	pub fn calculate_compound_interest_factor(
+		current_borrow_interest_rate: Rate,
+		block_delta: T::BlockNumber,
+	) -> RateResult {
+		let block_delta_as_usize = TryInto::<usize>::try_into(block_delta)
+			.ok()
+			.expect("blockchain will not exceed 2^32 blocks; qed");
+
+		let interest_factor = Rate::one()
+			.checked_add(&current_borrow_interest_rate)
+			.and_then(|base| checked_pow(base, block_delta_as_usize))
+			.and_then(|v| v.checked_sub(&Rate::one()))
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		Ok(interest_factor)
+	}
+
 	fn is_valid_max_borrow_rate(max_borrow_rate: Rate) -> bool {
 		!max_borrow_rate.is_zero()
 	}
@@ -920,6 +1774,20 @@ impl<T: Config> Pallet<T> {
 			None => true,
 		}
 	}
+
+	fn is_valid_supply_cap(supply_cap: Option<Balance>) -> bool {
+		match supply_cap {
+			Some(cap) => cap <= T::MaxSupplyCap::get(),
+			None => true,
+		}
+	}
+
+	fn is_valid_emode_category(category: EModeCategory) -> bool {
+		Self::is_valid_collateral_factor(category.collateral_factor)
+			&& Rate::one()
+				.checked_sub(&category.collateral_factor)
+				.map_or(false, |max_threshold| category.liquidation_threshold <= max_threshold)
+	}
 }
 
 impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
@@ -955,6 +1823,8 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 				collateral_factor,
 				borrow_cap: None,
 				protocol_interest_threshold,
+				supply_cap: None,
+				interest_accrual_mode: InterestAccrualMode::Simple,
 			},
 		);
 		PauseKeepers::<T>::insert(
@@ -1008,11 +1878,19 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 			// Read the balances and exchange rate from the cToken
 			let user_borrow_underlying = Self::borrow_balance_stored(account, underlying_asset)?;
 			let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(underlying_asset)?;
-			let collateral_factor = Self::controller_params(underlying_asset).collateral_factor;
+			// The users in e-mode get the collateral factor of their category.
+			let collateral_factor = Self::get_user_emode_category_for_pool(account, underlying_asset).map_or_else(
+				|| Self::controller_params(underlying_asset).collateral_factor,
+				|category| category.collateral_factor,
+			);
 
-			// Get the normalized price of the asset.
+			// Get the normalized price of the asset. The price of the smallest unit of the asset
+			// is converted to the USD decimals, so that assets with different decimals can be summed up.
 			let oracle_price =
 				T::PriceSource::get_underlying_price(underlying_asset).ok_or(Error::<T>::InvalidFeedPrice)?;
+			let oracle_price = underlying_asset
+				.normalize_price(oracle_price)
+				.ok_or(Error::<T>::NumOverflow)?;
 
 			// Pre-compute a conversion factor from tokens -> dollars (normalized price value)
 			// tokens_to_denom = collateral_factor * exchange_rate * oracle_price
@@ -1021,7 +1899,9 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 				.and_then(|v| v.checked_mul(&oracle_price))
 				.ok_or(Error::<T>::NumOverflow)?;
 
-			if T::LiquidityPoolsManager::is_pool_collateral(&account, underlying_asset) {
+			if T::LiquidityPoolsManager::is_pool_collateral(&account, underlying_asset)
+				&& Self::usage_params(underlying_asset).can_be_collateral
+			{
 				let user_supply_wrap = T::MultiCurrency::free_balance(asset, account);
 
 				// user_total_collateral += tokens_to_denom * user_supply_wrap
@@ -1096,28 +1976,43 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 		let ControllerData {
 			max_borrow_rate,
 			protocol_interest_factor: pool_interest_factor,
+			interest_accrual_mode,
 			..
 		} = Self::controller_params(underlying_asset);
 
-		ensure!(
-			pool_borrow_interest_rate <= max_borrow_rate,
-			Error::<T>::BorrowRateTooHigh
-		);
+		// In the clamping mode the interest is accrued at the maximum borrow rate, so that the pool
+		// stays usable. Only new borrows are blocked until the rate goes down.
+		let pool_borrow_interest_rate = if pool_borrow_interest_rate > max_borrow_rate {
+			ensure!(
+				Self::borrow_rate_clamping(underlying_asset),
+				Error::<T>::BorrowRateTooHigh
+			);
+			Self::deposit_event(Event::BorrowRateClamped(underlying_asset, pool_borrow_interest_rate));
+			max_borrow_rate
+		} else {
+			pool_borrow_interest_rate
+		};
 
 		let block_delta = Self::calculate_block_delta(current_block_number, accrual_block_number_previous)?;
 
 		/*
 		Calculate the interest accumulated into borrows and protocol interest and the new index:
-			*  simple_interest_factor = pool_borrow_interest_rate * block_delta
-			*  pool_interest_accumulated = simple_interest_factor * pool_borrow_underlying
+			*  interest_factor = pool_borrow_interest_rate * block_delta (simple interest)
+			   or (1 + pool_borrow_interest_rate) ^ block_delta - 1 (compound interest)
+			*  pool_interest_accumulated = interest_factor * pool_borrow_underlying
 			*  updated_pool_borrow_underlying = pool_interest_accumulated + pool_borrow_underlying
 			*  updated_pool_protocol_interest = pool_interest_accumulated * pool_interest_factor + pool_interest_underlying
-			*  updated_pool_borrow_index = simpleInterest_factor * pool_borrow_index + pool_borrow_index
+			*  updated_pool_borrow_index = interest_factor * pool_borrow_index + pool_borrow_index
 		*/
 
-		let simple_interest_factor = Self::calculate_interest_factor(pool_borrow_interest_rate, block_delta)?;
+		let interest_factor = match interest_accrual_mode {
+			InterestAccrualMode::Simple => Self::calculate_interest_factor(pool_borrow_interest_rate, block_delta)?,
+			InterestAccrualMode::Compound => {
+				Self::calculate_compound_interest_factor(pool_borrow_interest_rate, block_delta)?
+			}
+		};
 		let pool_interest_accumulated = Rate::from_inner(pool_data.borrowed)
-			.checked_mul(&simple_interest_factor)
+			.checked_mul(&interest_factor)
 			.map(|x| x.into_inner())
 			.ok_or(Error::<T>::BalanceOverflow)?;
 		let updated_pool_borrow_underlying = pool_interest_accumulated
@@ -1129,7 +2024,7 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 			pool_interest_factor,
 		)
 		.map_err(|_| Error::<T>::ProtocolInterestOverflow)?;
-		let updated_borrow_index: Rate = simple_interest_factor
+		let updated_borrow_index: Rate = interest_factor
 			.checked_mul(&pool_data.borrow_index)
 			.and_then(|v| v.checked_add(&pool_data.borrow_index))
 			.ok_or(Error::<T>::NumOverflow)?;
@@ -1162,6 +2057,16 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 		}
 	}
 
+	/// Pauses a specific operation on a pool.
+	fn pause_operation(pool_id: CurrencyId, operation: Operation) {
+		Self::do_pause_operation(pool_id, operation, None)
+	}
+
+	/// Resumes a specific operation on a pool.
+	fn resume_operation(pool_id: CurrencyId, operation: Operation) {
+		Self::do_resume_operation(pool_id, operation)
+	}
+
 	/// Checks if the account should be allowed to redeem tokens in the given pool.
 	///
 	/// - `underlying_asset` - The CurrencyId to verify the redeem against.
@@ -1181,6 +2086,109 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	/// Checks if the underlying asset can be deposited into the given pool.
+	/// Pools with a supply cap are checked against the last accepted price of the underlying
+	/// asset, if there is no fresh one.
+	///
+	/// - `underlying_asset` - The CurrencyId to verify the deposit against.
+	/// - `deposit_amount` - The amount of underlying assets the account would deposit.
+	///
+	/// Return Ok if the deposit is allowed.
+	fn deposit_allowed(underlying_asset: CurrencyId, deposit_amount: Balance) -> DispatchResult {
+		let supply_cap_reached = Self::is_supply_cap_reached(underlying_asset, deposit_amount)?;
+		ensure!(!supply_cap_reached, Error::<T>::SupplyCapReached);
+		Ok(())
+	}
+
+	/// Checks if the account should be allowed to enable the pool as collateral.
+	///
+	/// - `pool_id` - The CurrencyId of the pool to be enabled as collateral.
+	/// - `who` - The account which would enable the pool as collateral.
+	///
+	/// Return Ok if the pool can be enabled as collateral.
+	fn collateral_allowed(pool_id: CurrencyId, who: &T::AccountId) -> DispatchResult {
+		let usage_params = Self::usage_params(pool_id);
+		ensure!(usage_params.can_be_collateral, Error::<T>::CollateralNotAllowed);
+		Self::ensure_pool_in_user_emode_category(who, pool_id)?;
+
+		// An isolated collateral can only be used alone.
+		let has_other_collateral = Self::existing_pools().into_iter().any(|other_pool_id| {
+			other_pool_id != pool_id && T::LiquidityPoolsManager::is_pool_collateral(who, other_pool_id)
+		});
+		let combines_isolated_collateral = if usage_params.isolation_debt_ceiling.is_some() {
+			has_other_collateral
+		} else {
+			Self::get_isolated_collateral(who).is_some()
+		};
+		ensure!(
+			!combines_isolated_collateral,
+			Error::<T>::IsolatedCollateralCannotBeCombined
+		);
+		Ok(())
+	}
+
+	/// Records the borrow in the total debt of the isolated collateral of the borrower, if any.
+	/// The borrow is also recorded for the loan of the borrower, so that it can be released on
+	/// repay without a price.
+	fn increase_isolation_mode_debt(
+		who: &T::AccountId,
+		underlying_asset: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult {
+		if let Some(isolated_pool_id) = Self::get_isolated_collateral(who) {
+			let amount_in_usd = Self::underlying_amount_to_usd(underlying_asset, amount)?;
+			IsolationModeUserDebt::<T>::try_mutate(who, underlying_asset, |user_debt| -> DispatchResult {
+				let (recorded_pool_id, debt) = user_debt.get_or_insert((isolated_pool_id, Balance::zero()));
+				// The loan is backed by a single isolated collateral until it is repaid.
+				ensure!(
+					*recorded_pool_id == isolated_pool_id,
+					Error::<T>::IsolatedCollateralCannotBeCombined
+				);
+				*debt = debt.checked_add(amount_in_usd).ok_or(Error::<T>::BalanceOverflow)?;
+				Ok(())
+			})?;
+			IsolationModeTotalDebt::<T>::try_mutate(isolated_pool_id, |total_debt| -> DispatchResult {
+				*total_debt = total_debt
+					.checked_add(amount_in_usd)
+					.ok_or(Error::<T>::BalanceOverflow)?;
+				Ok(())
+			})?;
+		}
+		Ok(())
+	}
+
+	/// Removes the repaid or written off share of the loan from the total debt of the isolated
+	/// collateral it was recorded against, if any:
+	/// `released_debt = recorded_debt * amount / account_borrows`.
+	/// The whole recorded debt is released when the loan is repaid in full.
+	fn decrease_isolation_mode_debt(
+		who: &T::AccountId,
+		underlying_asset: CurrencyId,
+		amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult {
+		let (isolated_pool_id, debt) = match Self::isolation_mode_user_debt(who, underlying_asset) {
+			Some(user_debt) => user_debt,
+			None => return Ok(()),
+		};
+		let released_debt = if amount >= account_borrows {
+			IsolationModeUserDebt::<T>::remove(who, underlying_asset);
+			debt
+		} else {
+			let released_debt = Rate::saturating_from_rational(amount, account_borrows).saturating_mul_int(debt);
+			IsolationModeUserDebt::<T>::insert(
+				who,
+				underlying_asset,
+				(isolated_pool_id, debt.saturating_sub(released_debt)),
+			);
+			released_debt
+		};
+		IsolationModeTotalDebt::<T>::mutate(isolated_pool_id, |total_debt| {
+			*total_debt = total_debt.saturating_sub(released_debt)
+		});
+		Ok(())
+	}
+
 	/// Checks if the account should be allowed to borrow the underlying asset of the given pool.
 	///
 	/// - `underlying_asset` - The CurrencyId to verify the borrow against.
@@ -1189,6 +2197,19 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 	///
 	/// Return Ok if the borrow is allowed.
 	fn borrow_allowed(underlying_asset: CurrencyId, who: &T::AccountId, borrow_amount: Balance) -> DispatchResult {
+		ensure!(
+			Self::usage_params(underlying_asset).can_be_borrowed,
+			Error::<T>::BorrowNotAllowed
+		);
+		Self::ensure_pool_in_user_emode_category(who, underlying_asset)?;
+		Self::isolation_mode_borrow_allowed(underlying_asset, who, borrow_amount)?;
+
+		// New borrows are blocked if they would push the borrow rate above the maximum one.
+		ensure!(
+			!Self::is_borrow_rate_too_high(underlying_asset, borrow_amount)?,
+			Error::<T>::BorrowRateTooHigh
+		);
+
 		let borrow_cap_reached = Self::is_borrow_cap_reached(underlying_asset, borrow_amount)?;
 		ensure!(!borrow_cap_reached, Error::<T>::BorrowCapReached);
 
@@ -1200,8 +2221,126 @@ impl<T: Config> ControllerManager<T::AccountId> for Pallet<T> {
 		Ok(())
 	}
 
+	/// Switches the user to the e-mode category or out of e-mode.
+	///
+	/// - `who` - The account which opts in to / out of e-mode.
+	/// - `category_id` - The e-mode category ID. `None` switches the user out of e-mode.
+	///
+	/// All the collaterals and borrows of the user must belong to the category, and the user
+	/// must not have a shortfall after the switch.
+	fn set_user_emode(who: &T::AccountId, category_id: Option<u8>) -> DispatchResult {
+		let pools = Self::existing_pools();
+		if let Some(category_id) = category_id {
+			ensure!(
+				EModeCategories::<T>::contains_key(category_id),
+				Error::<T>::EModeCategoryNotFound
+			);
+			pools.iter().try_for_each(|&pool_id| -> DispatchResult {
+				let has_position = T::LiquidityPoolsManager::is_pool_collateral(who, pool_id)
+					|| !Self::borrow_balance_stored(who, pool_id)?.is_zero();
+				ensure!(
+					!has_position || Self::pool_emode_category(pool_id) == Some(category_id),
+					Error::<T>::EModeCategoryMismatch
+				);
+				Ok(())
+			})?;
+		}
+
+		UserEModeCategory::<T>::mutate_exists(who, |user_category_id| *user_category_id = category_id);
+
+		// Leaving e-mode lowers the collateral factors back to the pool values.
+		if let Some(&pool_id) = pools.first() {
+			let (_, shortfall) = Self::get_hypothetical_account_liquidity(who, pool_id, 0, 0)
+				.map_err(|_| Error::<T>::HypotheticalLiquidityCalculationError)?;
+			ensure!(shortfall.is_zero(), Error::<T>::InsufficientLiquidity);
+		}
+		Ok(())
+	}
+
+	/// Checks if the liquidator should be allowed to liquidate the borrower's loan.
+	///
+	/// - `repay_pool_id` - The CurrencyId of the pool in which the borrow is repaid.
+	/// - `collateral_pool_id` - The CurrencyId of the pool from which the collateral is seized.
+	/// - `borrower` - The account whose borrow would be liquidated.
+	/// - `repay_amount` - The amount of underlying assets the liquidator would repay.
+	///
+	/// Return Ok if the liquidation is allowed.
+	fn liquidate_borrow_allowed(
+		repay_pool_id: CurrencyId,
+		collateral_pool_id: CurrencyId,
+		borrower: &T::AccountId,
+		repay_amount: Balance,
+	) -> DispatchResult {
+		ensure!(
+			T::LiquidityPoolsManager::is_pool_collateral(&borrower, collateral_pool_id),
+			Error::<T>::PoolIsNotCollateral
+		);
+
+		let (_, shortfall) = Self::get_hypothetical_account_liquidity(&borrower, repay_pool_id, 0, 0)
+			.map_err(|_| Error::<T>::HypotheticalLiquidityCalculationError)?;
+		ensure!(!shortfall.is_zero(), Error::<T>::InsufficientShortfall);
+
+		// max_close = borrow_balance * close_factor
+		let borrow_balance = Self::borrow_balance_stored(&borrower, repay_pool_id)?;
+		let max_close = Rate::from_inner(borrow_balance)
+			.checked_mul(&T::CloseFactor::get())
+			.map(|x| x.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+		ensure!(repay_amount <= max_close, Error::<T>::TooMuchRepay);
+
+		Ok(())
+	}
+
+	/// Calculates the number of wrapped tokens of the collateral pool to be seized,
+	/// given an amount of underlying assets repaid in the borrowed pool.
+	///
+	/// - `repay_pool_id` - The CurrencyId of the pool in which the borrow is repaid.
+	/// - `collateral_pool_id` - The CurrencyId of the pool from which the collateral is seized.
+	/// - `repay_amount` - The amount of underlying assets repaid.
+	/// - `liquidation_fee` - The discount the liquidator receives on the seized collateral.
+	///
+	/// Return the number of wrapped tokens to be seized.
+	fn liquidate_calculate_seize_tokens(
+		repay_pool_id: CurrencyId,
+		collateral_pool_id: CurrencyId,
+		repay_amount: Balance,
+		liquidation_fee: Rate,
+	) -> BalanceResult {
+		let repay_price = T::PriceSource::get_underlying_price(repay_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let collateral_price =
+			T::PriceSource::get_underlying_price(collateral_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(collateral_pool_id)?;
+
+		// seize_usd = repay_amount * repay_price * (1 + liquidation_fee)
+		let liquidation_incentive = Rate::one()
+			.checked_add(&liquidation_fee)
+			.ok_or(Error::<T>::NumOverflow)?;
+		let seize_usd = Rate::from_inner(T::LiquidityPoolsManager::underlying_to_usd(
+			repay_pool_id,
+			repay_amount,
+			repay_price,
+		)?)
+		.checked_mul(&liquidation_incentive)
+		.map(|x| x.into_inner())
+		.ok_or(Error::<T>::NumOverflow)?;
+
+		// seize_tokens = seize_usd / (collateral_price * exchange_rate)
+		T::LiquidityPoolsManager::usd_to_wrapped(collateral_pool_id, seize_usd, exchange_rate, collateral_price)
+	}
+
 	/// Return minimum protocol interest needed to transfer it to liquidation pool
 	fn get_protocol_interest_threshold(pool_id: CurrencyId) -> Balance {
 		Self::controller_params(pool_id).protocol_interest_threshold
 	}
+
+	/// Return collateral factor of the pool
+	fn get_collateral_factor(pool_id: CurrencyId) -> Rate {
+		Self::controller_params(pool_id).collateral_factor
+	}
+
+	/// Return liquidation threshold of the e-mode category of the user for the pool, if the user
+	/// opted in to the category of the pool
+	fn get_user_emode_liquidation_threshold(who: &T::AccountId, pool_id: CurrencyId) -> Option<Rate> {
+		Self::get_user_emode_category_for_pool(who, pool_id).map(|category| category.liquidation_threshold)
+	}
 }
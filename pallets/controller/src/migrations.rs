@@ -0,0 +1,101 @@
+//! Storage migrations for the controller pallet.
+
+use super::*;
+use codec::DecodeAll;
+use frame_support::storage::unhashed;
+
+/// `ControllerData` as it was stored before the supply cap was added.
+#[derive(Encode, Decode)]
+struct OldControllerData<BlockNumber> {
+	last_interest_accrued_block: BlockNumber,
+	protocol_interest_factor: Rate,
+	max_borrow_rate: Rate,
+	collateral_factor: Rate,
+	borrow_cap: Option<Balance>,
+	protocol_interest_threshold: Balance,
+}
+
+/// `ControllerData` as it was stored before the interest accrual mode was added.
+#[derive(Encode, Decode)]
+struct ControllerDataWithoutAccrualMode<BlockNumber> {
+	last_interest_accrued_block: BlockNumber,
+	protocol_interest_factor: Rate,
+	max_borrow_rate: Rate,
+	collateral_factor: Rate,
+	borrow_cap: Option<Balance>,
+	protocol_interest_threshold: Balance,
+	supply_cap: Option<Balance>,
+}
+
+/// Adds an infinite `supply_cap` to the `ControllerParams` of every pool.
+/// The pools that are already stored in the new format are left untouched, so it is safe to run
+/// the migration more than once.
+pub fn migrate_controller_data_add_supply_cap<T: Config>() -> Weight {
+	let mut migrated_count: Weight = 0;
+	let pools = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset);
+	pools.iter().for_each(|&pool_id| {
+		let key = ControllerParams::<T>::hashed_key_for(pool_id);
+		let raw_data = match unhashed::get_raw(&key) {
+			Some(raw_data) => raw_data,
+			None => return,
+		};
+		if ControllerData::<T::BlockNumber>::decode_all(&raw_data).is_ok() {
+			return;
+		}
+		if let Ok(old) = OldControllerData::<T::BlockNumber>::decode_all(&raw_data) {
+			ControllerParams::<T>::insert(
+				pool_id,
+				ControllerData {
+					last_interest_accrued_block: old.last_interest_accrued_block,
+					protocol_interest_factor: old.protocol_interest_factor,
+					max_borrow_rate: old.max_borrow_rate,
+					collateral_factor: old.collateral_factor,
+					borrow_cap: old.borrow_cap,
+					protocol_interest_threshold: old.protocol_interest_threshold,
+					supply_cap: None,
+					interest_accrual_mode: InterestAccrualMode::Simple,
+				},
+			);
+			migrated_count += 1;
+		}
+	});
+
+	T::DbWeight::get().reads_writes(pools.len() as Weight, migrated_count)
+}
+
+/// Sets the simple interest accrual mode in the `ControllerParams` of every pool, so that the
+/// accrual doesn't change until the mode is switched explicitly.
+/// The pools that are already stored in the new format are left untouched, so it is safe to run
+/// the migration more than once.
+pub fn migrate_controller_data_add_interest_accrual_mode<T: Config>() -> Weight {
+	let mut migrated_count: Weight = 0;
+	let pools = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset);
+	pools.iter().for_each(|&pool_id| {
+		let key = ControllerParams::<T>::hashed_key_for(pool_id);
+		let raw_data = match unhashed::get_raw(&key) {
+			Some(raw_data) => raw_data,
+			None => return,
+		};
+		if ControllerData::<T::BlockNumber>::decode_all(&raw_data).is_ok() {
+			return;
+		}
+		if let Ok(old) = ControllerDataWithoutAccrualMode::<T::BlockNumber>::decode_all(&raw_data) {
+			ControllerParams::<T>::insert(
+				pool_id,
+				ControllerData {
+					last_interest_accrued_block: old.last_interest_accrued_block,
+					protocol_interest_factor: old.protocol_interest_factor,
+					max_borrow_rate: old.max_borrow_rate,
+					collateral_factor: old.collateral_factor,
+					borrow_cap: old.borrow_cap,
+					protocol_interest_threshold: old.protocol_interest_threshold,
+					supply_cap: old.supply_cap,
+					interest_accrual_mode: InterestAccrualMode::Simple,
+				},
+			);
+			migrated_count += 1;
+		}
+	});
+
+	T::DbWeight::get().reads_writes(pools.len() as Weight, migrated_count)
+}
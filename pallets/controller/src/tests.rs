@@ -1,7 +1,13 @@
 //! Tests for the controller module.
-use controller::{Error, Event};
-use frame_support::{assert_err, assert_noop, assert_ok};
+use codec::Encode;
+use controller::{EModeCategory, Error, Event, Releases, UsageParams, WeightInfo};
+use frame_support::{
+	assert_err, assert_noop, assert_ok,
+	storage::unhashed,
+	traits::{Get, Hooks},
+};
 pub use minterest_primitives::{Balance, CurrencyId, Interest, Operation, Rate};
+use orml_traits::MultiCurrency;
 use pallet_traits::{ControllerManager, UserCollateral};
 use sp_runtime::DispatchError::BadOrigin;
 use sp_runtime::{
@@ -236,6 +242,241 @@ fn accrue_interest_should_not_work() {
 		});
 }
 
+#[test]
+fn borrow_rate_clamping_should_work() {
+	ExtBuilderNew::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_controller_data(
+			DOT,                                              // currency_id
+			1,                                                // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),            // protocol_interest_factor
+			Rate::saturating_from_rational(1, 1_000_000_000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),            // collateral_factor
+			None,                                             // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,             // protocol_interest_threshold
+		)
+		.set_pool_balance(DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(11);
+
+			// The borrow rate (0.00000000720 per block) exceeds the maximum borrow rate.
+			assert_noop!(
+				TestController::accrue_interest_rate(DOT),
+				Error::<TestRuntime>::BorrowRateTooHigh
+			);
+
+			assert_noop!(
+				TestController::switch_borrow_rate_clamping(bob_origin(), DOT, true),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::switch_borrow_rate_clamping(alice_origin(), MDOT, true),
+				Error::<TestRuntime>::PoolNotFound
+			);
+			assert_ok!(TestController::switch_borrow_rate_clamping(alice_origin(), DOT, true));
+			let expected_event = test_engine::Event::TestController(Event::BorrowRateClampingSwitched(DOT, true));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert!(TestController::borrow_rate_clamping(DOT));
+
+			// The interest is accrued at the maximum borrow rate:
+			// borrowed = 80 + 80 * 0.000000001 * 10 = 80.0000008
+			assert_ok!(TestController::accrue_interest_rate(DOT));
+			let expected_event =
+				test_engine::Event::TestController(Event::BorrowRateClamped(DOT, Rate::from_inner(7_200_000_000)));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestPools::pools(DOT).borrowed, 80_000_000_800_000_000_000);
+			assert_eq!(
+				TestController::get_pool_exchange_borrow_and_supply_rates(DOT).map(|(_, borrow_rate, _)| borrow_rate),
+				Some(Rate::saturating_from_rational(1, 1_000_000_000))
+			);
+
+			// New borrows are blocked while the borrow rate is clamped.
+			assert_noop!(
+				TestController::borrow_allowed(DOT, &ALICE, dollars(1)),
+				Error::<TestRuntime>::BorrowRateTooHigh
+			);
+
+			// Without the clamping the rate of the interest rate model is reported.
+			assert_ok!(TestController::switch_borrow_rate_clamping(alice_origin(), DOT, false));
+			let (_, borrow_rate, _) = TestController::get_pool_exchange_borrow_and_supply_rates(DOT).unwrap();
+			assert!(borrow_rate > Rate::saturating_from_rational(1, 1_000_000_000));
+		});
+}
+
+#[test]
+fn borrow_allowed_should_check_borrow_rate_after_borrow() {
+	ExtBuilderNew::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_controller_data(
+			DOT,                                               // currency_id
+			1,                                                 // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),             // protocol_interest_factor
+			Rate::saturating_from_rational(10, 1_000_000_000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),             // collateral_factor
+			None,                                              // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,              // protocol_interest_threshold
+		)
+		.set_pool_balance(DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			// The current borrow rate (0.0000000072 per block) is below the maximum borrow rate.
+			// Utilization after borrowing 1 DOT: 81 / 100, borrow rate: 0.00000000927 per block.
+			assert_noop!(
+				TestController::borrow_allowed(DOT, &ALICE, dollars(1)),
+				Error::<TestRuntime>::InsufficientLiquidity
+			);
+			// Utilization after borrowing 10 DOT: 90 / 100, borrow rate: 0.0000000279 per block.
+			assert_noop!(
+				TestController::borrow_allowed(DOT, &ALICE, dollars(10)),
+				Error::<TestRuntime>::BorrowRateTooHigh
+			);
+		});
+}
+
+#[test]
+fn accrue_interest_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(DOT, dollars(80_u128), Rate::one(), Balance::zero())
+		.init_pool(ETH, dollars(80_u128), Rate::one(), Balance::zero())
+		.set_controller_data(
+			DOT,                                          // currency_id
+			1,                                            // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),        // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1_000_000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),        // collateral_factor
+			None,                                         // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,         // protocol_interest_threshold
+		)
+		.set_controller_data(
+			ETH,                                          // currency_id
+			1,                                            // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),        // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1_000_000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),        // collateral_factor
+			None,                                         // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,         // protocol_interest_threshold
+		)
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.set_minterest_model_params(
+			ETH,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.set_pool_balance(DOT, dollars(20_u128))
+		.set_pool_balance(ETH, dollars(20_u128))
+		.set_mnt_account_balance(dollars(3_u128))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(50);
+
+			assert_noop!(TestController::accrue_interest(Origin::none(), DOT), BadOrigin);
+			assert_noop!(
+				TestController::accrue_interest(bob_origin(), MDOT),
+				Error::<TestRuntime>::PoolNotFound
+			);
+
+			// The interest was accrued 49 blocks ago, so the keeper reward is not paid.
+			assert_ok!(TestController::accrue_interest(bob_origin(), DOT));
+			let expected_event = test_engine::Event::TestController(Event::InterestAccrued(DOT));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::controller_params(DOT).last_interest_accrued_block, 50);
+			assert!(TestPools::pools(DOT).borrowed > dollars(80_u128));
+			assert_eq!(Currencies::free_balance(MNT, &BOB), Balance::zero());
+
+			// The interest was accrued 150 blocks ago, so the keeper reward is paid.
+			System::set_block_number(200);
+			assert_ok!(TestController::accrue_interest(bob_origin(), DOT));
+			let expected_event = test_engine::Event::TestController(Event::KeeperRewardPaid(DOT, dollars(1_u128)));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(MNT, &BOB), dollars(1_u128));
+
+			// The second accrual in the same block is a no-op.
+			System::reset_events();
+			assert_ok!(TestController::accrue_interest(bob_origin(), DOT));
+			assert!(System::events().is_empty());
+			assert_eq!(Currencies::free_balance(MNT, &BOB), dollars(1_u128));
+
+			// DOT pool has already been accrued in this block, only ETH pool is processed.
+			let post_info = TestController::accrue_interest_all(bob_origin()).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::accrue_interest_all(1))
+			);
+			assert_eq!(TestController::controller_params(ETH).last_interest_accrued_block, 200);
+			assert_eq!(Currencies::free_balance(MNT, &BOB), dollars(2_u128));
+
+			// The MNT treasury has funds for one reward only, the accrual still succeeds.
+			System::set_block_number(400);
+			let post_info = TestController::accrue_interest_all(bob_origin()).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::accrue_interest_all(2))
+			);
+			assert_eq!(TestController::controller_params(DOT).last_interest_accrued_block, 400);
+			assert_eq!(TestController::controller_params(ETH).last_interest_accrued_block, 400);
+			assert_eq!(Currencies::free_balance(MNT, &BOB), dollars(3_u128));
+			assert_eq!(
+				Currencies::free_balance(MNT, &TestMntToken::get_account_id()),
+				Balance::zero()
+			);
+
+			// The failed accrual in DOT pool is reverted and reported, ETH pool is still processed.
+			assert_ok!(TestController::set_max_borrow_rate(
+				alice_origin(),
+				DOT,
+				Rate::saturating_from_rational(1, 1_000_000_000)
+			));
+			System::set_block_number(500);
+			let dot_borrowed = TestPools::pools(DOT).borrowed;
+			let post_info = TestController::accrue_interest_all(bob_origin()).unwrap();
+			assert_eq!(
+				post_info.actual_weight,
+				Some(<() as WeightInfo>::accrue_interest_all(1))
+			);
+			let expected_event = test_engine::Event::TestController(Event::InterestAccrualFailed(
+				DOT,
+				Error::<TestRuntime>::BorrowRateTooHigh.into(),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::controller_params(DOT).last_interest_accrued_block, 400);
+			assert_eq!(TestPools::pools(DOT).borrowed, dot_borrowed);
+			assert_eq!(TestController::controller_params(ETH).last_interest_accrued_block, 500);
+		});
+}
+
 #[test]
 fn calculate_block_delta_should_work() {
 	ExtBuilderNew::default().build().execute_with(|| {
@@ -267,6 +508,148 @@ fn calculate_interest_factor_should_work() {
 	});
 }
 
+#[test]
+fn calculate_compound_interest_factor_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		// interest_factor = (1 + 0.1) ^ 3 - 1 = 0.331
+		assert_eq!(
+			TestController::calculate_compound_interest_factor(Rate::saturating_from_rational(1, 10), 3),
+			Ok(Rate::saturating_from_rational(331, 1000))
+		);
+		assert_eq!(
+			TestController::calculate_compound_interest_factor(Rate::saturating_from_rational(1, 10), 0),
+			Ok(Rate::zero())
+		);
+
+		// Overflow in calculation: (1 + 1) ^ 128
+		assert_noop!(
+			TestController::calculate_compound_interest_factor(Rate::one(), 128),
+			Error::<TestRuntime>::NumOverflow
+		);
+	});
+}
+
+/// Returns the borrow index of the pool after `total_blocks` blocks, if the interest is accrued
+/// every `accrual_period` blocks with the given accrual mode.
+fn borrow_index_after_blocks(
+	interest_accrual_mode: InterestAccrualMode,
+	accrual_period: u64,
+	total_blocks: u64,
+) -> Rate {
+	let mut borrow_index = Rate::zero();
+	ExtBuilderNew::default()
+		.set_minterest_model_params(
+			DOT,                                          // currency_id
+			Rate::saturating_from_rational(8, 10),        // kink
+			Rate::saturating_from_rational(1, 1_000_000), // base_rate_per_block
+			Rate::zero(),                                 // multiplier_per_block
+			Rate::zero(),                                 // jump_multiplier_per_block
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_controller_data(
+			DOT,                                     // currency_id
+			1,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   // collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
+		)
+		.set_pool_balance(DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestController::set_interest_accrual_mode(
+				alice_origin(),
+				DOT,
+				interest_accrual_mode
+			));
+			for block_number in (1 + accrual_period..=1 + total_blocks).step_by(accrual_period as usize) {
+				System::set_block_number(block_number);
+				assert_ok!(TestController::accrue_interest_rate(DOT));
+			}
+			borrow_index = TestPools::pools(DOT).borrow_index;
+		});
+	borrow_index
+}
+
+#[test]
+fn compound_interest_should_not_depend_on_accrual_frequency() {
+	// The borrow rate is constant: 0.000001 per block.
+	let per_block = borrow_index_after_blocks(InterestAccrualMode::Compound, 1, 1_000);
+	let assert_close = |borrow_index: Rate| {
+		let difference = borrow_index.into_inner().max(per_block.into_inner())
+			- borrow_index.into_inner().min(per_block.into_inner());
+		// Per-block accrual accumulates a rounding error of at most a few units per block.
+		assert!(difference <= 2_000, "{:?} != {:?}", borrow_index, per_block);
+	};
+
+	assert_close(borrow_index_after_blocks(InterestAccrualMode::Compound, 10, 1_000));
+	assert_close(borrow_index_after_blocks(InterestAccrualMode::Compound, 1_000, 1_000));
+	// Simple interest accrued every block is the same as compound interest.
+	assert_close(borrow_index_after_blocks(InterestAccrualMode::Simple, 1, 1_000));
+
+	// Simple interest under-accrues after a long idle gap: 1 + 0.000001 * 1000 = 1.001
+	assert_eq!(
+		borrow_index_after_blocks(InterestAccrualMode::Simple, 1_000, 1_000),
+		Rate::saturating_from_rational(1_001, 1_000)
+	);
+	assert!(per_block > Rate::saturating_from_rational(1_001, 1_000));
+}
+
+#[test]
+fn set_interest_accrual_mode_should_work() {
+	ExtBuilderNew::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,              // pool_id
+			dollars(80_u128), // total_borrowed
+			Rate::one(),      // borrow_index
+			Balance::zero(),  // total_protocol_interest
+		)
+		.set_pool_balance(DOT, dollars(20_u128))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(10);
+
+			assert_noop!(
+				TestController::set_interest_accrual_mode(bob_origin(), DOT, InterestAccrualMode::Compound),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::set_interest_accrual_mode(alice_origin(), MDOT, InterestAccrualMode::Compound),
+				Error::<TestRuntime>::PoolNotFound
+			);
+
+			assert_ok!(TestController::set_interest_accrual_mode(
+				alice_origin(),
+				DOT,
+				InterestAccrualMode::Compound
+			));
+			let expected_event = test_engine::Event::TestController(Event::InterestAccrualModeChanged(
+				DOT,
+				InterestAccrualMode::Compound,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(
+				TestController::controller_params(DOT).interest_accrual_mode,
+				InterestAccrualMode::Compound
+			);
+			// The interest accrued so far is applied before the mode is switched.
+			assert_eq!(TestController::controller_params(DOT).last_interest_accrued_block, 10);
+		});
+}
+
 #[test]
 fn borrow_balance_stored_with_zero_balance_should_work() {
 	ExtBuilderNew::default()
@@ -426,23 +809,26 @@ fn get_hypothetical_account_liquidity_when_m_tokens_balance_is_zero_should_work(
 			// Set price = 2.00 USD for all assets.
 			MockPriceSource::set_underlying_price(Some(Price::from_inner(2 * DOLLARS)));
 
+			// The liquidity is calculated in USD with 18 decimals: DOT has 10 decimals and
+			// BTC has 8 decimals.
+
 			// Checking the function when called from redeem.
 			// The function should return the shortfall to a large zero.
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 5, 0),
-				Ok((0, 9))
+				Ok((0, 900_000_000))
 			);
 			// Checking the function when called from borrow.
 			// The function should return the shortfall to a large zero.
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 10),
-				Ok((0, 20))
+				Ok((0, 2_000_000_000))
 			);
 			// Checking scenario: the user tries to take a borrow in a currency which is not
 			// pool as available for collateral, and he fails.
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&BOB, BTC, 0, 10),
-				Ok((0, 20))
+				Ok((0, 200_000_000_000))
 			);
 		});
 }
@@ -458,17 +844,18 @@ fn get_hypothetical_account_liquidity_one_currency_from_redeem_should_work() {
 
 			// Checking the function when called from redeem.
 			// collateral parameter is set to false, user can't redeem.
+			// DOT amounts are converted to 18 decimals.
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 5, 0),
-				Ok((0, 9))
+				Ok((0, 900_000_000))
 			);
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 60, 0),
-				Ok((0, 108))
+				Ok((0, 10_800_000_000))
 			);
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 200, 0),
-				Ok((0, 360))
+				Ok((0, 36_000_000_000))
 			);
 		});
 }
@@ -537,9 +924,10 @@ fn get_hypothetical_account_liquidity_two_currencies_from_borrow_should_work() {
 
 			// Checking the function when called from borrow.
 			// collateral parameter for DOT and ETH pool is set to false. User can't borrow.
+			// DOT amounts are converted to 18 decimals.
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 30),
-				Ok((0, 120))
+				Ok((0, 12_000_000_000))
 			);
 
 			// Alice set collateral parameter value to true for DOT pool. Alice can borrow.
@@ -547,35 +935,53 @@ fn get_hypothetical_account_liquidity_two_currencies_from_borrow_should_work() {
 
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 50),
-				Ok((2, 0))
+				Ok((200_000_000, 0))
 			);
 			assert_eq!(
 				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 100),
-				Ok((0, 98))
+				Ok((0, 9_800_000_000))
 			);
 		});
 }
 
 #[test]
-fn get_liquidity_pool_exchange_rate_should_work() {
+fn get_hypothetical_account_liquidity_with_different_decimals_should_work() {
 	ExtBuilderNew::default()
-		.set_minterest_model_params(
-			DOT,                                                // currency_id
-			Rate::saturating_from_rational(8, 10),              // kink
-			Rate::zero(),                                       // base_rate_per_block
-			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
-			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		// ALICE deposit 1 BTC, BTC has 8 decimals.
+		.set_user_balance(ALICE, MBTC, 100_000_000)
+		.set_pool_balance(BTC, 100_000_000)
+		.init_pool(
+			BTC,             // pool_id
+			Balance::zero(), // borrowed
+			Rate::one(),     // borrow_index
+			Balance::zero(), // protocol_interest
+		)
+		.set_pool_user_data(
+			BTC,             // pool_id
+			ALICE,           // user
+			Balance::zero(), // borrowed
+			Rate::zero(),    // interest_index
+			true,            // is_collateral
+		)
+		.set_controller_data(
+			BTC,                                     // currency_id
+			0,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   //collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
 		)
+		// ETH has 18 decimals.
+		.set_pool_balance(ETH, dollars(10_u128))
 		.init_pool(
-			DOT,               // pool_id
-			dollars(300_u128), // borrowed
-			Rate::one(),       // borrow_index
-			Balance::zero(),   // protocol_interest
+			ETH,             // pool_id
+			Balance::zero(), // borrowed
+			Rate::one(),     // borrow_index
+			Balance::zero(), // protocol_interest
 		)
-		.set_pool_balance(DOT, dollars(100_u128))
-		.set_user_balance(ALICE, MDOT, dollars(125_u128))
 		.set_controller_data(
-			DOT,                                     // currency_id
+			ETH,                                     // currency_id
 			0,                                       // last_interest_accrued_block
 			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
 			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
@@ -585,16 +991,74 @@ fn get_liquidity_pool_exchange_rate_should_work() {
 		)
 		.build()
 		.execute_with(|| {
-			// exchange_rate = (100 - 0 + 300) / 125 = 3.2
+			// Set price = 2.00 USD for all assets.
+			MockPriceSource::set_underlying_price(Some(Price::from_inner(2 * DOLLARS)));
+
+			// collateral = 1 BTC * 2 USD * 0.9 = 1.8 USD
+			// borrow 0.5 ETH = 1 USD
 			assert_eq!(
-				TestController::get_pool_exchange_borrow_and_supply_rates(DOT)
-					.unwrap()
-					.0,
-				Rate::from_inner(3200000014580000000)
+				TestController::get_hypothetical_account_liquidity(&ALICE, ETH, 0, dollars(1) / 2),
+				Ok((dollars(8) / 10, 0))
 			);
-			assert_eq!(TestController::get_pool_exchange_borrow_and_supply_rates(ETH), None);
+			assert_ok!(TestController::borrow_allowed(ETH, &ALICE, dollars(1) / 2));
 
-			// Check that the exchange rate has changed. That is, the current value is returned.
+			// borrow 1 ETH = 2 USD
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, ETH, 0, dollars(1)),
+				Ok((0, dollars(2) / 10))
+			);
+			assert_noop!(
+				TestController::borrow_allowed(ETH, &ALICE, dollars(1)),
+				Error::<TestRuntime>::InsufficientLiquidity
+			);
+
+			// redeem 0.5 BTC: 0.5 BTC * 2 USD * 0.9 = 0.9 USD
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, BTC, 50_000_000, 0),
+				Ok((dollars(9) / 10, 0))
+			);
+		});
+}
+
+#[test]
+fn get_liquidity_pool_exchange_rate_should_work() {
+	ExtBuilderNew::default()
+		.set_minterest_model_params(
+			DOT,                                                // currency_id
+			Rate::saturating_from_rational(8, 10),              // kink
+			Rate::zero(),                                       // base_rate_per_block
+			Rate::saturating_from_rational(9, 1_000_000_000),   // multiplier_per_block: 0.047304 PerYear
+			Rate::saturating_from_rational(207, 1_000_000_000), // jump_multiplier_per_block: 1.09 PerYear
+		)
+		.init_pool(
+			DOT,               // pool_id
+			dollars(300_u128), // borrowed
+			Rate::one(),       // borrow_index
+			Balance::zero(),   // protocol_interest
+		)
+		.set_pool_balance(DOT, dollars(100_u128))
+		.set_user_balance(ALICE, MDOT, dollars(125_u128))
+		.set_controller_data(
+			DOT,                                     // currency_id
+			0,                                       // last_interest_accrued_block
+			Rate::saturating_from_rational(1, 10),   // protocol_interest_factor
+			Rate::saturating_from_rational(5, 1000), // max_borrow_rate
+			Rate::saturating_from_rational(9, 10),   //collateral_factor
+			None,                                    // borrow_cap
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,    // protocol_interest_threshold
+		)
+		.build()
+		.execute_with(|| {
+			// exchange_rate = (100 - 0 + 300) / 125 = 3.2
+			assert_eq!(
+				TestController::get_pool_exchange_borrow_and_supply_rates(DOT)
+					.unwrap()
+					.0,
+				Rate::from_inner(3200000014580000000)
+			);
+			assert_eq!(TestController::get_pool_exchange_borrow_and_supply_rates(ETH), None);
+
+			// Check that the exchange rate has changed. That is, the current value is returned.
 			System::set_block_number(100);
 			assert_eq!(
 				TestController::get_pool_exchange_borrow_and_supply_rates(DOT)
@@ -729,6 +1193,383 @@ fn borrow_allowed_should_work() {
 		});
 }
 
+#[test]
+fn set_usage_params_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.build()
+		.execute_with(|| {
+			let usage_params = UsageParams {
+				can_be_collateral: true,
+				can_be_borrowed: false,
+				borrowable_in_isolation: false,
+				isolation_debt_ceiling: Some(dollars(1_000)),
+			};
+			assert_eq!(TestController::usage_params(DOT), UsageParams::default());
+
+			assert_noop!(
+				TestController::set_usage_params(bob_origin(), DOT, usage_params),
+				BadOrigin
+			);
+			assert_ok!(TestController::set_usage_params(alice_origin(), DOT, usage_params));
+			assert_eq!(TestController::usage_params(DOT), usage_params);
+			let expected_event = test_engine::Event::TestController(Event::UsageParamsChanged(DOT, usage_params));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_noop!(
+				TestController::set_usage_params(
+					alice_origin(),
+					DOT,
+					UsageParams {
+						isolation_debt_ceiling: Some(dollars(1_000_001_u128)),
+						..usage_params
+					}
+				),
+				Error::<TestRuntime>::InvalidIsolationDebtCeiling
+			);
+			assert_noop!(
+				TestController::set_usage_params(alice_origin(), ETH, usage_params),
+				Error::<TestRuntime>::PoolNotFound
+			);
+		});
+}
+
+#[test]
+fn collateral_and_borrow_flags_should_work() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_60_dot()
+		.preset_alice_deposit_20_eth()
+		.build()
+		.execute_with(|| {
+			TestPools::enable_is_collateral(&ALICE, DOT);
+			let (liquidity, _) = TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0).unwrap();
+			assert!(!liquidity.is_zero());
+
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				ETH,
+				UsageParams {
+					can_be_borrowed: false,
+					..UsageParams::default()
+				}
+			));
+			assert_noop!(
+				TestController::borrow_allowed(ETH, &ALICE, dollars(1)),
+				Error::<TestRuntime>::BorrowNotAllowed
+			);
+
+			// A pool that cannot be collateral is not taken into account in the liquidity.
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				DOT,
+				UsageParams {
+					can_be_collateral: false,
+					..UsageParams::default()
+				}
+			));
+			assert_noop!(
+				TestController::collateral_allowed(DOT, &BOB),
+				Error::<TestRuntime>::CollateralNotAllowed
+			);
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0),
+				Ok((0, 0))
+			);
+		});
+}
+
+#[test]
+fn isolation_mode_should_work() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_60_dot()
+		.preset_alice_deposit_20_eth()
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				DOT,
+				UsageParams {
+					isolation_debt_ceiling: Some(dollars(10)),
+					..UsageParams::default()
+				}
+			));
+			assert_ok!(TestController::collateral_allowed(DOT, &ALICE));
+			TestPools::enable_is_collateral(&ALICE, DOT);
+
+			// An isolated collateral cannot be combined with other collaterals.
+			assert_noop!(
+				TestController::collateral_allowed(ETH, &ALICE),
+				Error::<TestRuntime>::IsolatedCollateralCannotBeCombined
+			);
+			TestPools::enable_is_collateral(&BOB, ETH);
+			assert_noop!(
+				TestController::collateral_allowed(DOT, &BOB),
+				Error::<TestRuntime>::IsolatedCollateralCannotBeCombined
+			);
+
+			// Only the approved assets can be borrowed against an isolated collateral.
+			assert_noop!(
+				TestController::borrow_allowed(ETH, &ALICE, dollars(5)),
+				Error::<TestRuntime>::NotBorrowableInIsolation
+			);
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				ETH,
+				UsageParams {
+					borrowable_in_isolation: true,
+					..UsageParams::default()
+				}
+			));
+			assert_ok!(TestController::borrow_allowed(ETH, &ALICE, dollars(5)));
+			assert_ok!(TestController::increase_isolation_mode_debt(&ALICE, ETH, dollars(5)));
+			assert_eq!(TestController::isolation_mode_total_debt(DOT), dollars(5));
+
+			// The total debt backed by the isolated collateral is limited by the ceiling.
+			assert_ok!(TestController::borrow_allowed(ETH, &ALICE, dollars(5)));
+			assert_noop!(
+				TestController::borrow_allowed(ETH, &ALICE, dollars(6)),
+				Error::<TestRuntime>::IsolationDebtCeilingReached
+			);
+
+			assert_eq!(
+				TestController::isolation_mode_user_debt(ALICE, ETH),
+				Some((DOT, dollars(5)))
+			);
+
+			// A repay releases the repaid share of the recorded debt, without a price.
+			MockPriceSource::set_underlying_price(None);
+			assert_ok!(TestController::decrease_isolation_mode_debt(
+				&ALICE,
+				ETH,
+				dollars(3),
+				dollars(5)
+			));
+			assert_eq!(TestController::isolation_mode_total_debt(DOT), dollars(2));
+			assert_eq!(
+				TestController::isolation_mode_user_debt(ALICE, ETH),
+				Some((DOT, dollars(2)))
+			);
+			MockPriceSource::set_underlying_price(Some(Price::one()));
+			assert_ok!(TestController::borrow_allowed(ETH, &ALICE, dollars(8)));
+
+			// The borrows of the users without an isolated collateral are not recorded.
+			assert_ok!(TestController::increase_isolation_mode_debt(&BOB, ETH, dollars(5)));
+			assert_eq!(TestController::isolation_mode_total_debt(DOT), dollars(2));
+			assert_eq!(TestController::isolation_mode_user_debt(BOB, ETH), None);
+
+			// The recorded debt is released in full when the loan is repaid in full, even if it has
+			// grown with the interest, or if the pool is not isolated anymore.
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				DOT,
+				UsageParams::default()
+			));
+			assert_ok!(TestController::decrease_isolation_mode_debt(
+				&ALICE,
+				ETH,
+				dollars(4),
+				dollars(4)
+			));
+			assert_eq!(TestController::isolation_mode_total_debt(DOT), Balance::zero());
+			assert_eq!(TestController::isolation_mode_user_debt(ALICE, ETH), None);
+		});
+}
+
+#[test]
+fn set_emode_category_should_work() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_60_dot()
+		.build()
+		.execute_with(|| {
+			let category = EModeCategory {
+				collateral_factor: Rate::saturating_from_rational(95, 100),
+				liquidation_threshold: Rate::saturating_from_rational(5, 100),
+			};
+			assert_ok!(TestController::set_emode_category(alice_origin(), 1, category));
+			let expected_event = test_engine::Event::TestController(Event::EModeCategoryChanged(1, category));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::emode_categories(1), Some(category));
+
+			// The liquidation threshold cannot exceed 1 - collateral_factor.
+			assert_noop!(
+				TestController::set_emode_category(
+					alice_origin(),
+					1,
+					EModeCategory {
+						liquidation_threshold: Rate::saturating_from_rational(6, 100),
+						..category
+					}
+				),
+				Error::<TestRuntime>::InvalidEModeCategory
+			);
+			assert_noop!(
+				TestController::set_emode_category(
+					alice_origin(),
+					1,
+					EModeCategory {
+						collateral_factor: Rate::zero(),
+						..category
+					}
+				),
+				Error::<TestRuntime>::InvalidEModeCategory
+			);
+			assert_noop!(TestController::set_emode_category(bob_origin(), 1, category), BadOrigin);
+
+			assert_ok!(TestController::set_pool_emode_category(alice_origin(), DOT, Some(1)));
+			let expected_event = test_engine::Event::TestController(Event::PoolEModeCategoryChanged(DOT, Some(1)));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::pool_emode_category(DOT), Some(1));
+			assert_noop!(
+				TestController::set_pool_emode_category(alice_origin(), ETH, Some(2)),
+				Error::<TestRuntime>::EModeCategoryNotFound
+			);
+
+			// The collateral factor of the category cannot be lower than the one of its pools (0.9).
+			assert_noop!(
+				TestController::set_emode_category(
+					alice_origin(),
+					1,
+					EModeCategory {
+						collateral_factor: Rate::saturating_from_rational(85, 100),
+						..category
+					}
+				),
+				Error::<TestRuntime>::EModeCollateralFactorTooLow
+			);
+			assert_noop!(
+				TestController::set_collateral_factor(alice_origin(), DOT, Rate::saturating_from_rational(96, 100)),
+				Error::<TestRuntime>::EModeCollateralFactorTooLow
+			);
+			assert_ok!(TestController::set_emode_category(
+				alice_origin(),
+				2,
+				EModeCategory {
+					collateral_factor: Rate::saturating_from_rational(85, 100),
+					liquidation_threshold: Rate::zero(),
+				}
+			));
+			assert_noop!(
+				TestController::set_pool_emode_category(alice_origin(), DOT, Some(2)),
+				Error::<TestRuntime>::EModeCollateralFactorTooLow
+			);
+
+			assert_ok!(TestController::set_pool_emode_category(alice_origin(), DOT, None));
+			assert_eq!(TestController::pool_emode_category(DOT), None);
+		});
+}
+
+#[test]
+fn emode_changes_should_apply_to_users_immediately() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_60_dot()
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestController::set_emode_category(
+				alice_origin(),
+				1,
+				EModeCategory {
+					collateral_factor: Rate::one(),
+					liquidation_threshold: Rate::zero(),
+				}
+			));
+			assert_ok!(TestController::set_pool_emode_category(alice_origin(), DOT, Some(1)));
+			TestPools::enable_is_collateral(&ALICE, DOT);
+			let (liquidity, _) = TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0).unwrap();
+			assert_ok!(TestController::set_user_emode(&ALICE, Some(1)));
+			let (emode_liquidity, _) = TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0).unwrap();
+
+			// Lowering the collateral factor of the category lowers the liquidity of its users in the
+			// same block, so a user who borrowed close to the e-mode limit becomes liquidatable.
+			assert_ok!(TestController::set_emode_category(
+				alice_origin(),
+				1,
+				EModeCategory {
+					collateral_factor: Rate::saturating_from_rational(95, 100),
+					liquidation_threshold: Rate::zero(),
+				}
+			));
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0),
+				Ok((emode_liquidity * 95 / 100, 0))
+			);
+
+			// Removing the pool from the category applies the pool collateral factor (0.9) to the
+			// users of the category in the same block.
+			assert_ok!(TestController::set_pool_emode_category(alice_origin(), DOT, None));
+			assert_eq!(TestController::user_emode_category(ALICE), Some(1));
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0),
+				Ok((liquidity, 0))
+			);
+		});
+}
+
+#[test]
+fn emode_should_work() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_60_dot()
+		.preset_alice_deposit_20_eth()
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestController::set_emode_category(
+				alice_origin(),
+				1,
+				EModeCategory {
+					collateral_factor: Rate::one(),
+					liquidation_threshold: Rate::zero(),
+				}
+			));
+			assert_ok!(TestController::set_pool_emode_category(alice_origin(), DOT, Some(1)));
+			TestPools::enable_is_collateral(&ALICE, DOT);
+			let (liquidity, _) = TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0).unwrap();
+
+			assert_noop!(
+				TestController::set_user_emode(&ALICE, Some(2)),
+				Error::<TestRuntime>::EModeCategoryNotFound
+			);
+			assert_ok!(TestController::set_user_emode(&ALICE, Some(1)));
+			assert_eq!(TestController::user_emode_category(ALICE), Some(1));
+
+			// The collateral factor of the category (1.0) is applied instead of the pool one (0.9).
+			let (emode_liquidity, _) = TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0).unwrap();
+			assert_eq!(emode_liquidity * 9, liquidity * 10);
+			assert_eq!(
+				TestController::get_user_emode_liquidation_threshold(&ALICE, DOT),
+				Some(Rate::zero())
+			);
+			assert_eq!(TestController::get_user_emode_liquidation_threshold(&ALICE, ETH), None);
+
+			// The assets outside the category cannot be used by the user in e-mode.
+			assert_noop!(
+				TestController::borrow_allowed(ETH, &ALICE, dollars(1)),
+				Error::<TestRuntime>::EModeCategoryMismatch
+			);
+			assert_noop!(
+				TestController::collateral_allowed(ETH, &ALICE),
+				Error::<TestRuntime>::EModeCategoryMismatch
+			);
+
+			// The positions of the user must belong to the category.
+			TestPools::enable_is_collateral(&BOB, ETH);
+			assert_noop!(
+				TestController::set_user_emode(&BOB, Some(1)),
+				Error::<TestRuntime>::EModeCategoryMismatch
+			);
+
+			assert_ok!(TestController::set_user_emode(&ALICE, None));
+			assert_eq!(TestController::user_emode_category(ALICE), None);
+			assert_eq!(
+				TestController::get_hypothetical_account_liquidity(&ALICE, DOT, 0, 0),
+				Ok((liquidity, 0))
+			);
+		});
+}
+
 #[test]
 fn is_operation_allowed_should_work() {
 	ExtBuilderNew::default()
@@ -1033,6 +1874,182 @@ fn resume_operation_should_work() {
 		});
 }
 
+#[test]
+fn pause_all_and_resume_all_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.init_pool(
+			KSM,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.set_pause_keeper(DOT, false)
+		.set_pause_keeper(KSM, false)
+		.build()
+		.execute_with(|| {
+			// The pause guardian can pause, but not resume.
+			assert_noop!(TestController::pause_all(dave_origin(), None), BadOrigin);
+			assert_ok!(TestController::pause_all(charlie_origin(), None));
+			let expected_event = test_engine::Event::TestController(Event::AllOperationsPaused);
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::pause_keepers(&DOT), PauseKeeper::all_paused());
+			assert_eq!(TestController::pause_keepers(&KSM), PauseKeeper::all_paused());
+			assert_noop!(TestController::resume_all(charlie_origin()), BadOrigin);
+			assert_noop!(
+				TestController::resume_operation(charlie_origin(), DOT, Operation::Deposit),
+				BadOrigin
+			);
+
+			assert_ok!(TestController::resume_all(alice_origin()));
+			let expected_event = test_engine::Event::TestController(Event::AllOperationsUnPaused);
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestController::pause_keepers(&DOT), PauseKeeper::all_unpaused());
+			assert_eq!(TestController::pause_keepers(&KSM), PauseKeeper::all_unpaused());
+
+			assert_ok!(TestController::pause_all(alice_origin(), None));
+			assert_eq!(TestController::pause_keepers(&DOT), PauseKeeper::all_paused());
+
+			assert_noop!(TestController::pause_all(bob_origin(), None), BadOrigin);
+			assert_noop!(TestController::resume_all(bob_origin()), BadOrigin);
+		});
+}
+
+#[test]
+fn pause_with_expiry_should_resume_automatically() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.set_pause_keeper(DOT, false)
+		.build()
+		.execute_with(|| {
+			assert_noop!(
+				TestController::pause_operation_until(charlie_origin(), DOT, Operation::Borrow, 1),
+				Error::<TestRuntime>::InvalidPauseExpiry
+			);
+			assert_noop!(
+				TestController::pause_all(charlie_origin(), Some(0)),
+				Error::<TestRuntime>::InvalidPauseExpiry
+			);
+
+			assert_ok!(TestController::pause_operation_until(
+				charlie_origin(),
+				DOT,
+				Operation::Borrow,
+				10
+			));
+			assert_ok!(TestController::pause_operation_until(
+				charlie_origin(),
+				DOT,
+				Operation::Deposit,
+				20
+			));
+			assert_ok!(TestController::pause_operation_until(
+				charlie_origin(),
+				DOT,
+				Operation::Redeem,
+				10
+			));
+			// Pausing again without an expiry makes the pause permanent.
+			assert_ok!(TestController::pause_operation(
+				charlie_origin(),
+				DOT,
+				Operation::Redeem
+			));
+			assert_eq!(TestController::paused_until(DOT, Operation::Borrow), Some(10));
+			assert_eq!(TestController::paused_until(DOT, Operation::Redeem), None);
+
+			TestController::on_initialize(9);
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Borrow));
+
+			TestController::on_initialize(10);
+			assert!(TestController::is_operation_allowed(DOT, Operation::Borrow));
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Deposit));
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Redeem));
+			assert_eq!(TestController::paused_until(DOT, Operation::Borrow), None);
+			let expected_event = test_engine::Event::TestController(Event::OperationIsUnPaused(DOT, Operation::Borrow));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// An explicit resume cancels the scheduled one.
+			assert_ok!(TestController::resume_operation(
+				alice_origin(),
+				DOT,
+				Operation::Deposit
+			));
+			assert_eq!(TestController::paused_until(DOT, Operation::Deposit), None);
+
+			TestController::on_initialize(20);
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Redeem));
+		});
+}
+
+#[test]
+fn pause_guardian_should_not_shorten_existing_pause() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.set_pause_keeper(DOT, false)
+		.build()
+		.execute_with(|| {
+			// Governance pauses borrowing indefinitely and deposits until block 100.
+			assert_ok!(TestController::pause_operation(alice_origin(), DOT, Operation::Borrow));
+			assert_ok!(TestController::pause_operation_until(
+				alice_origin(),
+				DOT,
+				Operation::Deposit,
+				100
+			));
+
+			// The emergency origin tries to make both pauses expire at block 5.
+			assert_ok!(TestController::pause_operation_until(
+				charlie_origin(),
+				DOT,
+				Operation::Borrow,
+				5
+			));
+			assert_ok!(TestController::pause_all(charlie_origin(), Some(5)));
+			assert_eq!(TestController::paused_until(DOT, Operation::Borrow), None);
+			assert_eq!(TestController::paused_until(DOT, Operation::Deposit), Some(100));
+			assert_eq!(TestController::paused_until(DOT, Operation::Redeem), Some(5));
+
+			TestController::on_initialize(5);
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Borrow));
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Deposit));
+			assert!(TestController::is_operation_allowed(DOT, Operation::Redeem));
+
+			// A later expiry extends the pause, and pausing without an expiry makes it indefinite.
+			assert_ok!(TestController::pause_operation_until(
+				charlie_origin(),
+				DOT,
+				Operation::Deposit,
+				200
+			));
+			assert_eq!(TestController::paused_until(DOT, Operation::Deposit), Some(200));
+			assert_ok!(TestController::pause_operation(
+				charlie_origin(),
+				DOT,
+				Operation::Deposit
+			));
+			assert_eq!(TestController::paused_until(DOT, Operation::Deposit), None);
+
+			TestController::on_initialize(200);
+			assert!(!TestController::is_operation_allowed(DOT, Operation::Deposit));
+		});
+}
+
 #[test]
 fn set_borrow_cap_should_work() {
 	ExtBuilderNew::default()
@@ -1074,6 +2091,335 @@ fn set_borrow_cap_should_work() {
 		});
 }
 
+#[test]
+fn set_supply_cap_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.build()
+		.execute_with(|| {
+			// The dispatch origin of this call must be Administrator.
+			assert_noop!(
+				TestController::set_supply_cap(bob_origin(), DOT, Some(dollars(10))),
+				BadOrigin
+			);
+
+			// ALICE set supply cap to 10.
+			assert_ok!(TestController::set_supply_cap(alice_origin(), DOT, Some(dollars(10))));
+			assert_eq!(TestController::controller_params(DOT).supply_cap, Some(dollars(10)));
+			let expected_event = test_engine::Event::TestController(Event::SupplyCapChanged(DOT, Some(dollars(10))));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// Unable to set supply cap greater than MAX_SUPPLY_CAP.
+			assert_noop!(
+				TestController::set_supply_cap(alice_origin(), DOT, Some(dollars(1_000_001_u128))),
+				Error::<TestRuntime>::InvalidSupplyCap
+			);
+			assert_noop!(
+				TestController::set_supply_cap(alice_origin(), MDOT, None),
+				Error::<TestRuntime>::PoolNotFound
+			);
+
+			// ALICE is able to remove the supply cap.
+			assert_ok!(TestController::set_supply_cap(alice_origin(), DOT, None));
+			assert_eq!(TestController::controller_params(DOT).supply_cap, None);
+		});
+}
+
+#[test]
+fn emergency_origin_should_only_lower_risk_parameters() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.build()
+		.execute_with(|| {
+			// DAVE is the emergency origin. The collateral factor can't be changed by it at all.
+			assert_noop!(
+				TestController::set_collateral_factor(dave_origin(), DOT, Rate::saturating_from_rational(4, 10)),
+				BadOrigin
+			);
+
+			// There is no borrow cap, so any cap lowers it. Removing the cap raises it.
+			assert_ok!(TestController::set_borrow_cap(dave_origin(), DOT, Some(dollars(10))));
+			assert_noop!(
+				TestController::set_borrow_cap(dave_origin(), DOT, Some(dollars(11))),
+				BadOrigin
+			);
+			assert_noop!(TestController::set_borrow_cap(dave_origin(), DOT, None), BadOrigin);
+			assert_ok!(TestController::set_borrow_cap(dave_origin(), DOT, Some(dollars(5))));
+			assert_eq!(TestController::controller_params(DOT).borrow_cap, Some(dollars(5)));
+
+			assert_ok!(TestController::set_supply_cap(dave_origin(), DOT, Some(dollars(10))));
+			assert_noop!(TestController::set_supply_cap(dave_origin(), DOT, None), BadOrigin);
+			assert_eq!(TestController::controller_params(DOT).supply_cap, Some(dollars(10)));
+
+			// Only forbidding the usage of the pool or lowering the debt ceiling lowers the risk.
+			let restricted_usage = UsageParams {
+				can_be_collateral: false,
+				can_be_borrowed: true,
+				borrowable_in_isolation: false,
+				isolation_debt_ceiling: Some(dollars(100)),
+			};
+			assert_ok!(TestController::set_usage_params(dave_origin(), DOT, restricted_usage));
+			assert_eq!(TestController::usage_params(DOT), restricted_usage);
+			assert_noop!(
+				TestController::set_usage_params(
+					dave_origin(),
+					DOT,
+					UsageParams {
+						can_be_collateral: true,
+						..restricted_usage
+					}
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::set_usage_params(
+					dave_origin(),
+					DOT,
+					UsageParams {
+						borrowable_in_isolation: true,
+						..restricted_usage
+					}
+				),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::set_usage_params(
+					dave_origin(),
+					DOT,
+					UsageParams {
+						isolation_debt_ceiling: None,
+						..restricted_usage
+					}
+				),
+				BadOrigin
+			);
+			assert_ok!(TestController::set_usage_params(
+				dave_origin(),
+				DOT,
+				UsageParams {
+					can_be_borrowed: false,
+					isolation_debt_ceiling: Some(dollars(50)),
+					..restricted_usage
+				}
+			));
+
+			// The pause guardian can't change the risk parameters.
+			assert_noop!(
+				TestController::set_borrow_cap(charlie_origin(), DOT, Some(dollars(1))),
+				BadOrigin
+			);
+
+			// The other risk parameters can't be changed by the emergency origin.
+			assert_noop!(
+				TestController::set_max_borrow_rate(dave_origin(), DOT, Rate::saturating_from_rational(1, 100)),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::switch_borrow_rate_clamping(dave_origin(), DOT, true),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::set_interest_accrual_mode(dave_origin(), DOT, InterestAccrualMode::Compound),
+				BadOrigin
+			);
+			assert_noop!(
+				TestController::set_pool_emode_category(dave_origin(), DOT, None),
+				BadOrigin
+			);
+
+			// The risk parameter origin can raise them again.
+			assert_ok!(TestController::set_borrow_cap(alice_origin(), DOT, None));
+			assert_ok!(TestController::set_supply_cap(alice_origin(), DOT, None));
+			assert_ok!(TestController::set_usage_params(
+				alice_origin(),
+				DOT,
+				UsageParams::default()
+			));
+			assert_eq!(TestController::controller_params(DOT).borrow_cap, None);
+			assert_eq!(TestController::controller_params(DOT).supply_cap, None);
+		});
+}
+
+#[test]
+fn deposit_allowed_should_check_supply_cap() {
+	ExtBuilderNew::default()
+		.preset_alice_deposit_20_eth()
+		.build()
+		.execute_with(|| {
+			assert_eq!(TestController::get_pool_total_supply_in_usd(ETH), Some(dollars(20)));
+			// No supply cap.
+			assert_ok!(TestController::deposit_allowed(ETH, dollars(1_000_000)));
+
+			assert_ok!(TestController::set_supply_cap(alice_origin(), ETH, Some(dollars(30))));
+			assert_ok!(TestController::deposit_allowed(ETH, dollars(5)));
+			assert_noop!(
+				TestController::deposit_allowed(ETH, dollars(10)),
+				Error::<TestRuntime>::SupplyCapReached
+			);
+
+			// The supply cap is calculated in usd.
+			MockPriceSource::set_underlying_price(Some(Price::saturating_from_rational(1, 2)));
+			assert_ok!(TestController::deposit_allowed(ETH, dollars(30)));
+			// Without a fresh price the capped pool is checked against the last known price.
+			MockPriceSource::set_underlying_price(None);
+			assert_ok!(TestController::deposit_allowed(ETH, dollars(35)));
+			assert_noop!(
+				TestController::deposit_allowed(ETH, dollars(45)),
+				Error::<TestRuntime>::SupplyCapReached
+			);
+			assert_ok!(TestController::set_supply_cap(alice_origin(), ETH, None));
+			assert_ok!(TestController::deposit_allowed(ETH, dollars(45)));
+		});
+}
+
+#[test]
+fn controller_data_interest_accrual_mode_migration_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.build()
+		.execute_with(|| {
+			// Emulate the storage before migration: controller data without the interest accrual mode.
+			let old_controller_data = (
+				5_u64,
+				Rate::saturating_from_rational(1, 10),
+				Rate::saturating_from_rational(5, 1000),
+				Rate::saturating_from_rational(9, 10),
+				Some(dollars(100_u128)),
+				PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+				Some(dollars(200_u128)),
+			);
+			unhashed::put_raw(
+				&controller::ControllerParams::<TestRuntime>::hashed_key_for(ETH),
+				&old_controller_data.encode(),
+			);
+			let dot_controller_data = TestController::controller_params(DOT);
+
+			// The supply cap migration doesn't touch the data it doesn't recognize.
+			controller::migrations::migrate_controller_data_add_supply_cap::<TestRuntime>();
+			controller::migrations::migrate_controller_data_add_interest_accrual_mode::<TestRuntime>();
+
+			assert_eq!(
+				TestController::controller_params(ETH),
+				ControllerData {
+					last_interest_accrued_block: 5,
+					protocol_interest_factor: Rate::saturating_from_rational(1, 10),
+					max_borrow_rate: Rate::saturating_from_rational(5, 1000),
+					collateral_factor: Rate::saturating_from_rational(9, 10),
+					borrow_cap: Some(dollars(100_u128)),
+					protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+					supply_cap: Some(dollars(200_u128)),
+					interest_accrual_mode: InterestAccrualMode::Simple,
+				}
+			);
+			// The pools in the new format are left untouched.
+			assert_eq!(TestController::controller_params(DOT), dot_controller_data);
+		});
+}
+
+#[test]
+fn controller_data_supply_cap_migration_should_work() {
+	ExtBuilderNew::default()
+		.init_pool(
+			DOT,                                  // pool_id
+			Balance::zero(),                      // borrowed
+			Rate::saturating_from_rational(2, 1), // borrow_index
+			Balance::zero(),                      // protocol_interest
+		)
+		.build()
+		.execute_with(|| {
+			// Emulate the storage before migration: controller data without the supply cap.
+			let old_controller_data = (
+				5_u64,
+				Rate::saturating_from_rational(1, 10),
+				Rate::saturating_from_rational(5, 1000),
+				Rate::saturating_from_rational(9, 10),
+				Some(dollars(100_u128)),
+				PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+			);
+			unhashed::put_raw(
+				&controller::ControllerParams::<TestRuntime>::hashed_key_for(ETH),
+				&old_controller_data.encode(),
+			);
+			let dot_controller_data = TestController::controller_params(DOT);
+
+			controller::migrations::migrate_controller_data_add_supply_cap::<TestRuntime>();
+
+			assert_eq!(
+				TestController::controller_params(ETH),
+				ControllerData {
+					last_interest_accrued_block: 5,
+					protocol_interest_factor: Rate::saturating_from_rational(1, 10),
+					max_borrow_rate: Rate::saturating_from_rational(5, 1000),
+					collateral_factor: Rate::saturating_from_rational(9, 10),
+					borrow_cap: Some(dollars(100_u128)),
+					protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+					supply_cap: None,
+					interest_accrual_mode: InterestAccrualMode::Simple,
+				}
+			);
+			// The pools in the new format are left untouched.
+			assert_eq!(TestController::controller_params(DOT), dot_controller_data);
+
+			// The second run does nothing.
+			assert_ok!(TestController::set_supply_cap(alice_origin(), DOT, Some(dollars(10))));
+			controller::migrations::migrate_controller_data_add_supply_cap::<TestRuntime>();
+			assert_eq!(TestController::controller_params(DOT).supply_cap, Some(dollars(10)));
+		});
+}
+
+#[test]
+fn controller_data_migrations_should_run_once() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		// A new chain starts with the latest storage version, so the upgrade doesn't probe the pools.
+		assert_eq!(controller::StorageVersion::<TestRuntime>::get(), Releases::V3_0_0);
+		assert_eq!(
+			TestController::on_runtime_upgrade(),
+			<TestRuntime as frame_system::Config>::DbWeight::get().reads(1)
+		);
+
+		// Emulate the storage of a chain started before the supply cap was added.
+		controller::StorageVersion::<TestRuntime>::kill();
+		let old_controller_data = (
+			5_u64,
+			Rate::saturating_from_rational(1, 10),
+			Rate::saturating_from_rational(5, 1000),
+			Rate::saturating_from_rational(9, 10),
+			Some(dollars(100_u128)),
+			PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+		);
+		let key = controller::ControllerParams::<TestRuntime>::hashed_key_for(ETH);
+		unhashed::put_raw(&key, &old_controller_data.encode());
+
+		TestController::on_runtime_upgrade();
+		assert_eq!(controller::StorageVersion::<TestRuntime>::get(), Releases::V3_0_0);
+		assert_eq!(TestController::controller_params(ETH).supply_cap, None);
+		assert_eq!(
+			TestController::controller_params(ETH).interest_accrual_mode,
+			InterestAccrualMode::Simple
+		);
+
+		// The next upgrade doesn't run the migrations again.
+		unhashed::put_raw(&key, &old_controller_data.encode());
+		TestController::on_runtime_upgrade();
+		assert_eq!(unhashed::get_raw(&key), Some(old_controller_data.encode()));
+	});
+}
+
 #[test]
 fn set_protocol_interest_threshold_should_work() {
 	ExtBuilderNew::default()
@@ -46,21 +46,38 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for controller.
 pub trait WeightInfo {
 	fn pause_operation() -> Weight;
+	fn pause_operation_until() -> Weight;
 	fn resume_operation() -> Weight;
 	fn set_protocol_interest_factor() -> Weight;
 	fn set_max_borrow_rate() -> Weight;
 	fn set_collateral_factor() -> Weight;
 	fn set_borrow_cap() -> Weight;
+	fn set_supply_cap() -> Weight;
+	fn set_usage_params() -> Weight;
+	fn set_interest_accrual_mode() -> Weight;
+	fn switch_borrow_rate_clamping() -> Weight;
+	fn accrue_interest() -> Weight;
+	fn accrue_interest_all(n: u32, ) -> Weight;
+	fn set_emode_category() -> Weight;
+	fn set_pool_emode_category() -> Weight;
 	fn set_protocol_interest_threshold() -> Weight;
+	fn pause_all() -> Weight;
+	fn resume_all() -> Weight;
+	fn on_initialize(n: u32, ) -> Weight;
 }
 
 /// Weights for controller using the Minterest node and recommended hardware.
 pub struct MinterestWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 	fn pause_operation() -> Weight {
-		(105_200_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(108_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn pause_operation_until() -> Weight {
+		(112_900_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	fn resume_operation() -> Weight {
 		(61_853_000 as Weight)
@@ -87,19 +104,83 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_supply_cap() -> Weight {
+		(108_512_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_usage_params() -> Weight {
+		(102_731_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_interest_accrual_mode() -> Weight {
+		(186_203_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn switch_borrow_rate_clamping() -> Weight {
+		(97_850_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn accrue_interest() -> Weight {
+		(214_386_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn accrue_interest_all(n: u32, ) -> Weight {
+		(8_264_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((208_517_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_emode_category() -> Weight {
+		(88_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_emode_category() -> Weight {
+		(101_574_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn set_protocol_interest_threshold() -> Weight {
 		(104_986_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn pause_all() -> Weight {
+		(812_400_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(50 as Weight))
+	}
+	fn resume_all() -> Weight {
+		(785_100_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(50 as Weight))
+	}
+	fn on_initialize(n: u32, ) -> Weight {
+		(4_150_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((30_212_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 }
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
 	fn pause_operation() -> Weight {
-		(105_200_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+		(108_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn pause_operation_until() -> Weight {
+		(112_900_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 	fn resume_operation() -> Weight {
 		(61_853_000 as Weight)
@@ -126,9 +207,68 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_supply_cap() -> Weight {
+		(108_512_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_usage_params() -> Weight {
+		(102_731_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_interest_accrual_mode() -> Weight {
+		(186_203_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn switch_borrow_rate_clamping() -> Weight {
+		(97_850_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn accrue_interest() -> Weight {
+		(214_386_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(10 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn accrue_interest_all(n: u32, ) -> Weight {
+		(8_264_000 as Weight)
+			// Standard Error: 41_000
+			.saturating_add((208_517_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((9 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_emode_category() -> Weight {
+		(88_412_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_emode_category() -> Weight {
+		(101_574_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	fn set_protocol_interest_threshold() -> Weight {
 		(104_986_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn pause_all() -> Weight {
+		(812_400_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(30 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(50 as Weight))
+	}
+	fn resume_all() -> Weight {
+		(785_100_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(30 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(50 as Weight))
+	}
+	fn on_initialize(n: u32, ) -> Weight {
+		(4_150_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((30_212_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 }
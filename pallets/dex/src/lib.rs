@@ -4,18 +4,49 @@
 //!
 //! This is a pallet for trading tokens with DeXes. May be used when balancing Liquidation pools or
 //! buying back MNT tokens for re-distribution.
+//!
+//! Trading happens against constant product (`x * y = k`) liquidity pools. Each pool is keyed by
+//! a sorted pair of currencies and holds reserves of both of them. Liquidity providers deposit
+//! both currencies in the current pool proportion and receive shares of the pool. Shares are
+//! minted as `CurrencyId::DexShare` tokens and can be transferred like any other currency.
+//! The first deposit locks `MINIMUM_LIQUIDITY` shares in the DEX account forever, so that the
+//! price of a share can't be inflated to round the deposits of later providers down to zero.
+//! A swap fee is taken from the supply amount of every trade and stays in the pool, rewarding
+//! the providers.
+//!
+//! Every pool accumulates the prices of its currencies weighted by the number of blocks they
+//! held. The accumulators are only updated when the reserves of the pool change, so idle pools
+//! cost nothing. The first change after an averaging window has lasted for `TwapWindow` blocks
+//! turns the accumulators into time-weighted average prices, which `TwapDataProvider` exposes as
+//! a price source resistant to single-block manipulation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
-use frame_support::{pallet_prelude::*, transactional, PalletId};
-use minterest_primitives::{Balance, CurrencyId};
+use frame_support::{pallet_prelude::*, traits::Time, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use minterest_primitives::{Balance, CurrencyId, Moment, Price, Rate};
 pub use module::*;
-use orml_traits::MultiCurrency;
+use orml_traits::{DataProvider, MultiCurrency};
 use pallet_traits::DEXManager;
-use sp_runtime::traits::{AccountIdConversion, Zero};
+use sp_runtime::{
+	helpers_128bit::multiply_by_rational,
+	traits::{AccountIdConversion, CheckedDiv, CheckedMul, One, Saturating, UniqueSaturatedInto, Zero},
+	FixedPointNumber,
+};
+use sp_std::{marker::PhantomData, vec, vec::Vec};
+pub use weights::WeightInfo;
 
 mod tests;
+pub mod weights;
+
+type BalanceResult = sp_std::result::Result<Balance, DispatchError>;
+
+/// Sorted pair of currencies identifying a liquidity pool.
+pub type TradingPair = (CurrencyId, CurrencyId);
+
+/// The liquidity shares locked in the DEX account on the first deposit into a pool.
+pub const MINIMUM_LIQUIDITY: Balance = 1_000;
 
 #[frame_support::pallet]
 pub mod module {
@@ -33,14 +64,44 @@ pub mod module {
 		type DexPalletId: Get<PalletId>;
 
 		#[pallet::constant]
-		/// The Dex account id.
-		type DexAccountId: Get<Self::AccountId>;
+		/// The fee taken from the supply amount of every swap.
+		type SwapFee: Get<Rate>;
+
+		#[pallet::constant]
+		/// The maximum number of currencies in a trading path.
+		type TradingPathLimit: Get<u32>;
+
+		#[pallet::constant]
+		/// The number of blocks the time-weighted average prices are calculated over.
+		type TwapWindow: Get<Self::BlockNumber>;
+
+		/// Time used to stamp the average prices.
+		type Time: Time<Moment = Moment>;
+
+		/// Weight information for the extrinsics.
+		type DexWeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
 	pub enum Error<T> {
-		/// Insufficient available dex balance.
-		InsufficientDexBalance,
+		/// Number overflow in calculation.
+		NumOverflow,
+		/// The currencies can't form a trading pair.
+		InvalidCurrencyPair,
+		/// The trading path is too short or too long.
+		InvalidTradingPathLength,
+		/// The liquidity pool doesn't have enough liquidity for the swap.
+		InsufficientLiquidity,
+		/// The target amount is less than the minimum target amount.
+		InsufficientTargetAmount,
+		/// The supply amount is greater than the maximum supply amount.
+		ExcessiveSupplyAmount,
+		/// The deposited amounts are too small to issue liquidity shares.
+		InvalidLiquidityIncrement,
+		/// The user doesn't have enough liquidity shares.
+		InsufficientShares,
+		/// Transaction with zero balance is not allowed.
+		ZeroBalanceTransaction,
 	}
 
 	#[pallet::event]
@@ -49,8 +110,40 @@ pub mod module {
 		/// Use supply currency to swap target currency. \[trader, supply_currency_id,
 		/// target_currency_id supply_currency_amount, target_currency_amount\]
 		Swap(T::AccountId, CurrencyId, CurrencyId, Balance, Balance),
+		/// Liquidity has been added to the pool. \[who, currency_id_0, amount_0, currency_id_1,
+		/// amount_1, shares_issued\]
+		AddLiquidity(T::AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
+		/// Liquidity has been removed from the pool. \[who, currency_id_0, amount_0,
+		/// currency_id_1, amount_1, shares_burned\]
+		RemoveLiquidity(T::AccountId, CurrencyId, Balance, CurrencyId, Balance, Balance),
 	}
 
+	/// Reserves of the liquidity pool, in the order of the sorted trading pair.
+	#[pallet::storage]
+	#[pallet::getter(fn liquidity_pool)]
+	pub type LiquidityPool<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Balance, Balance), ValueQuery>;
+
+	/// Cumulative prices of the first and the second currency of the trading pair and the block
+	/// of the last update. Each price is weighted by the number of blocks it held. The sums wrap
+	/// around on overflow, so only differences between them are meaningful.
+	#[pallet::storage]
+	#[pallet::getter(fn price_cumulative)]
+	pub type PriceCumulative<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, (Price, Price, T::BlockNumber), ValueQuery>;
+
+	/// Cumulative prices of the trading pair at the start of the current averaging window.
+	#[pallet::storage]
+	#[pallet::getter(fn twap_observation)]
+	pub type TwapObservation<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, (Price, Price, T::BlockNumber), OptionQuery>;
+
+	/// Time-weighted average prices of the first and the second currency of the trading pair
+	/// over the last complete averaging window, each expressed in the other currency, and the time
+	/// the window was closed.
+	#[pallet::storage]
+	#[pallet::getter(fn average_price)]
+	pub type AveragePrice<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Price, Price, Moment), OptionQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(PhantomData<T>);
 
@@ -58,62 +151,386 @@ pub mod module {
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
 
 	#[pallet::call]
-	impl<T: Config> Pallet<T> {}
+	impl<T: Config> Pallet<T> {
+		/// Add liquidity to the liquidity pool of the trading pair.
+		///
+		/// The deposited amounts follow the current pool proportion, so one of them may be
+		/// less than the maximum. The first liquidity provider sets the initial price and
+		/// `MINIMUM_LIQUIDITY` of the issued shares are locked.
+		/// - `currency_id_a`: first currency of the trading pair.
+		/// - `currency_id_b`: second currency of the trading pair.
+		/// - `max_amount_a`: maximum amount of `currency_id_a` to deposit.
+		/// - `max_amount_b`: maximum amount of `currency_id_b` to deposit.
+		#[pallet::weight(T::DexWeightInfo::add_liquidity())]
+		#[transactional]
+		pub fn add_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[pallet::compact] max_amount_a: Balance,
+			#[pallet::compact] max_amount_b: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_add_liquidity(&who, currency_id_a, currency_id_b, max_amount_a, max_amount_b)?;
+			Ok(().into())
+		}
+
+		/// Remove liquidity from the liquidity pool of the trading pair.
+		///
+		/// The provider receives both currencies in the current pool proportion.
+		/// - `currency_id_a`: first currency of the trading pair.
+		/// - `currency_id_b`: second currency of the trading pair.
+		/// - `remove_share`: amount of liquidity shares to burn.
+		#[pallet::weight(T::DexWeightInfo::remove_liquidity())]
+		#[transactional]
+		pub fn remove_liquidity(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[pallet::compact] remove_share: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_remove_liquidity(&who, currency_id_a, currency_id_b, remove_share)?;
+			Ok(().into())
+		}
+
+		/// Swap an exact amount of supply currency for as much target currency as possible.
+		/// - `path`: trading route from the supply currency to the target currency.
+		/// - `supply_amount`: exact amount of supply currency to sell.
+		/// - `min_target_amount`: minimum acceptable amount of target currency.
+		#[pallet::weight(T::DexWeightInfo::swap_with_exact_supply())]
+		#[transactional]
+		pub fn swap_with_exact_supply(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_with_exact_supply(&who, &path, supply_amount, min_target_amount)?;
+			Ok(().into())
+		}
+
+		/// Swap as little supply currency as possible for an exact amount of target currency.
+		/// - `path`: trading route from the supply currency to the target currency.
+		/// - `max_supply_amount`: maximum acceptable amount of supply currency.
+		/// - `target_amount`: exact amount of target currency to buy.
+		#[pallet::weight(T::DexWeightInfo::swap_with_exact_target())]
+		#[transactional]
+		pub fn swap_with_exact_target(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] max_supply_amount: Balance,
+			#[pallet::compact] target_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			Self::do_swap_with_exact_target(&who, &path, max_supply_amount, target_amount)?;
+			Ok(().into())
+		}
+	}
 }
 
 impl<T: Config> Pallet<T> {
-	/// Ensured atomic.
-	#[transactional]
-	fn do_swap_with_exact_supply(
+	fn do_add_liquidity(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
-		_supply_amount: Balance,
-		_min_target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		let actual_supply_amount = Balance::zero();
-		let actual_target_amount = Balance::zero();
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		max_amount_a: Balance,
+		max_amount_b: Balance,
+	) -> DispatchResult {
+		let share_currency_id = Self::dex_share_currency_id(currency_id_a, currency_id_b)?;
+		let trading_pair = Self::trading_pair(currency_id_a, currency_id_b)?;
+		ensure!(
+			!max_amount_a.is_zero() && !max_amount_b.is_zero(),
+			Error::<T>::ZeroBalanceTransaction
+		);
 
-		Self::deposit_event(Event::Swap(
+		let (max_amount_0, max_amount_1) = if currency_id_a == trading_pair.0 {
+			(max_amount_a, max_amount_b)
+		} else {
+			(max_amount_b, max_amount_a)
+		};
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		let total_shares = T::MultiCurrency::total_issuance(share_currency_id);
+
+		let is_first_deposit = total_shares.is_zero();
+		let (amount_0, amount_1, shares) = if is_first_deposit {
+			// The first liquidity provider defines the price of the pair. A part of the shares is
+			// locked forever, so that the pool can never be emptied to a dust amount of shares.
+			let shares = max_amount_0
+				.checked_sub(MINIMUM_LIQUIDITY)
+				.ok_or(Error::<T>::InvalidLiquidityIncrement)?;
+			(max_amount_0, max_amount_1, shares)
+		} else {
+			let optimal_amount_1 = Self::proportional_amount(max_amount_0, pool_1, pool_0)?;
+			let (amount_0, amount_1) = if optimal_amount_1 <= max_amount_1 {
+				(max_amount_0, optimal_amount_1)
+			} else {
+				(Self::proportional_amount(max_amount_1, pool_0, pool_1)?, max_amount_1)
+			};
+			let shares = Self::proportional_amount(amount_0, total_shares, pool_0)?;
+			(amount_0, amount_1, shares)
+		};
+		ensure!(
+			!amount_0.is_zero() && !amount_1.is_zero() && !shares.is_zero(),
+			Error::<T>::InvalidLiquidityIncrement
+		);
+
+		Self::update_price_cumulative(trading_pair);
+
+		let dex_account_id = Self::dex_account_id();
+		T::MultiCurrency::transfer(trading_pair.0, who, &dex_account_id, amount_0)?;
+		T::MultiCurrency::transfer(trading_pair.1, who, &dex_account_id, amount_1)?;
+		T::MultiCurrency::deposit(share_currency_id, who, shares)?;
+		if is_first_deposit {
+			T::MultiCurrency::deposit(share_currency_id, &dex_account_id, MINIMUM_LIQUIDITY)?;
+		}
+
+		LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+			*pool_0 = pool_0.checked_add(amount_0).ok_or(Error::<T>::NumOverflow)?;
+			*pool_1 = pool_1.checked_add(amount_1).ok_or(Error::<T>::NumOverflow)?;
+			Ok(())
+		})?;
+
+		Self::deposit_event(Event::AddLiquidity(
 			who.clone(),
-			supply_currency_id,
-			target_currency_id,
-			actual_supply_amount,
-			actual_target_amount,
+			trading_pair.0,
+			amount_0,
+			trading_pair.1,
+			amount_1,
+			shares,
 		));
 
-		Ok(actual_supply_amount)
+		Ok(())
+	}
+
+	fn do_remove_liquidity(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		remove_share: Balance,
+	) -> DispatchResult {
+		let share_currency_id = Self::dex_share_currency_id(currency_id_a, currency_id_b)?;
+		let trading_pair = Self::trading_pair(currency_id_a, currency_id_b)?;
+		ensure!(!remove_share.is_zero(), Error::<T>::ZeroBalanceTransaction);
+		ensure!(
+			T::MultiCurrency::free_balance(share_currency_id, who) >= remove_share,
+			Error::<T>::InsufficientShares
+		);
+
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		let total_shares = T::MultiCurrency::total_issuance(share_currency_id);
+		let amount_0 = Self::proportional_amount(remove_share, pool_0, total_shares)?;
+		let amount_1 = Self::proportional_amount(remove_share, pool_1, total_shares)?;
+
+		Self::update_price_cumulative(trading_pair);
+
+		let dex_account_id = Self::dex_account_id();
+		T::MultiCurrency::withdraw(share_currency_id, who, remove_share)?;
+		T::MultiCurrency::transfer(trading_pair.0, &dex_account_id, who, amount_0)?;
+		T::MultiCurrency::transfer(trading_pair.1, &dex_account_id, who, amount_1)?;
+
+		LiquidityPool::<T>::mutate(trading_pair, |(pool_0, pool_1)| {
+			*pool_0 = pool_0.saturating_sub(amount_0);
+			*pool_1 = pool_1.saturating_sub(amount_1);
+		});
+
+		Self::deposit_event(Event::RemoveLiquidity(
+			who.clone(),
+			trading_pair.0,
+			amount_0,
+			trading_pair.1,
+			amount_1,
+			remove_share,
+		));
+
+		Ok(())
 	}
 
 	/// Ensured atomic.
 	///
-	/// TODO Temporary implementation. Makes an exchange at the rate of 1:1
-	/// (for example: 1 ETH = 1 BTC)
+	/// Returns the amount of target currency received.
+	#[transactional]
+	pub fn do_swap_with_exact_supply(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> BalanceResult {
+		ensure!(!supply_amount.is_zero(), Error::<T>::ZeroBalanceTransaction);
+
+		let amounts = Self::get_target_amounts(path, supply_amount)?;
+		let target_amount = amounts[amounts.len() - 1];
+		ensure!(
+			!target_amount.is_zero() && target_amount >= min_target_amount,
+			Error::<T>::InsufficientTargetAmount
+		);
+
+		Self::do_swap(who, path, &amounts)?;
+
+		Ok(target_amount)
+	}
+
+	/// Ensured atomic.
+	///
+	/// Returns the amount of supply currency spent.
 	#[transactional]
 	pub fn do_swap_with_exact_target(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
+		path: &[CurrencyId],
 		max_supply_amount: Balance,
 		target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		let target_dex_balance = Self::get_dex_available_liquidity(target_currency_id);
-		let module_account_id = Self::dex_account_id();
+	) -> BalanceResult {
+		ensure!(!target_amount.is_zero(), Error::<T>::ZeroBalanceTransaction);
 
-		ensure!(target_dex_balance >= target_amount, Error::<T>::InsufficientDexBalance);
+		let amounts = Self::get_supply_amounts(path, target_amount)?;
+		let supply_amount = amounts[0];
+		ensure!(supply_amount <= max_supply_amount, Error::<T>::ExcessiveSupplyAmount);
+
+		Self::do_swap(who, path, &amounts)?;
+
+		Ok(supply_amount)
+	}
 
-		T::MultiCurrency::transfer(supply_currency_id, &who, &module_account_id, max_supply_amount)?;
-		T::MultiCurrency::transfer(target_currency_id, &module_account_id, &who, target_amount)?;
+	/// Moves the currencies between the trader and the DEX and updates the reserves of every
+	/// pool along the path. `amounts` are the amounts of each currency of the path.
+	fn do_swap(who: &T::AccountId, path: &[CurrencyId], amounts: &[Balance]) -> DispatchResult {
+		let dex_account_id = Self::dex_account_id();
+		let (supply_currency_id, supply_amount) = (path[0], amounts[0]);
+		let (target_currency_id, target_amount) = (path[path.len() - 1], amounts[amounts.len() - 1]);
+
+		T::MultiCurrency::transfer(supply_currency_id, who, &dex_account_id, supply_amount)?;
+		T::MultiCurrency::transfer(target_currency_id, &dex_account_id, who, target_amount)?;
+
+		path.windows(2)
+			.zip(amounts.windows(2))
+			.try_for_each(|(pair, pair_amounts)| -> DispatchResult {
+				let trading_pair = Self::trading_pair(pair[0], pair[1])?;
+				Self::update_price_cumulative(trading_pair);
+				LiquidityPool::<T>::try_mutate(trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+					let (supply_pool, target_pool) = if pair[0] == trading_pair.0 {
+						(pool_0, pool_1)
+					} else {
+						(pool_1, pool_0)
+					};
+					*supply_pool = supply_pool
+						.checked_add(pair_amounts[0])
+						.ok_or(Error::<T>::NumOverflow)?;
+					*target_pool = target_pool
+						.checked_sub(pair_amounts[1])
+						.ok_or(Error::<T>::InsufficientLiquidity)?;
+					Ok(())
+				})
+			})?;
 
 		Self::deposit_event(Event::Swap(
 			who.clone(),
 			supply_currency_id,
 			target_currency_id,
-			max_supply_amount,
+			supply_amount,
 			target_amount,
 		));
 
-		Ok(target_amount)
+		Ok(())
+	}
+}
+
+// Time-weighted average prices
+impl<T: Config> Pallet<T> {
+	/// Adds the prices held since the last update to the accumulators of the trading pair and
+	/// closes the averaging window if it has lasted long enough. Must be called before the
+	/// reserves change, so the accumulators only see the prices at the end of blocks.
+	fn update_price_cumulative(trading_pair: TradingPair) {
+		let now = frame_system::Pallet::<T>::block_number();
+		let (cumulative_0, cumulative_1) = Self::current_price_cumulative(trading_pair, now);
+		PriceCumulative::<T>::insert(trading_pair, (cumulative_0, cumulative_1, now));
+		Self::update_average_price(trading_pair, now);
+	}
+
+	/// Returns the accumulators of the trading pair extended up to the block `now` with the
+	/// current prices.
+	fn current_price_cumulative(trading_pair: TradingPair, now: T::BlockNumber) -> (Price, Price) {
+		let (cumulative_0, cumulative_1, last_updated) = Self::price_cumulative(trading_pair);
+		let elapsed: u128 = now.saturating_sub(last_updated).unique_saturated_into();
+		// The reserves are normalized, so the prices are the prices of whole tokens regardless
+		// of the decimals of the currencies.
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		let pool_0 = trading_pair.0.normalize_amount(pool_0).unwrap_or_default();
+		let pool_1 = trading_pair.1.normalize_amount(pool_1).unwrap_or_default();
+		let price_0 = Price::checked_from_rational(pool_1, pool_0).unwrap_or_default();
+		let price_1 = Price::checked_from_rational(pool_0, pool_1).unwrap_or_default();
+		let accumulate = |cumulative: Price, price: Price| {
+			Price::from_inner(
+				cumulative
+					.into_inner()
+					.wrapping_add(price.into_inner().wrapping_mul(elapsed)),
+			)
+		};
+		(accumulate(cumulative_0, price_0), accumulate(cumulative_1, price_1))
+	}
+
+	/// Calculates the average prices of the trading pair once the averaging window has lasted
+	/// for at least `TwapWindow` blocks and starts a new window.
+	fn update_average_price(trading_pair: TradingPair, now: T::BlockNumber) {
+		let (cumulative_0, cumulative_1) = Self::current_price_cumulative(trading_pair, now);
+		if let Some((start_cumulative_0, start_cumulative_1, window_start)) = Self::twap_observation(trading_pair) {
+			let window = now.saturating_sub(window_start);
+			if window < T::TwapWindow::get() || window.is_zero() {
+				return;
+			}
+			let window: u128 = window.unique_saturated_into();
+			let average = |cumulative: Price, start_cumulative: Price| {
+				Price::from_inner(cumulative.into_inner().wrapping_sub(start_cumulative.into_inner()) / window)
+			};
+			AveragePrice::<T>::insert(
+				trading_pair,
+				(
+					average(cumulative_0, start_cumulative_0),
+					average(cumulative_1, start_cumulative_1),
+					T::Time::now(),
+				),
+			);
+		}
+		TwapObservation::<T>::insert(trading_pair, (cumulative_0, cumulative_1, now));
+	}
+
+	/// Returns the time-weighted average price of the currency expressed in the quote currency.
+	pub fn get_average_price(currency_id: CurrencyId, quote_currency_id: CurrencyId) -> Option<Price> {
+		let trading_pair = Self::trading_pair(currency_id, quote_currency_id).ok()?;
+		let (average_price_0, average_price_1, _) = Self::average_price(trading_pair)?;
+		let average_price = if currency_id == trading_pair.0 {
+			average_price_0
+		} else {
+			average_price_1
+		};
+		Some(average_price).filter(|price| !price.is_zero())
+	}
+
+	/// Returns the time the average prices of the pool of two currencies were last updated.
+	pub fn get_average_price_timestamp(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Option<Moment> {
+		let trading_pair = Self::trading_pair(currency_id_a, currency_id_b).ok()?;
+		Self::average_price(trading_pair).map(|(_, _, timestamp)| timestamp)
+	}
+}
+
+/// Provides USD prices derived from the time-weighted average prices of the dex pools against
+/// the quote currency. The USD price of the quote currency itself comes from `QuoteSource`.
+pub struct TwapDataProvider<T, GetQuoteCurrencyId, QuoteSource>(PhantomData<(T, GetQuoteCurrencyId, QuoteSource)>);
+
+impl<T, GetQuoteCurrencyId, QuoteSource> DataProvider<CurrencyId, Price>
+	for TwapDataProvider<T, GetQuoteCurrencyId, QuoteSource>
+where
+	T: Config,
+	GetQuoteCurrencyId: Get<CurrencyId>,
+	QuoteSource: DataProvider<CurrencyId, Price>,
+{
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		let quote_currency_id = GetQuoteCurrencyId::get();
+		let quote_price = QuoteSource::get(&quote_currency_id)?;
+		if *currency_id == quote_currency_id {
+			return Some(quote_price);
+		}
+		Pallet::<T>::get_average_price(*currency_id, quote_currency_id)?.checked_mul(&quote_price)
 	}
 }
 
@@ -123,43 +540,157 @@ impl<T: Config> Pallet<T> {
 		T::DexPalletId::get().into_account()
 	}
 
-	/// Gets current the total amount of cash the dex has.
-	pub fn get_dex_available_liquidity(dex_id: CurrencyId) -> Balance {
-		let module_account_id = Self::dex_account_id();
-		T::MultiCurrency::free_balance(dex_id, &module_account_id)
+	/// Returns the share token of the liquidity pool of two currencies.
+	pub fn dex_share_currency_id(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+	) -> sp_std::result::Result<CurrencyId, DispatchError> {
+		CurrencyId::join_dex_share_currency_id(currency_id_a, currency_id_b)
+			.ok_or_else(|| Error::<T>::InvalidCurrencyPair.into())
+	}
+
+	/// Returns the sorted trading pair of two currencies.
+	/// Only the native currency and underlying assets are tradable.
+	pub fn trading_pair(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+	) -> sp_std::result::Result<TradingPair, DispatchError> {
+		Self::dex_share_currency_id(currency_id_a, currency_id_b)?
+			.split_dex_share_currency_id()
+			.ok_or_else(|| Error::<T>::InvalidCurrencyPair.into())
+	}
+
+	/// Returns pool reserves of supply and target currencies.
+	pub fn get_liquidity(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let trading_pair = Self::trading_pair(supply_currency_id, target_currency_id)?;
+		let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+		ensure!(
+			!pool_0.is_zero() && !pool_1.is_zero(),
+			Error::<T>::InsufficientLiquidity
+		);
+		if supply_currency_id == trading_pair.0 {
+			Ok((pool_0, pool_1))
+		} else {
+			Ok((pool_1, pool_0))
+		}
+	}
+
+	/// A path must contain from two to `TradingPathLimit` currencies without repetitions.
+	fn ensure_valid_path(path: &[CurrencyId]) -> DispatchResult {
+		ensure!(
+			path.len() >= 2 && path.len() as u32 <= T::TradingPathLimit::get(),
+			Error::<T>::InvalidTradingPathLength
+		);
+		ensure!(
+			path.iter()
+				.enumerate()
+				.all(|(i, currency_id)| !path[i + 1..].contains(currency_id)),
+			Error::<T>::InvalidCurrencyPair
+		);
+		Ok(())
+	}
+
+	/// Calculates the amounts of every currency along the path received for the exact supply
+	/// amount at the current pool reserves.
+	pub fn get_target_amounts(
+		path: &[CurrencyId],
+		supply_amount: Balance,
+	) -> sp_std::result::Result<Vec<Balance>, DispatchError> {
+		Self::ensure_valid_path(path)?;
+
+		let mut amounts = vec![supply_amount];
+		for pair in path.windows(2) {
+			let (supply_pool, target_pool) = Self::get_liquidity(pair[0], pair[1])?;
+			let target_amount = Self::get_target_amount(supply_pool, target_pool, amounts[amounts.len() - 1])?;
+			ensure!(!target_amount.is_zero(), Error::<T>::InsufficientTargetAmount);
+			amounts.push(target_amount);
+		}
+		Ok(amounts)
+	}
+
+	/// Calculates the amounts of every currency along the path required for the exact target
+	/// amount at the current pool reserves.
+	pub fn get_supply_amounts(
+		path: &[CurrencyId],
+		target_amount: Balance,
+	) -> sp_std::result::Result<Vec<Balance>, DispatchError> {
+		Self::ensure_valid_path(path)?;
+
+		let mut amounts = vec![Balance::zero(); path.len()];
+		amounts[path.len() - 1] = target_amount;
+		for i in (0..path.len() - 1).rev() {
+			let (supply_pool, target_pool) = Self::get_liquidity(path[i], path[i + 1])?;
+			amounts[i] = Self::get_supply_amount(supply_pool, target_pool, amounts[i + 1])?;
+		}
+		Ok(amounts)
+	}
+
+	/// Keeps the product of reserves constant after the fee is taken from the supply amount:
+	/// target_amount = supply_amount_with_fee * target_pool / (supply_pool + supply_amount_with_fee)
+	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> BalanceResult {
+		let supply_amount_with_fee = Rate::from_inner(supply_amount)
+			.checked_mul(&Rate::one().saturating_sub(T::SwapFee::get()))
+			.map(|x| x.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+		let denominator = supply_pool
+			.checked_add(supply_amount_with_fee)
+			.ok_or(Error::<T>::NumOverflow)?;
+		Self::proportional_amount(supply_amount_with_fee, target_pool, denominator)
+	}
+
+	/// Inverse of `get_target_amount`, rounded up in favor of the pool:
+	/// supply_amount = supply_pool * target_amount / (target_pool - target_amount) / (1 - swap_fee)
+	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> BalanceResult {
+		let denominator = target_pool
+			.checked_sub(target_amount)
+			.filter(|x| !x.is_zero())
+			.ok_or(Error::<T>::InsufficientLiquidity)?;
+		let supply_amount_with_fee = Self::proportional_amount(supply_pool, target_amount, denominator)?;
+		Rate::from_inner(supply_amount_with_fee)
+			.checked_div(&Rate::one().saturating_sub(T::SwapFee::get()))
+			.and_then(|x| x.into_inner().checked_add(Balance::one()))
+			.ok_or_else(|| Error::<T>::NumOverflow.into())
+	}
+
+	/// Calculates amount * numerator / denominator without intermediate overflow.
+	fn proportional_amount(amount: Balance, numerator: Balance, denominator: Balance) -> BalanceResult {
+		multiply_by_rational(amount, numerator, denominator).map_err(|_| Error::<T>::NumOverflow.into())
 	}
 }
 
 impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
-	fn swap_with_exact_supply(
+	fn get_target_amounts(
+		path: &[CurrencyId],
+		supply_amount: Balance,
+	) -> sp_std::result::Result<Vec<Balance>, DispatchError> {
+		Self::get_target_amounts(path, supply_amount)
+	}
+
+	fn get_supply_amounts(
+		path: &[CurrencyId],
+		target_amount: Balance,
+	) -> sp_std::result::Result<Vec<Balance>, DispatchError> {
+		Self::get_supply_amounts(path, target_amount)
+	}
+
+	fn swap_with_exact_supply_by_path(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
+		path: &[CurrencyId],
 		supply_amount: Balance,
 		min_target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		Self::do_swap_with_exact_supply(
-			who,
-			supply_currency_id,
-			target_currency_id,
-			supply_amount,
-			min_target_amount,
-		)
+	) -> BalanceResult {
+		Self::do_swap_with_exact_supply(who, path, supply_amount, min_target_amount)
 	}
 
-	fn swap_with_exact_target(
+	fn swap_with_exact_target_by_path(
 		who: &T::AccountId,
-		supply_currency_id: CurrencyId,
-		target_currency_id: CurrencyId,
+		path: &[CurrencyId],
 		max_supply_amount: Balance,
 		target_amount: Balance,
-	) -> sp_std::result::Result<Balance, DispatchError> {
-		Self::do_swap_with_exact_target(
-			who,
-			supply_currency_id,
-			target_currency_id,
-			max_supply_amount,
-			target_amount,
-		)
+	) -> BalanceResult {
+		Self::do_swap_with_exact_target(who, path, max_supply_amount, target_amount)
 	}
 }
@@ -3,106 +3,595 @@
 #![cfg(test)]
 
 use dex::{Error, Event};
-use frame_support::assert_err;
-use orml_traits::MultiCurrency;
+use frame_support::{assert_noop, assert_ok, parameter_types};
+use minterest_primitives::{currency::TokenSymbol, Price};
+use orml_traits::{DataProvider, MultiCurrency};
 use pallet_traits::{DEXManager, PoolsManager};
+use sp_runtime::{traits::Zero, FixedPointNumber};
 use test_engine::*;
 
+const DOT_ETH: CurrencyId = CurrencyId::DexShare(TokenSymbol::DOT, TokenSymbol::ETH);
+
+parameter_types! {
+	pub const QuoteCurrencyId: CurrencyId = DOT;
+}
+
+/// Quotes DOT at 20$.
+pub struct MockQuoteSource;
+impl DataProvider<CurrencyId, Price> for MockQuoteSource {
+	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		Some(Price::saturating_from_integer(20)).filter(|_| *currency_id == DOT)
+	}
+}
+
+type TestTwapDataProvider = dex::TwapDataProvider<TestRuntime, QuoteCurrencyId, MockQuoteSource>;
+
+fn dex_balance(currency_id: CurrencyId) -> Balance {
+	Currencies::free_balance(currency_id, &TestDex::dex_account_id())
+}
+
 #[test]
-fn swap_with_exact_target_should_work() {
+fn add_liquidity_should_work() {
 	ExtBuilderNew::default()
-		.set_liquidation_pool_balance(DOT, 300_000 * DOLLARS)
-		.set_liquidation_pool_balance(ETH, 400_000 * DOLLARS)
-		.set_dex_balance(DOT, 500_000 * DOLLARS)
-		.set_dex_balance(ETH, 500_000 * DOLLARS)
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 50_000 * DOLLARS)
+		.set_user_balance(BOB, DOT, 40_000 * DOLLARS)
+		.set_user_balance(BOB, ETH, 30_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
+			// The first deposit must cover the locked shares.
+			assert_noop!(
+				TestDex::add_liquidity(alice_origin(), DOT, ETH, dex::MINIMUM_LIQUIDITY, DOLLARS),
+				Error::<TestRuntime>::InvalidLiquidityIncrement
+			);
+
+			// The first liquidity provider sets the price: 1 ETH = 2 DOT.
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				ETH,
+				DOT,
+				50_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+			let expected_event = test_engine::Event::TestDex(Event::AddLiquidity(
+				ALICE,
+				DOT,
+				100_000 * DOLLARS,
+				ETH,
+				50_000 * DOLLARS,
+				100_000 * DOLLARS - dex::MINIMUM_LIQUIDITY,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
 			assert_eq!(
-				TestDex::swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
-					DOT,
-					ETH,
-					50_000 * DOLLARS,
-					50_000 * DOLLARS
-				),
-				Ok(50_000 * DOLLARS)
+				TestDex::liquidity_pool((DOT, ETH)),
+				(100_000 * DOLLARS, 50_000 * DOLLARS)
+			);
+			assert_eq!(Currencies::total_issuance(DOT_ETH), 100_000 * DOLLARS);
+			assert_eq!(
+				Currencies::free_balance(DOT_ETH, &ALICE),
+				100_000 * DOLLARS - dex::MINIMUM_LIQUIDITY
 			);
+			assert_eq!(dex_balance(DOT_ETH), dex::MINIMUM_LIQUIDITY);
 
+			// Only 20_000 ETH out of 30_000 ETH keep the pool proportion.
+			assert_ok!(TestDex::add_liquidity(
+				bob_origin(),
+				DOT,
+				ETH,
+				40_000 * DOLLARS,
+				30_000 * DOLLARS
+			));
+			assert_eq!(
+				TestDex::liquidity_pool((DOT, ETH)),
+				(140_000 * DOLLARS, 70_000 * DOLLARS)
+			);
+			assert_eq!(Currencies::total_issuance(DOT_ETH), 140_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(DOT_ETH, &BOB), 40_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 10_000 * DOLLARS);
+			assert_eq!(dex_balance(DOT), 140_000 * DOLLARS);
+			assert_eq!(dex_balance(ETH), 70_000 * DOLLARS);
+
+			assert_noop!(
+				TestDex::add_liquidity(bob_origin(), DOT, DOT, 10 * DOLLARS, 10 * DOLLARS),
+				Error::<TestRuntime>::InvalidCurrencyPair
+			);
+			assert_noop!(
+				TestDex::add_liquidity(bob_origin(), DOT, MDOT, 10 * DOLLARS, 10 * DOLLARS),
+				Error::<TestRuntime>::InvalidCurrencyPair
+			);
+			assert_noop!(
+				TestDex::add_liquidity(bob_origin(), DOT, ETH, Balance::zero(), 10 * DOLLARS),
+				Error::<TestRuntime>::ZeroBalanceTransaction
+			);
+			assert_noop!(
+				TestDex::add_liquidity(bob_origin(), DOT, ETH, 1, 1),
+				Error::<TestRuntime>::InvalidLiquidityIncrement
+			);
+		});
+}
+
+#[test]
+fn remove_liquidity_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 50_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				50_000 * DOLLARS
+			));
+
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), ETH, DOT, 40_000 * DOLLARS));
+			let expected_event = test_engine::Event::TestDex(Event::RemoveLiquidity(
+				ALICE,
+				DOT,
+				40_000 * DOLLARS,
+				ETH,
+				20_000 * DOLLARS,
+				40_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
-				250_000 * DOLLARS
+				TestDex::liquidity_pool((DOT, ETH)),
+				(60_000 * DOLLARS, 30_000 * DOLLARS)
 			);
+			assert_eq!(Currencies::total_issuance(DOT_ETH), 60_000 * DOLLARS);
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
-				450_000 * DOLLARS
+				Currencies::free_balance(DOT_ETH, &ALICE),
+				60_000 * DOLLARS - dex::MINIMUM_LIQUIDITY
+			);
+			assert_eq!(Currencies::free_balance(DOT, &ALICE), 40_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), 20_000 * DOLLARS);
+
+			assert_noop!(
+				TestDex::remove_liquidity(alice_origin(), DOT, ETH, 60_001 * DOLLARS),
+				Error::<TestRuntime>::InsufficientShares
+			);
+			assert_noop!(
+				TestDex::remove_liquidity(bob_origin(), DOT, ETH, DOLLARS),
+				Error::<TestRuntime>::InsufficientShares
+			);
+			assert_noop!(
+				TestDex::remove_liquidity(alice_origin(), DOT, ETH, Balance::zero()),
+				Error::<TestRuntime>::ZeroBalanceTransaction
 			);
 
+			// The last provider withdraws everything except the liquidity of the locked shares.
+			assert_ok!(TestDex::remove_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				60_000 * DOLLARS - dex::MINIMUM_LIQUIDITY
+			));
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
-				550_000 * DOLLARS
+				TestDex::liquidity_pool((DOT, ETH)),
+				(dex::MINIMUM_LIQUIDITY, dex::MINIMUM_LIQUIDITY / 2)
 			);
+			assert_eq!(Currencies::total_issuance(DOT_ETH), dex::MINIMUM_LIQUIDITY);
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestDex::dex_account_id()),
-				450_000 * DOLLARS
+				Currencies::free_balance(DOT, &ALICE),
+				100_000 * DOLLARS - dex::MINIMUM_LIQUIDITY
+			);
+			assert_eq!(
+				Currencies::free_balance(ETH, &ALICE),
+				50_000 * DOLLARS - dex::MINIMUM_LIQUIDITY / 2
 			);
 		});
 }
 
 #[test]
-fn do_swap_with_exact_target_should_work() {
+fn dex_shares_should_be_transferable() {
 	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 50_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				50_000 * DOLLARS
+			));
+			assert_eq!(TestDex::dex_share_currency_id(ETH, DOT), Ok(DOT_ETH));
+
+			assert_ok!(Currencies::transfer(alice_origin(), BOB, DOT_ETH, 30_000 * DOLLARS));
+			assert_eq!(
+				Currencies::free_balance(DOT_ETH, &ALICE),
+				70_000 * DOLLARS - dex::MINIMUM_LIQUIDITY
+			);
+			assert_eq!(Currencies::free_balance(DOT_ETH, &BOB), 30_000 * DOLLARS);
+
+			// The new holder of the shares can withdraw the liquidity.
+			assert_ok!(TestDex::remove_liquidity(bob_origin(), DOT, ETH, 30_000 * DOLLARS));
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 30_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 15_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(DOT_ETH, &BOB), Balance::zero());
+			assert_eq!(Currencies::total_issuance(DOT_ETH), 70_000 * DOLLARS);
+
+			assert_noop!(
+				TestDex::add_liquidity(alice_origin(), DOT, DOT_ETH, DOLLARS, DOLLARS),
+				Error::<TestRuntime>::InvalidCurrencyPair
+			);
+		});
+}
+
+#[test]
+fn swap_with_exact_supply_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 100_000 * DOLLARS)
 		.set_liquidation_pool_balance(DOT, 300_000 * DOLLARS)
-		.set_liquidation_pool_balance(ETH, 400_000 * DOLLARS)
-		.set_dex_balance(DOT, 50_000 * DOLLARS)
-		.set_dex_balance(ETH, 50_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+			let who = TestLiquidationPools::pools_account_id();
+
+			// target = 10_000 * 0.997 * 100_000 / (100_000 + 10_000 * 0.997)
+			let expected_target_amount = 9_066_108_938_801_491_315_813;
 			assert_eq!(
-				TestDex::do_swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
-					DOT,
+				TestDex::get_target_amounts(&[DOT, ETH], 10_000 * DOLLARS),
+				Ok(vec![10_000 * DOLLARS, expected_target_amount])
+			);
+
+			assert_noop!(
+				TestDex::do_swap_with_exact_supply(&who, &[DOT, ETH], 10_000 * DOLLARS, 9_100 * DOLLARS),
+				Error::<TestRuntime>::InsufficientTargetAmount
+			);
+			assert_noop!(
+				TestDex::do_swap_with_exact_supply(&who, &[DOT, KSM], 10_000 * DOLLARS, Balance::zero()),
+				Error::<TestRuntime>::InsufficientLiquidity
+			);
+
+			assert_eq!(
+				<TestDex as DEXManager<_, _, _>>::swap_with_exact_supply(
+					&who,
 					ETH,
+					DOT,
 					10_000 * DOLLARS,
+					9_000 * DOLLARS
+				),
+				Ok(expected_target_amount)
+			);
+			let expected_event = test_engine::Event::TestDex(Event::Swap(
+				who.clone(),
+				DOT,
+				ETH,
+				10_000 * DOLLARS,
+				expected_target_amount,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(Currencies::free_balance(DOT, &who), 290_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &who), expected_target_amount);
+			assert_eq!(
+				TestDex::liquidity_pool((DOT, ETH)),
+				(110_000 * DOLLARS, 100_000 * DOLLARS - expected_target_amount)
+			);
+			assert_eq!(dex_balance(DOT), 110_000 * DOLLARS);
+			assert_eq!(dex_balance(ETH), 100_000 * DOLLARS - expected_target_amount);
+		});
+}
+
+#[test]
+fn swap_with_exact_target_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 100_000 * DOLLARS)
+		.set_liquidation_pool_balance(DOT, 300_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+			let who = TestLiquidationPools::pools_account_id();
+
+			// supply = 100_000 * 10_000 / (100_000 - 10_000) / 0.997, rounded up
+			let expected_supply_amount = 11_144_544_745_347_152_568_818;
+			assert_eq!(
+				TestDex::get_supply_amounts(&[DOT, ETH], 10_000 * DOLLARS),
+				Ok(vec![expected_supply_amount, 10_000 * DOLLARS])
+			);
+
+			assert_noop!(
+				TestDex::do_swap_with_exact_target(&who, &[DOT, ETH], 11_000 * DOLLARS, 10_000 * DOLLARS),
+				Error::<TestRuntime>::ExcessiveSupplyAmount
+			);
+			assert_noop!(
+				TestDex::do_swap_with_exact_target(&who, &[DOT, ETH], 300_000 * DOLLARS, 100_000 * DOLLARS),
+				Error::<TestRuntime>::InsufficientLiquidity
+			);
+
+			// Only the actual supply amount is taken, not the maximum.
+			assert_eq!(
+				<TestDex as DEXManager<_, _, _>>::swap_with_exact_target(
+					&who,
+					DOT,
+					ETH,
+					50_000 * DOLLARS,
 					10_000 * DOLLARS
 				),
-				Ok(10_000 * DOLLARS)
+				Ok(expected_supply_amount)
 			);
 			let expected_event = test_engine::Event::TestDex(Event::Swap(
-				TestLiquidationPools::pools_account_id(),
+				who.clone(),
 				DOT,
 				ETH,
+				expected_supply_amount,
 				10_000 * DOLLARS,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(
+				Currencies::free_balance(DOT, &who),
+				300_000 * DOLLARS - expected_supply_amount
+			);
+			assert_eq!(Currencies::free_balance(ETH, &who), 10_000 * DOLLARS);
+			assert_eq!(
+				TestDex::liquidity_pool((DOT, ETH)),
+				(100_000 * DOLLARS + expected_supply_amount, 90_000 * DOLLARS)
+			);
+
+			// The fee stays in the pool, so the product of reserves grows.
+			let (dot_pool, eth_pool) = TestDex::liquidity_pool((DOT, ETH));
+			assert!(
+				sp_core::U256::from(dot_pool) * sp_core::U256::from(eth_pool)
+					> sp_core::U256::from(100_000 * DOLLARS) * sp_core::U256::from(100_000 * DOLLARS)
+			);
+		});
+}
+
+#[test]
+fn swap_extrinsics_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 100_000 * DOLLARS)
+		.set_user_balance(BOB, ETH, 20_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+
+			assert_ok!(TestDex::swap_with_exact_supply(
+				bob_origin(),
+				vec![ETH, DOT],
+				10_000 * DOLLARS,
+				9_000 * DOLLARS
+			));
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 10_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 9_066_108_938_801_491_315_813);
+
+			assert_ok!(TestDex::swap_with_exact_target(
+				bob_origin(),
+				vec![ETH, DOT],
+				10_000 * DOLLARS,
+				DOLLARS
+			));
+			assert_eq!(
+				Currencies::free_balance(DOT, &BOB),
+				9_066_108_938_801_491_315_813 + DOLLARS
+			);
+
+			assert_noop!(
+				TestDex::swap_with_exact_supply(bob_origin(), vec![ETH, DOT], Balance::zero(), Balance::zero()),
+				Error::<TestRuntime>::ZeroBalanceTransaction
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_target(bob_origin(), vec![ETH, METH], DOLLARS, DOLLARS),
+				Error::<TestRuntime>::InvalidCurrencyPair
+			);
+		});
+}
+
+#[test]
+fn swap_by_path_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, 100_000 * DOLLARS)
+		.set_user_balance(ALICE, ETH, 200_000 * DOLLARS)
+		.set_user_balance(ALICE, KSM, 50_000 * DOLLARS)
+		.set_user_balance(BOB, DOT, 50_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				100_000 * DOLLARS,
+				100_000 * DOLLARS
+			));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				ETH,
+				KSM,
+				100_000 * DOLLARS,
+				50_000 * DOLLARS
+			));
+
+			// DOT -> ETH -> KSM
+			let expected_eth_amount = 9_066_108_938_801_491_315_813;
+			let expected_ksm_amount = 4_144_809_665_308_398_984_605;
+			assert_eq!(
+				TestDex::get_target_amounts(&[DOT, ETH, KSM], 10_000 * DOLLARS),
+				Ok(vec![10_000 * DOLLARS, expected_eth_amount, expected_ksm_amount])
+			);
+
+			assert_ok!(TestDex::swap_with_exact_supply(
+				bob_origin(),
+				vec![DOT, ETH, KSM],
 				10_000 * DOLLARS,
+				4_000 * DOLLARS
 			));
+			// A single event is emitted for the whole path.
+			let expected_event =
+				test_engine::Event::TestDex(Event::Swap(BOB, DOT, KSM, 10_000 * DOLLARS, expected_ksm_amount));
 			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(DOT, &BOB), 40_000 * DOLLARS);
+			assert_eq!(Currencies::free_balance(ETH, &BOB), Balance::zero());
+			assert_eq!(Currencies::free_balance(KSM, &BOB), expected_ksm_amount);
+			assert_eq!(
+				TestDex::liquidity_pool((DOT, ETH)),
+				(110_000 * DOLLARS, 100_000 * DOLLARS - expected_eth_amount)
+			);
+			assert_eq!(
+				TestDex::liquidity_pool((KSM, ETH)),
+				(
+					50_000 * DOLLARS - expected_ksm_amount,
+					100_000 * DOLLARS + expected_eth_amount
+				)
+			);
 
+			let supply_amounts = TestDex::get_supply_amounts(&[DOT, ETH, KSM], 1_000 * DOLLARS).unwrap();
+			assert_ok!(TestDex::swap_with_exact_target(
+				bob_origin(),
+				vec![DOT, ETH, KSM],
+				5_000 * DOLLARS,
+				1_000 * DOLLARS
+			));
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
-				290_000 * DOLLARS
+				Currencies::free_balance(DOT, &BOB),
+				40_000 * DOLLARS - supply_amounts[0]
 			);
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
-				410_000 * DOLLARS
+				Currencies::free_balance(KSM, &BOB),
+				expected_ksm_amount + 1_000 * DOLLARS
 			);
 
+			assert_noop!(
+				TestDex::swap_with_exact_supply(bob_origin(), vec![DOT], DOLLARS, Balance::zero()),
+				Error::<TestRuntime>::InvalidTradingPathLength
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply(bob_origin(), vec![DOT, ETH, KSM, BTC], DOLLARS, Balance::zero()),
+				Error::<TestRuntime>::InvalidTradingPathLength
+			);
+			assert_noop!(
+				TestDex::swap_with_exact_supply(bob_origin(), vec![DOT, ETH, DOT], DOLLARS, Balance::zero()),
+				Error::<TestRuntime>::InvalidCurrencyPair
+			);
+		});
+}
+
+#[test]
+fn average_price_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, units(DOT, 100_000))
+		.set_user_balance(ALICE, ETH, 50_000 * DOLLARS)
+		.set_user_balance(BOB, DOT, units(DOT, 10_000))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				units(DOT, 100_000),
+				50_000 * DOLLARS
+			));
+
+			// The first window starts with the first change of the reserves: 1 DOT = 0.5 ETH,
+			// 1 ETH = 2 DOT.
 			assert_eq!(
-				Currencies::free_balance(DOT, &TestDex::dex_account_id()),
-				60_000 * DOLLARS
+				TestDex::twap_observation((DOT, ETH)),
+				Some((Price::zero(), Price::zero(), 1))
 			);
+			assert_eq!(TestDex::average_price((DOT, ETH)), None);
+			assert_eq!(TestDex::get_average_price(ETH, DOT), None);
+
+			// The price changes in the middle of the window.
+			System::set_block_number(6);
+			assert_ok!(TestDex::swap_with_exact_supply(
+				bob_origin(),
+				vec![DOT, ETH],
+				units(DOT, 10_000),
+				Balance::zero()
+			));
 			assert_eq!(
-				Currencies::free_balance(ETH, &TestDex::dex_account_id()),
-				40_000 * DOLLARS
+				TestDex::liquidity_pool((DOT, ETH)),
+				(units(DOT, 110_000), 45_466_945_530_599_254_342_094)
 			);
+			assert_eq!(TestDex::average_price((DOT, ETH)), None);
 
-			assert_err!(
-				TestDex::do_swap_with_exact_target(
-					&TestLiquidationPools::pools_account_id(),
-					DOT,
-					ETH,
-					100_000 * DOLLARS,
-					100_000 * DOLLARS
-				),
-				Error::<TestRuntime>::InsufficientDexBalance
+			// Idle blocks don't touch the pool.
+			System::set_block_number(20);
+			MockTime::set_now(114_000);
+			assert_eq!(TestDex::average_price((DOT, ETH)), None);
+
+			// The next change closes the window: 5 blocks at the first price and 14 at the second.
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), DOT, ETH, units(DOT_ETH, 1)));
+			let expected_dot_price = Price::from_inner(436_142_218_865_258_162);
+			let expected_eth_price = Price::from_inner(2_308_987_368_421_052_630);
+			assert_eq!(
+				TestDex::average_price((DOT, ETH)),
+				Some((expected_dot_price, expected_eth_price, 114_000))
+			);
+			assert_eq!(TestDex::get_average_price(DOT, ETH), Some(expected_dot_price));
+			assert_eq!(TestDex::get_average_price(ETH, DOT), Some(expected_eth_price));
+			assert_eq!(TestDex::get_average_price(ETH, KSM), None);
+			assert_eq!(TestDex::get_average_price_timestamp(ETH, DOT), Some(114_000));
+			assert_eq!(TestDex::get_average_price_timestamp(ETH, KSM), None);
+			assert_eq!(
+				TestDex::twap_observation((DOT, ETH)).map(|(_, _, block)| block),
+				Some(20)
+			);
+		});
+}
+
+#[test]
+fn twap_data_provider_should_normalize_reserves() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, units(DOT, 200_000))
+		.set_user_balance(ALICE, BTC, units(BTC, 100))
+		.build()
+		.execute_with(|| {
+			// 1 BTC = 2_000 DOT, while the raw reserves are 10^10 BTC units and 2 * 10^15 DOT units.
+			System::set_block_number(1);
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				BTC,
+				DOT,
+				units(BTC, 100),
+				units(DOT, 200_000)
+			));
+
+			System::set_block_number(11);
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), BTC, DOT, units(DOT, 1)));
+
+			assert_eq!(
+				TestDex::average_price((DOT, BTC)),
+				Some((
+					Price::saturating_from_rational(1, 2_000),
+					Price::saturating_from_integer(2_000),
+					0
+				))
+			);
+			// 1 DOT = 20$, 1 BTC = 2_000 DOT = 40_000$
+			assert_eq!(
+				TestTwapDataProvider::get(&DOT),
+				Some(Price::saturating_from_integer(20))
+			);
+			assert_eq!(
+				TestTwapDataProvider::get(&BTC),
+				Some(Price::saturating_from_integer(40_000))
 			);
+			assert_eq!(TestTwapDataProvider::get(&ETH), None);
 		});
 }
@@ -11,7 +11,7 @@
 
 #[cfg(test)]
 mod tests {
-	use controller::{ControllerData, PauseKeeper};
+	use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 	use frame_support::{
 		assert_noop, assert_ok, ord_parameter_types, pallet_prelude::GenesisBuild, parameter_types, PalletId,
 	};
@@ -98,12 +98,14 @@ mod tests {
 	mock_impl_whitelist_module_config!(Test, ZeroAdmin);
 
 	thread_local! {
+		// The prices are set so that `DOLLARS` of any asset are worth 1$ regardless of
+		// the asset decimals.
 		static UNDERLYING_PRICE: RefCell<HashMap<CurrencyId, Price>> = RefCell::new(
 			[
-				(DOT, Price::one()),
+				(DOT, Price::saturating_from_rational(1, 100_000_000)),
 				(ETH, Price::one()),
-				(BTC, Price::one()),
-				(KSM, Price::one()),
+				(BTC, Price::saturating_from_rational(1, 10_000_000_000_u128)),
+				(KSM, Price::saturating_from_rational(1, 1_000_000)),
 			]
 			.iter()
 			.cloned()
@@ -140,7 +142,7 @@ mod tests {
 		minterest_model_params: Vec<(CurrencyId, MinterestModelData)>,
 		mnt_claim_threshold: Balance,
 		liquidation_fee: Vec<(CurrencyId, Rate)>,
-		liquidation_threshold: Rate,
+		liquidation_threshold: Vec<(CurrencyId, Rate)>,
 	}
 
 	impl Default for ExtBuilder {
@@ -160,6 +162,8 @@ mod tests {
 							collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 							borrow_cap: None,
 							protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+							supply_cap: None,
+							interest_accrual_mode: InterestAccrualMode::Simple,
 						},
 					),
 					(
@@ -171,6 +175,8 @@ mod tests {
 							collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 							borrow_cap: None,
 							protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+							supply_cap: None,
+							interest_accrual_mode: InterestAccrualMode::Simple,
 						},
 					),
 					(
@@ -182,6 +188,8 @@ mod tests {
 							collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 							borrow_cap: None,
 							protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+							supply_cap: None,
+							interest_accrual_mode: InterestAccrualMode::Simple,
 						},
 					),
 				],
@@ -221,7 +229,12 @@ mod tests {
 					(BTC, Rate::saturating_from_rational(5, 100)),
 					(KSM, Rate::saturating_from_rational(5, 100)),
 				],
-				liquidation_threshold: Rate::saturating_from_rational(3, 100),
+				liquidation_threshold: vec![
+					(DOT, Rate::saturating_from_rational(3, 100)),
+					(ETH, Rate::saturating_from_rational(3, 100)),
+					(BTC, Rate::saturating_from_rational(3, 100)),
+					(KSM, Rate::saturating_from_rational(3, 100)),
+				],
 			}
 		}
 	}
@@ -230,7 +243,7 @@ mod tests {
 		pub fn set_risk_manager_params(
 			mut self,
 			liquidation_fee: Vec<(CurrencyId, Rate)>,
-			liquidation_threshold: Rate,
+			liquidation_threshold: Vec<(CurrencyId, Rate)>,
 		) -> Self {
 			self.liquidation_fee = liquidation_fee;
 			self.liquidation_threshold = liquidation_threshold;
@@ -212,6 +212,8 @@ mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -223,6 +225,8 @@ mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			])
@@ -251,7 +255,10 @@ mod tests {
 					(DOT, Rate::saturating_from_rational(5, 100)),
 					(ETH, Rate::saturating_from_rational(5, 100)),
 				],
-				Rate::saturating_from_rational(3, 100),
+				vec![
+					(DOT, Rate::saturating_from_rational(3, 100)),
+					(ETH, Rate::saturating_from_rational(3, 100)),
+				],
 			)
 			.pool_initial(DOT)
 			.pool_initial(ETH)
@@ -387,6 +394,8 @@ mod tests {
 					collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 					borrow_cap: None,
 					protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+					supply_cap: None,
+					interest_accrual_mode: InterestAccrualMode::Simple,
 				},
 			)])
 			.set_minterest_model_params(vec![(
@@ -403,7 +412,10 @@ mod tests {
 					(DOT, Rate::saturating_from_rational(5, 100)),
 					(ETH, Rate::saturating_from_rational(5, 100)),
 				],
-				Rate::saturating_from_rational(3, 100),
+				vec![
+					(DOT, Rate::saturating_from_rational(3, 100)),
+					(ETH, Rate::saturating_from_rational(3, 100)),
+				],
 			)
 			.pool_initial(ETH)
 			.user_balance(ADMIN, ETH, ONE_HUNDRED_THOUSAND)
@@ -27,11 +27,11 @@ use pallet_traits::{
 };
 use sp_runtime::{
 	offchain::storage_lock::{StorageLock, Time},
-	traits::{AccountIdConversion, CheckedMul, One, Zero},
+	traits::{AccountIdConversion, CheckedMul, One, Saturating, Zero},
 	transaction_validity::TransactionPriority,
 	DispatchResult, FixedPointNumber, RuntimeDebug,
 };
-use sp_std::{cmp::Ordering, prelude::*};
+use sp_std::{cmp::Ordering, prelude::*, vec};
 
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -102,6 +102,11 @@ pub mod module {
 		/// The DEX participating in balancing
 		type Dex: DEXManager<Self::AccountId, CurrencyId, Balance>;
 
+		#[pallet::constant]
+		/// The maximum price deviation from the oracle price accepted when selling
+		/// the oversupply on the DEX.
+		type MaxBalancingSlippage: Get<Rate>;
+
 		/// Weight information for the extrinsics.
 		type LiquidationPoolsWeightInfo: WeightInfo;
 	}
@@ -328,13 +333,8 @@ pub mod module {
 			);
 
 			let module_id = Self::pools_account_id();
-			T::Dex::swap_with_exact_target(
-				&module_id,
-				supply_pool_id,
-				target_pool_id,
-				max_supply_amount,
-				target_amount,
-			)?;
+			let path = Self::get_best_swap_path(supply_pool_id, target_pool_id, target_amount);
+			T::Dex::swap_with_exact_target_by_path(&module_id, &path, max_supply_amount, target_amount)?;
 			Self::deposit_event(Event::LiquidationPoolsBalanced);
 			Ok(().into())
 		}
@@ -468,6 +468,7 @@ impl<T: Config> Pallet<T> {
 							T::PriceSource::get_underlying_price(*pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 						let liquidation_pool_supply_underlying = Self::get_pool_available_liquidity(*pool_id);
 						let liquidation_pool_supply_usd = T::LiquidityPoolsManager::underlying_to_usd(
+							*pool_id,
 							liquidation_pool_supply_underlying,
 							oracle_price,
 						)?;
@@ -578,7 +579,10 @@ impl<T: Config> Pallet<T> {
 		Ok(to_sell_list)
 	}
 
-	/// Temporary function
+	/// Converts the USD amount of a sale into the DEX swap amounts.
+	///
+	/// Returns (max_supply_amount, target_amount). The maximum supply amount allows the DEX
+	/// price to be worse than the oracle price by no more than `MaxBalancingSlippage`.
 	pub fn get_amounts(
 		supply_pool_id: CurrencyId,
 		target_pool_id: CurrencyId,
@@ -588,11 +592,41 @@ impl<T: Config> Pallet<T> {
 			T::PriceSource::get_underlying_price(supply_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 		let target_oracle_price =
 			T::PriceSource::get_underlying_price(target_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
-		let max_supply_amount = T::LiquidityPoolsManager::usd_to_underlying(amount, supply_oracle_price)?;
-		let target_amount = T::LiquidityPoolsManager::usd_to_underlying(amount, target_oracle_price)?;
+		let supply_amount = T::LiquidityPoolsManager::usd_to_underlying(supply_pool_id, amount, supply_oracle_price)?;
+		let max_supply_amount = Rate::from_inner(supply_amount)
+			.checked_mul(&Rate::one().saturating_add(T::MaxBalancingSlippage::get()))
+			.map(|x| x.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+		let target_amount = T::LiquidityPoolsManager::usd_to_underlying(target_pool_id, amount, target_oracle_price)?;
 		Ok((max_supply_amount, target_amount))
 	}
 
+	/// Chooses the DEX trading route that spends the least supply currency for the exact target
+	/// amount. Besides the direct pair, routes through every other enabled underlying asset are
+	/// considered. Falls back to the direct pair if no route can be quoted.
+	pub fn get_best_swap_path(
+		supply_pool_id: CurrencyId,
+		target_pool_id: CurrencyId,
+		target_amount: Balance,
+	) -> Vec<CurrencyId> {
+		let direct_path = vec![supply_pool_id, target_pool_id];
+		let indirect_paths = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|&currency_id| currency_id != supply_pool_id && currency_id != target_pool_id)
+			.map(|currency_id| vec![supply_pool_id, currency_id, target_pool_id]);
+
+		sp_std::iter::once(direct_path.clone())
+			.chain(indirect_paths)
+			.filter_map(|path| {
+				T::Dex::get_supply_amounts(&path, target_amount)
+					.ok()
+					.map(|amounts| (amounts[0], path))
+			})
+			.min_by_key(|(supply_amount, _)| *supply_amount)
+			.map(|(_, path)| path)
+			.unwrap_or(direct_path)
+	}
+
 	/// Calculates ideal balance for pool balancing
 	/// - `pool_id`: PoolID for which the ideal balance is calculated.
 	///
@@ -601,8 +635,12 @@ impl<T: Config> Pallet<T> {
 	pub fn calculate_ideal_balance(pool_id: CurrencyId) -> BalanceResult {
 		let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
 		let balance_ratio = Self::liquidation_pools_data(pool_id).balance_ratio;
+		// The balance is converted to the USD decimals.
+		let liquidity_pool_balance = pool_id
+			.normalize_amount(T::LiquidityPoolsManager::get_pool_available_liquidity(pool_id))
+			.ok_or(Error::<T>::BalanceOverflow)?;
 		// Liquidation pool ideal balance in USD: liquidity_pool_balance * balance_ratio * oracle_price
-		let ideal_balance_usd = Rate::from_inner(T::LiquidityPoolsManager::get_pool_available_liquidity(pool_id))
+		let ideal_balance_usd = Rate::from_inner(liquidity_pool_balance)
 			.checked_mul(&balance_ratio)
 			.and_then(|v| v.checked_mul(&oracle_price))
 			.map(|x| x.into_inner())
@@ -91,6 +91,7 @@ parameter_types! {
 	pub const LiquidationPoolsPalletId: PalletId = PalletId(*b"lqdn/min");
 	pub LiquidationPoolAccountId: AccountId = LiquidationPoolsPalletId::get().into_account();
 	pub const LiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value();
+	pub const MaxBalancingSlippage: Rate = Rate::from_inner(50_000_000_000_000_000);
 }
 
 ord_parameter_types! {
@@ -107,6 +108,7 @@ impl Config for Test {
 	type UpdateOrigin = EnsureSignedBy<ZeroAdmin, AccountId>;
 	type LiquidityPoolsManager = liquidity_pools::Pallet<Test>;
 	type Dex = dex::Pallet<Test>;
+	type MaxBalancingSlippage = MaxBalancingSlippage;
 	type LiquidationPoolsWeightInfo = ();
 }
 
@@ -229,12 +231,6 @@ impl ExternalityBuilder {
 		self
 	}
 
-	pub fn dex_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
-		self.endowed_accounts
-			.push((TestDex::dex_account_id(), currency_id, balance));
-		self
-	}
-
 	pub fn build(self) -> TestExternalities {
 		let mut t = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
@@ -15,11 +15,11 @@ use minterest_primitives::Price;
 fn offchain_worker_balancing_test() {
 	// balance ratio = 0.2 for two pools. Price the same.
 	// The offchain worker must send transaction for balancing.
-	// It must change 10_000 ETH to 10_000 DOT
+	// It must change 10_000 ETH to 10_000 DOT, accepting up to 5% slippage on the DEX.
 	let mut ext = ExternalityBuilder::default()
-		.liquidation_pool_balance(DOT, 10_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, units(DOT, 10_000))
 		.liquidation_pool_balance(ETH, 30_000 * DOLLARS)
-		.liquidity_pool_balance(DOT, 100_000 * DOLLARS)
+		.liquidity_pool_balance(DOT, units(DOT, 100_000))
 		.liquidity_pool_balance(ETH, 100_000 * DOLLARS)
 		.build();
 	let (offchain, _) = TestOffchainExt::new();
@@ -48,8 +48,8 @@ fn offchain_worker_balancing_test() {
 		};
 		assert_eq!(supply_pool_id, ETH);
 		assert_eq!(target_pool_id, DOT);
-		assert_eq!(max_supply_amount, 10_000 * DOLLARS);
-		assert_eq!(target_supply_amount, 10_000 * DOLLARS);
+		assert_eq!(max_supply_amount, 10_500 * DOLLARS);
+		assert_eq!(target_supply_amount, units(DOT, 10_000));
 	});
 }
 
@@ -216,7 +216,7 @@ fn set_max_ideal_balance_should_work() {
 #[test]
 fn calculate_ideal_balance_should_work() {
 	ExternalityBuilder::default()
-		.liquidity_pool_balance(DOT, 500_000 * DOLLARS)
+		.liquidity_pool_balance(DOT, units(DOT, 500_000))
 		.build()
 		.execute_with(|| {
 			// Check that ideal balance is calculated correctly when max_ideal_balance is set to None
@@ -312,14 +312,14 @@ fn collects_sales_list_should_work_2_2() {
 		.pool_initial(KSM)
 		.pool_initial(ETH)
 		.pool_initial(BTC)
-		.liquidity_pool_balance(DOT, 2_700_000 * DOLLARS)
-		.liquidity_pool_balance(KSM, 1_000_000 * DOLLARS)
+		.liquidity_pool_balance(DOT, units(DOT, 2_700_000))
+		.liquidity_pool_balance(KSM, units(KSM, 1_000_000))
 		.liquidity_pool_balance(ETH, 2_500_000_000 * DOLLARS)
-		.liquidity_pool_balance(BTC, 1_200_000 * DOLLARS)
-		.liquidation_pool_balance(DOT, 400_000 * DOLLARS)
-		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
+		.liquidity_pool_balance(BTC, units(BTC, 1_200_000))
+		.liquidation_pool_balance(DOT, units(DOT, 400_000))
+		.liquidation_pool_balance(KSM, units(KSM, 300_000))
 		.liquidation_pool_balance(ETH, 800_000_000 * DOLLARS)
-		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
+		.liquidation_pool_balance(BTC, units(BTC, 100_000))
 		.build()
 		.execute_with(|| {
 			set_prices_for_assets(vec![
@@ -364,18 +364,17 @@ fn balance_liquidation_pools_should_work() {
 		.pool_initial(KSM)
 		.pool_initial(ETH)
 		.pool_initial(BTC)
-		.liquidity_pool_balance(DOT, 500_000 * DOLLARS)
-		.liquidity_pool_balance(KSM, 1_000_000 * DOLLARS)
+		.liquidity_pool_balance(DOT, units(DOT, 500_000))
+		.liquidity_pool_balance(KSM, units(KSM, 1_000_000))
 		.liquidity_pool_balance(ETH, 1_500_000 * DOLLARS)
-		.liquidity_pool_balance(BTC, 2_000_000 * DOLLARS)
-		.liquidation_pool_balance(DOT, 400_000 * DOLLARS)
-		.liquidation_pool_balance(KSM, 300_000 * DOLLARS)
+		.liquidity_pool_balance(BTC, units(BTC, 2_000_000))
+		.liquidation_pool_balance(DOT, units(DOT, 400_000))
+		.liquidation_pool_balance(KSM, units(KSM, 300_000))
 		.liquidation_pool_balance(ETH, 200_000 * DOLLARS)
-		.liquidation_pool_balance(BTC, 100_000 * DOLLARS)
-		.dex_balance(DOT, 500_000 * DOLLARS)
-		.dex_balance(KSM, 500_000 * DOLLARS)
-		.dex_balance(ETH, 500_000 * DOLLARS)
-		.dex_balance(BTC, 500_000 * DOLLARS)
+		.liquidation_pool_balance(BTC, units(BTC, 100_000))
+		.user_balance(ALICE, DOT, units(DOT, 10_000_000))
+		.user_balance(ALICE, KSM, units(KSM, 5_000_000))
+		.user_balance(ALICE, BTC, units(BTC, 2_000_000))
 		.build()
 		.execute_with(|| {
 			set_prices_for_assets(vec![
@@ -384,6 +383,21 @@ fn balance_liquidation_pools_should_work() {
 				(ETH, Price::saturating_from_integer(5)),
 				(BTC, Price::saturating_from_integer(10)),
 			]);
+			// DEX pools are created at the oracle prices.
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				BTC,
+				units(DOT, 10_000_000),
+				units(BTC, 1_000_000)
+			));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				KSM,
+				BTC,
+				units(KSM, 5_000_000),
+				units(BTC, 1_000_000)
+			));
 
 			/*
 			Liquidity Pools balances (in assets): [500_000, 1_000_000, 1_500_000, 2_000_000]
@@ -414,6 +428,23 @@ fn balance_liquidation_pools_should_work() {
 				Ok(expected_sales_list.clone())
 			);
 
+			// max_supply_amount = 315_000 DOT, target_amount = 30_000 BTC
+			// max_supply_amount = 105_000 KSM, target_amount = 20_000 BTC
+			assert_eq!(
+				TestLiquidationPools::get_amounts(DOT, BTC, units(BTC, 300_000)),
+				Ok((units(DOT, 315_000), units(BTC, 30_000)))
+			);
+			assert_eq!(
+				TestLiquidationPools::get_amounts(KSM, BTC, units(BTC, 200_000)),
+				Ok((units(KSM, 105_000), units(BTC, 20_000)))
+			);
+
+			// The DEX charges the price impact and the swap fee, but stays within the slippage.
+			let dot_supply_amount = TestDex::get_supply_amounts(&[DOT, BTC], units(BTC, 30_000)).unwrap()[0];
+			let ksm_supply_amount = TestDex::get_supply_amounts(&[KSM, BTC], units(BTC, 20_000)).unwrap()[0];
+			assert!(units(DOT, 300_000) < dot_supply_amount && dot_supply_amount < units(DOT, 315_000));
+			assert!(units(KSM, 100_000) < ksm_supply_amount && ksm_supply_amount < units(KSM, 105_000));
+
 			expected_sales_list.iter().for_each(|sale| {
 				if let Some((max_supply_amount, target_amount)) =
 					TestLiquidationPools::get_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount).ok()
@@ -433,7 +464,7 @@ fn balance_liquidation_pools_should_work() {
 				.into_iter()
 				.map(|r| r.event)
 				.filter_map(|e| {
-					if let Event::TestDex(inner) = e {
+					if let Event::TestDex(inner @ dex::Event::Swap(..)) = e {
 						Some(inner)
 					} else {
 						None
@@ -445,24 +476,24 @@ fn balance_liquidation_pools_should_work() {
 					TestLiquidationPools::pools_account_id(),
 					DOT,
 					BTC,
-					300_000 * DOLLARS, // max_supply_amount = 300_000 DOT
-					30_000 * DOLLARS,  // target_amount = 30_000 BTC
+					dot_supply_amount,
+					units(BTC, 30_000), // target_amount = 30_000 BTC
 				),
 				dex::Event::Swap(
 					TestLiquidationPools::pools_account_id(),
 					KSM,
 					BTC,
-					100_000 * DOLLARS, // max_supply_amount = 100_000 DOT
-					20_000 * DOLLARS,  // target_amount = 20_000 BTC
+					ksm_supply_amount,
+					units(BTC, 20_000), // target_amount = 20_000 BTC
 				),
 			];
 			assert_eq!(our_events, expected_events);
 
 			// Liquidation Pool balances
-			assert_eq!(liquidation_pool_balance(DOT), 100_000 * DOLLARS);
-			assert_eq!(liquidation_pool_balance(KSM), 200_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(DOT), units(DOT, 400_000) - dot_supply_amount);
+			assert_eq!(liquidation_pool_balance(KSM), units(KSM, 300_000) - ksm_supply_amount);
 			assert_eq!(liquidation_pool_balance(ETH), 200_000 * DOLLARS);
-			assert_eq!(liquidation_pool_balance(BTC), 150_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(BTC), units(BTC, 150_000));
 		});
 }
 
@@ -471,18 +502,25 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 	ExternalityBuilder::default()
 		.pool_initial(DOT)
 		.pool_initial(ETH)
-		.liquidity_pool_balance(DOT, 500_000 * DOLLARS)
+		.liquidity_pool_balance(DOT, units(DOT, 500_000))
 		.liquidity_pool_balance(ETH, 300_000 * DOLLARS)
-		.liquidation_pool_balance(DOT, 170_000 * DOLLARS) // + 140_000$
+		.liquidation_pool_balance(DOT, units(DOT, 170_000)) // + 140_000$
 		.liquidation_pool_balance(ETH, 30_000 * DOLLARS) //- 120_000$
-		.dex_balance(DOT, 500_000 * DOLLARS)
-		.dex_balance(ETH, 500_000 * DOLLARS)
+		.user_balance(ALICE, DOT, units(DOT, 2_000_000))
+		.user_balance(ALICE, ETH, 1_000_000 * DOLLARS)
 		.build()
 		.execute_with(|| {
 			set_prices_for_assets(vec![
 				(DOT, Price::saturating_from_integer(2)),
 				(ETH, Price::saturating_from_integer(4)),
 			]);
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				units(DOT, 2_000_000),
+				1_000_000 * DOLLARS
+			));
 
 			/*
 			Liquidity Pools balances (in assets): [500_000, 300_000]
@@ -503,6 +541,13 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 				Ok(expected_sales_list.clone())
 			);
 
+			// max_supply_amount = 63_000 DOT, target_amount = 30_000 ETH
+			assert_eq!(
+				TestLiquidationPools::get_amounts(DOT, ETH, 120_000 * DOLLARS),
+				Ok((units(DOT, 63_000), 30_000 * DOLLARS))
+			);
+			let supply_amount = TestDex::get_supply_amounts(&[DOT, ETH], 30_000 * DOLLARS).unwrap()[0];
+
 			expected_sales_list.iter().for_each(|sale| {
 				if let Some((max_supply_amount, target_amount)) =
 					TestLiquidationPools::get_amounts(sale.supply_pool_id, sale.target_pool_id, sale.amount).ok()
@@ -522,7 +567,7 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 				.into_iter()
 				.map(|r| r.event)
 				.filter_map(|e| {
-					if let Event::TestDex(inner) = e {
+					if let Event::TestDex(inner @ dex::Event::Swap(..)) = e {
 						Some(inner)
 					} else {
 						None
@@ -533,14 +578,140 @@ fn balance_liquidation_pools_two_pools_should_work_test() {
 				TestLiquidationPools::pools_account_id(),
 				DOT,
 				ETH,
-				60_000 * DOLLARS, // max_supply_amount = 60_000 DOT
+				supply_amount,
 				30_000 * DOLLARS, // target_amount = 30_000 ETH
 			)];
 
 			assert_eq!(our_events, expected_events);
 
 			// Liquidation Pool balances
-			assert_eq!(liquidation_pool_balance(DOT), 110_000 * DOLLARS);
+			assert_eq!(liquidation_pool_balance(DOT), units(DOT, 170_000) - supply_amount);
 			assert_eq!(liquidation_pool_balance(ETH), 60_000 * DOLLARS);
 		});
 }
+
+#[test]
+fn balance_liquidation_pools_should_fail_on_excessive_slippage() {
+	ExternalityBuilder::default()
+		.pool_initial(DOT)
+		.pool_initial(ETH)
+		.liquidity_pool_balance(DOT, units(DOT, 500_000))
+		.liquidity_pool_balance(ETH, 300_000 * DOLLARS)
+		.liquidation_pool_balance(DOT, units(DOT, 170_000))
+		.liquidation_pool_balance(ETH, 30_000 * DOLLARS)
+		.user_balance(ALICE, DOT, units(DOT, 200_000))
+		.user_balance(ALICE, ETH, 100_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![
+				(DOT, Price::saturating_from_integer(2)),
+				(ETH, Price::saturating_from_integer(4)),
+			]);
+			// The DEX pool is too shallow to sell 120_000$ within 5% of the oracle price.
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				units(DOT, 200_000),
+				100_000 * DOLLARS
+			));
+
+			let (max_supply_amount, target_amount) =
+				TestLiquidationPools::get_amounts(DOT, ETH, 120_000 * DOLLARS).unwrap();
+			assert_noop!(
+				TestLiquidationPools::balance_liquidation_pools(
+					Origin::none(),
+					DOT,
+					ETH,
+					max_supply_amount,
+					target_amount
+				),
+				dex::Error::<Test>::ExcessiveSupplyAmount
+			);
+		});
+}
+
+#[test]
+fn balance_liquidation_pools_should_use_best_route() {
+	ExternalityBuilder::default()
+		.pool_initial(DOT)
+		.pool_initial(KSM)
+		.pool_initial(BTC)
+		.liquidation_pool_balance(KSM, units(KSM, 300_000))
+		.user_balance(ALICE, DOT, units(DOT, 40_000_000))
+		.user_balance(ALICE, KSM, units(KSM, 10_050_000))
+		.user_balance(ALICE, BTC, units(BTC, 2_010_000))
+		.build()
+		.execute_with(|| {
+			set_prices_for_assets(vec![
+				(DOT, Price::saturating_from_integer(1)),
+				(KSM, Price::saturating_from_integer(2)),
+				(BTC, Price::saturating_from_integer(10)),
+			]);
+			// KSM/BTC is thinly traded, while both KSM/DOT and DOT/BTC are deep.
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				KSM,
+				BTC,
+				units(KSM, 50_000),
+				units(BTC, 10_000)
+			));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				KSM,
+				DOT,
+				units(KSM, 10_000_000),
+				units(DOT, 20_000_000)
+			));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				BTC,
+				units(DOT, 20_000_000),
+				units(BTC, 2_000_000)
+			));
+
+			// max_supply_amount = 105_000 KSM, target_amount = 20_000 BTC
+			let (max_supply_amount, target_amount) =
+				TestLiquidationPools::get_amounts(KSM, BTC, units(BTC, 200_000)).unwrap();
+			assert_eq!(
+				(max_supply_amount, target_amount),
+				(units(KSM, 105_000), units(BTC, 20_000))
+			);
+
+			assert_eq!(
+				TestLiquidationPools::get_best_swap_path(KSM, BTC, target_amount),
+				vec![KSM, DOT, BTC]
+			);
+			// Small trades are cheaper on the direct pair, which charges the swap fee only once.
+			assert_eq!(
+				TestLiquidationPools::get_best_swap_path(KSM, BTC, units(BTC, 1)),
+				vec![KSM, BTC]
+			);
+			let supply_amount = TestDex::get_supply_amounts(&[KSM, DOT, BTC], target_amount).unwrap()[0];
+			assert!(supply_amount < max_supply_amount);
+
+			assert_ok!(TestLiquidationPools::balance_liquidation_pools(
+				Origin::none(),
+				KSM,
+				BTC,
+				max_supply_amount,
+				target_amount
+			));
+			let expected_event = Event::TestDex(dex::Event::Swap(
+				TestLiquidationPools::pools_account_id(),
+				KSM,
+				BTC,
+				supply_amount,
+				target_amount,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(liquidation_pool_balance(KSM), units(KSM, 300_000) - supply_amount);
+			assert_eq!(liquidation_pool_balance(BTC), units(BTC, 20_000));
+			assert_eq!(
+				TestDex::liquidity_pool((KSM, BTC)),
+				(units(KSM, 50_000), units(BTC, 10_000))
+			);
+		});
+}
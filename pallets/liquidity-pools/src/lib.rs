@@ -135,6 +135,12 @@ pub mod module {
 	pub(crate) type PoolUserParams<T: Config> =
 		StorageDoubleMap<_, Blake2_128Concat, CurrencyId, Twox64Concat, T::AccountId, PoolUserData, ValueQuery>;
 
+	/// The amount of written off borrows of the pool that were not covered by the borrowers'
+	/// collateral and are not covered by the protocol yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pool_bad_debt)]
+	pub(crate) type PoolBadDebt<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[allow(clippy::type_complexity)]
@@ -185,6 +191,9 @@ impl<T: Config> Pallet<T> {
 	/// - `pool_borrow_underlying`: Total amount of outstanding borrows of the underlying in this
 	/// pool.
 	///
+	/// Written off borrows are not part of the formula: the exchange rate drops as soon as the
+	/// borrow is written off and recovers when the bad debt is covered.
+	///
 	/// returns `exchange_rate = (pool_supply_underlying + pool_borrow_underlying -
 	/// - pool_protocol_interest) / pool_supply_wrap`.
 	pub fn calculate_exchange_rate(
@@ -313,6 +322,44 @@ impl<T: Config> Borrowing<T::AccountId> for Pallet<T> {
 
 		Ok(())
 	}
+
+	/// Writes off the uncovered part of the borrow. The amount is removed from the borrower
+	/// balance and pool total borrow balances and is added to the pool bad debt.
+	/// Also sets the global borrow_index to user interest index.
+	/// - `who`: The AccountId whose borrow balance should be written off.
+	/// - `pool_id`: PoolID whose total borrow balance should be calculated.
+	/// - `bad_debt_amount`: The amount of the underlying asset to write off.
+	/// - `account_borrows`: The borrow balance of account.
+	///
+	/// calculates: `account_borrows_new = account_borrows - bad_debt_amount`,
+	///             `total_borrows_new = total_borrows - bad_debt_amount`,
+	///             `bad_debt_new = bad_debt + bad_debt_amount`.
+	fn update_state_on_bad_debt(
+		who: &T::AccountId,
+		pool_id: CurrencyId,
+		bad_debt_amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult {
+		let pool_data = Self::get_pool_data(pool_id);
+
+		let account_borrow_new = account_borrows
+			.checked_sub(bad_debt_amount)
+			.ok_or(Error::<T>::RepayAmountTooBig)?;
+		let total_borrows_new = pool_data
+			.borrowed
+			.checked_sub(bad_debt_amount)
+			.ok_or(Error::<T>::RepayAmountTooBig)?;
+		let bad_debt_new = Self::pool_bad_debt(pool_id)
+			.checked_add(bad_debt_amount)
+			.ok_or(Error::<T>::BorrowBalanceOverflow)?;
+
+		// Write the previously calculated values into storage.
+		Self::set_pool_borrow_underlying(pool_id, total_borrows_new);
+		Self::set_pool_bad_debt(pool_id, bad_debt_new);
+		Self::set_user_borrow_and_interest_index(&who, pool_id, account_borrow_new, pool_data.borrow_index);
+
+		Ok(())
+	}
 }
 
 impl<T: Config> PoolsManager<T::AccountId> for Pallet<T> {
@@ -341,6 +388,10 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, Pool> for Pallet<T> {
 		Pools::<T>::mutate(pool_id, |r| r.protocol_interest = new_pool_protocol_interest)
 	}
 
+	fn set_pool_bad_debt(pool_id: CurrencyId, new_pool_bad_debt: Balance) {
+		PoolBadDebt::<T>::insert(pool_id, new_pool_bad_debt)
+	}
+
 	fn get_pool_data(pool_id: CurrencyId) -> Pool {
 		Self::pools(pool_id)
 	}
@@ -365,6 +416,10 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, Pool> for Pallet<T> {
 		Self::pools(pool_id).protocol_interest
 	}
 
+	fn get_pool_bad_debt(pool_id: CurrencyId) -> Balance {
+		Self::pool_bad_debt(pool_id)
+	}
+
 	fn pool_exists(underlying_asset: &CurrencyId) -> bool {
 		Pools::<T>::contains_key(underlying_asset)
 	}
@@ -384,7 +439,8 @@ impl<T: Config> LiquidityPoolStorageProvider<T::AccountId, Pool> for Pallet<T> {
 	}
 
 	fn remove_pool_data(pool_id: CurrencyId) {
-		Pools::<T>::remove(pool_id)
+		Pools::<T>::remove(pool_id);
+		PoolBadDebt::<T>::remove(pool_id)
 	}
 }
 
@@ -422,8 +478,11 @@ impl<T: Config> CurrencyConverter for Pallet<T> {
 	}
 
 	/// Converts a specified number of underlying assets into USD.
-	fn underlying_to_usd(underlying_amount: Balance, oracle_price: Price) -> BalanceResult {
-		let usd_amount = Rate::from_inner(underlying_amount)
+	fn underlying_to_usd(pool_id: CurrencyId, underlying_amount: Balance, oracle_price: Price) -> BalanceResult {
+		let normalized_amount = pool_id
+			.normalize_amount(underlying_amount)
+			.ok_or(Error::<T>::ConversionError)?;
+		let usd_amount = Rate::from_inner(normalized_amount)
 			.checked_mul(&oracle_price)
 			.map(|x| x.into_inner())
 			.ok_or(Error::<T>::ConversionError)?;
@@ -441,27 +500,40 @@ impl<T: Config> CurrencyConverter for Pallet<T> {
 
 	/// Converts a specified number of wrapped tokens into USD.
 	fn wrapped_to_usd(
+		pool_id: CurrencyId,
 		wrapped_amount: Balance,
 		exchange_rate: Rate,
 		oracle_price: Price,
 	) -> Result<Balance, DispatchError> {
 		let underlying_amount = Self::wrapped_to_underlying(wrapped_amount, exchange_rate)?;
-		let usd_amount = Self::underlying_to_usd(underlying_amount, oracle_price)?;
+		let usd_amount = Self::underlying_to_usd(pool_id, underlying_amount, oracle_price)?;
 		Ok(usd_amount)
 	}
 
 	/// Converts a specified number of USD into underlying assets.
-	fn usd_to_underlying(usd_amount: Balance, oracle_price: Price) -> Result<Balance, DispatchError> {
-		let underlying_amount = Rate::from_inner(usd_amount)
+	fn usd_to_underlying(
+		pool_id: CurrencyId,
+		usd_amount: Balance,
+		oracle_price: Price,
+	) -> Result<Balance, DispatchError> {
+		let normalized_amount = Rate::from_inner(usd_amount)
 			.checked_div(&oracle_price)
 			.map(|x| x.into_inner())
 			.ok_or(Error::<T>::ConversionError)?;
+		let underlying_amount = pool_id
+			.denormalize_amount(normalized_amount)
+			.ok_or(Error::<T>::ConversionError)?;
 		Ok(underlying_amount)
 	}
 
 	/// Converts a specified amount of USD into wrapped tokens.
-	fn usd_to_wrapped(usd_amount: Balance, exchange_rate: Rate, oracle_price: Price) -> Result<Balance, DispatchError> {
-		let underlying_amount = Self::usd_to_underlying(usd_amount, oracle_price)?;
+	fn usd_to_wrapped(
+		pool_id: CurrencyId,
+		usd_amount: Balance,
+		exchange_rate: Rate,
+		oracle_price: Price,
+	) -> Result<Balance, DispatchError> {
+		let underlying_amount = Self::usd_to_underlying(pool_id, usd_amount, oracle_price)?;
 		let wrapped_amount = Self::underlying_to_wrapped(underlying_amount, exchange_rate)?;
 		Ok(wrapped_amount)
 	}
@@ -482,7 +554,8 @@ impl<T: Config> UserCollateral<T::AccountId> for Pallet<T> {
 				}
 				let exchange_rate = Self::get_exchange_rate(pool_id).ok()?;
 				let oracle_price = T::PriceSource::get_underlying_price(pool_id)?;
-				let user_supply_in_usd = Self::wrapped_to_usd(user_supply_wrap, exchange_rate, oracle_price).ok()?;
+				let user_supply_in_usd =
+					Self::wrapped_to_usd(pool_id, user_supply_wrap, exchange_rate, oracle_price).ok()?;
 
 				Some((pool_id, user_supply_in_usd))
 			})
@@ -267,6 +267,35 @@ fn update_state_on_repay_should_work() {
 		});
 }
 
+#[test]
+fn update_state_on_bad_debt_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		assert_ok!(TestPools::update_state_on_borrow(&ALICE, DOT, dollars(60), 0));
+		assert_ok!(TestPools::update_state_on_borrow(&BOB, DOT, dollars(40), 0));
+		assert_eq!(TestPools::get_pool_borrow_underlying(DOT), dollars(100));
+
+		assert_ok!(TestPools::update_state_on_bad_debt(
+			&ALICE,
+			DOT,
+			dollars(20),
+			dollars(60)
+		));
+		assert_eq!(TestPools::get_pool_borrow_underlying(DOT), dollars(80));
+		assert_eq!(TestPools::get_user_borrow_balance(&ALICE, DOT), dollars(40));
+		assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(20));
+
+		assert_ok!(TestPools::update_state_on_bad_debt(&BOB, DOT, dollars(40), dollars(40)));
+		assert_eq!(TestPools::get_pool_borrow_underlying(DOT), dollars(40));
+		assert_eq!(TestPools::get_user_borrow_balance(&BOB, DOT), Balance::zero());
+		assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(60));
+
+		assert_noop!(
+			TestPools::update_state_on_bad_debt(&ALICE, DOT, dollars(50), dollars(40)),
+			Error::<TestRuntime>::RepayAmountTooBig
+		);
+	});
+}
+
 #[test]
 fn get_pool_members_with_loans_should_work() {
 	ExtBuilderNew::default()
@@ -403,6 +432,27 @@ fn get_exchange_rate_should_work() {
 				TestPools::get_exchange_rate(DOT),
 				Ok(Rate::saturating_from_rational(32, 10))
 			);
+
+			// exchange_rate = (100 - 0 + 400) / 125 = 4
+			assert_ok!(TestPools::update_state_on_borrow(&ALICE, DOT, dollars(100_u128), 0));
+			assert_eq!(
+				TestPools::get_exchange_rate(DOT),
+				Ok(Rate::saturating_from_rational(4, 1))
+			);
+
+			// Written off borrow is absorbed into the exchange rate right away:
+			// exchange_rate = (100 - 0 + 300) / 125 = 3.2
+			assert_ok!(TestPools::update_state_on_bad_debt(
+				&ALICE,
+				DOT,
+				dollars(100_u128),
+				dollars(100_u128)
+			));
+			assert_eq!(TestPools::get_pool_bad_debt(DOT), dollars(100_u128));
+			assert_eq!(
+				TestPools::get_exchange_rate(DOT),
+				Ok(Rate::saturating_from_rational(32, 10))
+			);
 		});
 }
 
@@ -434,10 +484,13 @@ fn underlying_to_wrapped_and_usd_should_work() {
 			);
 
 			// oracle_price = 2 USD.
+			// DOT has 10 decimals, USD amounts have 18 decimals.
 			let oracle_price = MockPriceSource::get_underlying_price(DOT).unwrap();
-			assert_eq!(TestPools::underlying_to_usd(10, oracle_price), Ok(20));
+			assert_eq!(TestPools::underlying_to_usd(DOT, 10, oracle_price), Ok(2_000_000_000));
 
-			assert_eq!(TestPools::usd_to_underlying(20, oracle_price), Ok(10));
+			assert_eq!(TestPools::usd_to_underlying(DOT, 2_000_000_000, oracle_price), Ok(10));
+			// The remainder below the smallest unit of DOT is discarded.
+			assert_eq!(TestPools::usd_to_underlying(DOT, 20, oracle_price), Ok(0));
 		});
 }
 
@@ -477,10 +530,94 @@ fn wrapped_to_underlying_and_usd_should_work() {
 			);
 
 			// oracle_price = 2 USD.
+			// DOT has 10 decimals, USD amounts have 18 decimals.
 			let oracle_price = MockPriceSource::get_underlying_price(DOT).unwrap();
-			assert_eq!(TestPools::wrapped_to_usd(10, exchange_rate_dot, oracle_price), Ok(8));
+			assert_eq!(
+				TestPools::wrapped_to_usd(DOT, 10, exchange_rate_dot, oracle_price),
+				Ok(800_000_000)
+			);
 
 			// wrapped_amount = 20 / 2 / 0.4 = 25
-			assert_eq!(TestPools::usd_to_wrapped(20, exchange_rate_dot, oracle_price), Ok(25));
+			assert_eq!(
+				TestPools::usd_to_wrapped(DOT, 2_000_000_000, exchange_rate_dot, oracle_price),
+				Ok(25)
+			);
+		});
+}
+
+#[test]
+fn usd_conversions_should_take_into_account_decimals() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		let btc_price = Price::saturating_from_integer(48_000);
+		let eth_price = Price::saturating_from_integer(1_500);
+
+		// 1 BTC = 10^8, 32 ETH = 32 * 10^18
+		let btc_usd = TestPools::underlying_to_usd(BTC, 100_000_000, btc_price).unwrap();
+		let eth_usd = TestPools::underlying_to_usd(ETH, 32 * DOLLARS, eth_price).unwrap();
+		assert_eq!(btc_usd, 48_000 * DOLLARS);
+		assert_eq!(btc_usd, eth_usd);
+		// 1.5 BTC is worth more than 32 ETH.
+		assert!(TestPools::underlying_to_usd(BTC, 150_000_000, btc_price).unwrap() > eth_usd);
+
+		assert_eq!(
+			TestPools::usd_to_underlying(BTC, 48_000 * DOLLARS, btc_price),
+			Ok(100_000_000)
+		);
+		assert_eq!(
+			TestPools::usd_to_underlying(ETH, 48_000 * DOLLARS, eth_price),
+			Ok(32 * DOLLARS)
+		);
+		assert_eq!(
+			TestPools::wrapped_to_usd(BTC, 100_000_000, Rate::saturating_from_rational(1, 2), btc_price),
+			Ok(24_000 * DOLLARS)
+		);
+		assert_eq!(
+			TestPools::usd_to_wrapped(BTC, 24_000 * DOLLARS, Rate::saturating_from_rational(1, 2), btc_price),
+			Ok(100_000_000)
+		);
+	});
+}
+
+#[test]
+fn usd_conversions_should_take_into_account_decimals_with_dex_prices() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, BTC, units(BTC, 10))
+		.set_user_balance(ALICE, ETH, 400 * DOLLARS)
+		.set_user_balance(ALICE, DOT, units(DOT, 27_000))
+		.build()
+		.execute_with(|| {
+			// 1 DOT = 40$ from the oracle, 1 BTC = 1_200 DOT and 1 ETH = 37.5 DOT on the dex.
+			System::set_block_number(1);
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				BTC,
+				DOT,
+				units(BTC, 10),
+				units(DOT, 12_000)
+			));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				ETH,
+				DOT,
+				400 * DOLLARS,
+				units(DOT, 15_000)
+			));
+			// The next change of the reserves closes the averaging windows.
+			System::set_block_number(11);
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), BTC, DOT, units(DOT, 1)));
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), ETH, DOT, units(DOT, 1)));
+			assert_ok!(TestPrices::set_price_source(alice_origin(), BTC, DataProviderId::Dex));
+			assert_ok!(TestPrices::set_price_source(alice_origin(), ETH, DataProviderId::Dex));
+
+			let btc_price = TestPrices::get_underlying_price(BTC).unwrap();
+			let eth_price = TestPrices::get_underlying_price(ETH).unwrap();
+			assert_eq!(btc_price, Price::saturating_from_integer(48_000));
+			assert_eq!(eth_price, Price::saturating_from_integer(1_500));
+
+			// 1 BTC = 10^8, 32 ETH = 32 * 10^18
+			let btc_usd = TestPools::underlying_to_usd(BTC, units(BTC, 1), btc_price).unwrap();
+			let eth_usd = TestPools::underlying_to_usd(ETH, 32 * DOLLARS, eth_price).unwrap();
+			assert_eq!(btc_usd, 48_000 * DOLLARS);
+			assert_eq!(btc_usd, eth_usd);
 		});
 }
@@ -5,6 +5,8 @@
 //! This pallet provides ways for user to interact with Minterest protocol.
 //! User call deposit, redeem, borrow, repay and transfer tokens.
 //! Also user is able to enable/disable pool to be used as collateral.
+//! Any user is able to liquidate an unsafe loan of another user: repay a part of the borrow
+//! (limited by the close factor) and receive the borrower's wrapped tokens at a discount.
 //! Every first in a block successful call of deposit/redeem/borrow/repay causes interest to be
 //! recalculated for a pool.
 //! In WhitelistMode only users from WhitelistMembers are able to call extrinsics of this module.
@@ -26,8 +28,8 @@ pub use module::*;
 use orml_traits::MultiCurrency;
 use pallet_traits::{
 	Borrowing, ControllerManager, CurrencyConverter, LiquidationPoolsManager, LiquidityPoolStorageProvider,
-	MinterestModelManager, MntManager, PoolsManager, RiskManagerStorageProvider, UserCollateral,
-	UserLiquidationAttemptsManager, UserStorageProvider, WhitelistManager,
+	MinterestModelManager, MinterestProtocolManager, MntManager, PoolsManager, RiskManagerStorageProvider,
+	UserCollateral, UserLiquidationAttemptsManager, UserStorageProvider, WhitelistManager,
 };
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
@@ -47,6 +49,7 @@ pub use weights::WeightInfo;
 
 type TokensResult = result::Result<(Balance, CurrencyId, Balance), DispatchError>;
 type BalanceResult = result::Result<Balance, DispatchError>;
+type LiquidationResult = result::Result<(Balance, Balance), DispatchError>;
 
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 #[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq, Default)]
@@ -155,6 +158,10 @@ pub mod module {
 		PoolAlreadyCreated,
 		/// Pool not found.
 		PoolNotFound,
+		/// The user is trying to liquidate their own loan.
+		CannotLiquidateSelf,
+		/// The borrower does not have enough wrapped tokens to be seized.
+		LiquidateSeizeTooMuch,
 	}
 
 	#[pallet::event]
@@ -185,6 +192,11 @@ pub mod module {
 		ProtocolInterestTransferFailed(CurrencyId),
 		/// New pool had been created: \[pool_id\]
 		PoolCreated(CurrencyId),
+		/// Unsafe loan has been liquidated by a third-party liquidator: \[liquidator, borrower,
+		/// repay_pool_id, repaid_amount, collateral_pool_id, seized_wrapped_amount\]
+		LiquidatedBorrow(T::AccountId, T::AccountId, CurrencyId, Balance, CurrencyId, Balance),
+		/// The user switched to the e-mode category or out of e-mode: \[who, category_id\]
+		UserEModeSet(T::AccountId, Option<u8>),
 	}
 
 	#[pallet::pallet]
@@ -236,6 +248,8 @@ pub mod module {
 
 		/// Transfers an asset into the protocol. The user receives a quantity of wrapped Tokens
 		/// equal to the underlying tokens supplied, divided by the current Exchange Rate.
+		/// If the pool has a supply cap, the deposit is checked against the last accepted price
+		/// of the underlying asset while there is no fresh one.
 		///
 		/// - `underlying_asset`: CurrencyId of underlying assets to be transferred into the
 		///   protocol.
@@ -492,6 +506,7 @@ pub mod module {
 			let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
 			let user_wrapped_balance = T::MultiCurrency::free_balance(wrapped_id, &sender);
 			ensure!(!user_wrapped_balance.is_zero(), Error::<T>::IsCollateralCannotBeEnabled);
+			T::ControllerManager::collateral_allowed(pool_id, &sender)?;
 
 			T::ManagerLiquidityPools::enable_is_collateral(&sender, pool_id);
 			Self::deposit_event(Event::PoolEnabledIsCollateral(sender, pool_id));
@@ -543,6 +558,25 @@ pub mod module {
 			Ok(().into())
 		}
 
+		/// Sender opts in to the efficiency mode category of correlated assets or opts out of
+		/// e-mode.
+		/// - `category_id`: the e-mode category ID. `None` switches the sender out of e-mode.
+		///
+		/// All the collaterals and borrows of the sender must belong to the category.
+		#[pallet::weight(T::ProtocolWeightInfo::set_user_emode())]
+		#[transactional]
+		pub fn set_user_emode(origin: OriginFor<T>, category_id: Option<u8>) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			if T::WhitelistManager::is_whitelist_mode_enabled() {
+				ensure!(T::WhitelistManager::is_whitelist_member(&sender), BadOrigin);
+			}
+
+			T::ControllerManager::set_user_emode(&sender, category_id)?;
+			Self::deposit_event(Event::UserEModeSet(sender, category_id));
+			Ok(().into())
+		}
+
 		/// Claim all the MNT accrued by holder in the specified markets.
 		/// - `pools`: The vector of markets to claim MNT in
 		#[pallet::weight(T::ProtocolWeightInfo::claim_mnt())]
@@ -553,6 +587,45 @@ pub mod module {
 			Self::deposit_event(Event::Claimed(who));
 			Ok(().into())
 		}
+
+		/// Repays a part of the unsafe loan of the borrower and seizes the borrower's
+		/// collateral at a discount.
+		///
+		/// - `borrower`: the account with the unsafe loan.
+		/// - `repay_pool_id`: the currency ID of the underlying asset to be repaid.
+		/// - `collateral_pool_id`: the currency ID of the underlying asset of the collateral to
+		/// be seized.
+		/// - `repay_amount`: the amount of the underlying borrowed asset to be repaid. Limited by
+		/// the close factor.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		#[pallet::weight(T::ProtocolWeightInfo::liquidate_borrow())]
+		#[transactional]
+		pub fn liquidate_borrow(
+			origin: OriginFor<T>,
+			borrower: T::AccountId,
+			repay_pool_id: CurrencyId,
+			collateral_pool_id: CurrencyId,
+			repay_amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+
+			if T::WhitelistManager::is_whitelist_mode_enabled() {
+				ensure!(T::WhitelistManager::is_whitelist_member(&who), BadOrigin);
+			}
+
+			let (repaid_amount, seized_wrapped) =
+				Self::do_liquidate_borrow(&who, &borrower, repay_pool_id, collateral_pool_id, repay_amount)?;
+			Self::deposit_event(Event::LiquidatedBorrow(
+				who,
+				borrower,
+				repay_pool_id,
+				repaid_amount,
+				collateral_pool_id,
+				seized_wrapped,
+			));
+			Ok(().into())
+		}
 	}
 }
 
@@ -625,6 +698,9 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::OperationPaused
 		);
 
+		// Fail if supply cap is reached
+		T::ControllerManager::deposit_allowed(underlying_asset, deposit_underlying_amount)?;
+
 		let wrapped_id = underlying_asset
 			.wrapped_asset()
 			.ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
@@ -763,6 +839,7 @@ impl<T: Config> Pallet<T> {
 		let account_borrows = T::ControllerManager::borrow_balance_stored(&who, underlying_asset)?;
 
 		T::ManagerLiquidityPools::update_state_on_borrow(&who, underlying_asset, borrow_amount, account_borrows)?;
+		T::ControllerManager::increase_isolation_mode_debt(&who, underlying_asset, borrow_amount)?;
 
 		// Transfer the borrow_amount from the protocol account to the borrower's account.
 		T::MultiCurrency::transfer(
@@ -851,6 +928,66 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Liquidator repays a part of the borrower's unsafe loan and receives the borrower's
+	/// wrapped tokens of the collateral pool.
+	///
+	/// - `liquidator`: the account paying off the borrow.
+	/// - `borrower`: the account with the unsafe loan.
+	/// - `repay_pool_id`: the currency ID of the underlying asset to repay.
+	/// - `collateral_pool_id`: the currency ID of the underlying asset of the collateral to seize.
+	/// - `repay_amount`: the amount of the underlying asset to repay.
+	///
+	/// Returns the amount of the underlying asset actually repaid and the amount of wrapped tokens
+	/// transferred to the liquidator.
+	fn do_liquidate_borrow(
+		liquidator: &T::AccountId,
+		borrower: &T::AccountId,
+		repay_pool_id: CurrencyId,
+		collateral_pool_id: CurrencyId,
+		repay_amount: Balance,
+	) -> LiquidationResult {
+		ensure!(liquidator != borrower, Error::<T>::CannotLiquidateSelf);
+
+		for pool_id in [repay_pool_id, collateral_pool_id].iter() {
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(T::ManagerLiquidityPools::pool_exists(pool_id), Error::<T>::PoolNotFound);
+			T::ControllerManager::accrue_interest_rate(*pool_id).map_err(|_| Error::<T>::AccrueInterestFailed)?;
+		}
+
+		// Fail if the loan is safe or the repay amount exceeds the close factor.
+		T::ControllerManager::liquidate_borrow_allowed(repay_pool_id, collateral_pool_id, borrower, repay_amount)?;
+
+		let repay_amount = Self::do_repay_fresh(liquidator, borrower, repay_pool_id, repay_amount, false)?;
+
+		let liquidation_fee = T::RiskManager::get_liquidation_fee(repay_pool_id);
+		let seize_wrapped = T::ControllerManager::liquidate_calculate_seize_tokens(
+			repay_pool_id,
+			collateral_pool_id,
+			repay_amount,
+			liquidation_fee,
+		)?;
+
+		let wrapped_id = collateral_pool_id
+			.wrapped_asset()
+			.ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		ensure!(
+			seize_wrapped <= T::MultiCurrency::free_balance(wrapped_id, &borrower),
+			Error::<T>::LiquidateSeizeTooMuch
+		);
+
+		T::MntManager::update_pool_mnt_supply_index(collateral_pool_id)?;
+		T::MntManager::distribute_supplier_mnt(collateral_pool_id, borrower, false)?;
+		T::MntManager::distribute_supplier_mnt(collateral_pool_id, liquidator, false)?;
+
+		// Transfer the seized wrapped tokens from the borrower to the liquidator.
+		T::MultiCurrency::transfer(wrapped_id, &borrower, &liquidator, seize_wrapped)?;
+
+		Ok((repay_amount, seize_wrapped))
+	}
+
 	fn transfer_protocol_interest(pool_id: CurrencyId) {
 		let pool_protocol_interest = T::ManagerLiquidityPools::get_pool_protocol_interest(pool_id);
 		if pool_protocol_interest < T::ControllerManager::get_protocol_interest_threshold(pool_id) {
@@ -945,6 +1082,7 @@ impl<T: Config> Pallet<T> {
 		);
 
 		T::ManagerLiquidityPools::update_state_on_repay(&borrower, underlying_asset, repay_amount, account_borrows)?;
+		T::ControllerManager::decrease_isolation_mode_debt(&borrower, underlying_asset, repay_amount, account_borrows)?;
 
 		// Transfer the repay_amount from the borrower's account to the protocol account.
 		T::MultiCurrency::transfer(
@@ -956,4 +1094,65 @@ impl<T: Config> Pallet<T> {
 
 		Ok(repay_amount)
 	}
+
+	/// Seizes the collateral of the borrower and transfers the equivalent amount of the
+	/// underlying asset to the liquidation pools.
+	///
+	/// - `borrower`: the account whose collateral is seized.
+	/// - `underlying_asset`: the currency ID of the collateral pool.
+	/// - `seize_underlying`: the amount of the underlying asset to seize.
+	///
+	/// Note: this function should be used after `accrue_interest_rate`.
+	pub fn do_seize(
+		borrower: &T::AccountId,
+		underlying_asset: CurrencyId,
+		seize_underlying: Balance,
+	) -> DispatchResult {
+		ensure!(!seize_underlying.is_zero(), Error::<T>::ZeroBalanceTransaction);
+
+		let wrapped_id = underlying_asset
+			.wrapped_asset()
+			.ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		let exchange_rate = T::ManagerLiquidityPools::get_exchange_rate(underlying_asset)?;
+		let seize_wrapped = T::ManagerLiquidityPools::underlying_to_wrapped(seize_underlying, exchange_rate)?;
+
+		ensure!(
+			seize_wrapped <= T::MultiCurrency::free_balance(wrapped_id, &borrower),
+			Error::<T>::NotEnoughWrappedTokens
+		);
+		ensure!(
+			seize_underlying <= T::ManagerLiquidityPools::get_pool_available_liquidity(underlying_asset),
+			Error::<T>::NotEnoughLiquidityAvailable
+		);
+
+		T::MntManager::update_pool_mnt_supply_index(underlying_asset)?;
+		T::MntManager::distribute_supplier_mnt(underlying_asset, borrower, false)?;
+
+		T::MultiCurrency::withdraw(wrapped_id, &borrower, seize_wrapped)?;
+
+		T::MultiCurrency::transfer(
+			underlying_asset,
+			&T::ManagerLiquidityPools::pools_account_id(),
+			&T::ManagerLiquidationPools::pools_account_id(),
+			seize_underlying,
+		)?;
+
+		Ok(())
+	}
+}
+
+impl<T: Config> MinterestProtocolManager<T::AccountId> for Pallet<T> {
+	fn do_repay_fresh(
+		who: &T::AccountId,
+		borrower: &T::AccountId,
+		underlying_asset: CurrencyId,
+		repay_amount: Balance,
+		all_assets: bool,
+	) -> BalanceResult {
+		Self::do_repay_fresh(who, borrower, underlying_asset, repay_amount, all_assets)
+	}
+
+	fn do_seize(borrower: &T::AccountId, underlying_asset: CurrencyId, seize_underlying: Balance) -> DispatchResult {
+		Self::do_seize(borrower, underlying_asset, seize_underlying)
+	}
 }
@@ -2,13 +2,15 @@
 
 use super::*;
 use crate as minterest_protocol;
-use controller::{ControllerData, PauseKeeper};
+use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 use frame_support::{ord_parameter_types, pallet_prelude::GenesisBuild, parameter_types, PalletId};
 use frame_system::EnsureSignedBy;
 use liquidity_pools::{Pool, PoolUserData};
 use minterest_model::MinterestModelData;
 pub use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
-use minterest_primitives::{Balance, CurrencyId, Price, Rate};
+use minterest_primitives::{
+	constants::currency::USD_DECIMALS, currency::GetDecimals, Balance, CurrencyId, Price, Rate,
+};
 use orml_traits::parameter_type_with_key;
 use pallet_traits::PricesManager;
 use sp_core::H256;
@@ -79,8 +81,13 @@ mock_impl_whitelist_module_config!(Test, OneAlice);
 pub struct MockPriceSource;
 
 impl PricesManager<CurrencyId> for MockPriceSource {
-	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
-		Some(Price::one())
+	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
+		// The price is set so that `DOLLARS` of any asset are worth 1$ regardless of
+		// the asset decimals.
+		Some(Price::saturating_from_rational(
+			1,
+			10_u128.pow(USD_DECIMALS - currency_id.decimals()),
+		))
 	}
 
 	fn lock_price(_currency_id: CurrencyId) {}
@@ -123,6 +130,8 @@ impl Default for ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -134,6 +143,8 @@ impl Default for ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -145,6 +156,8 @@ impl Default for ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -156,6 +169,8 @@ impl Default for ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			],
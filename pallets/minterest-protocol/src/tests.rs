@@ -3,7 +3,7 @@
 use super::*;
 use mock::{Event, *};
 
-use controller::{ControllerData, PauseKeeper};
+use controller::{ControllerData, EModeCategory, InterestAccrualMode, PauseKeeper};
 use frame_support::{assert_noop, assert_ok, error::BadOrigin};
 use liquidation_pools::LiquidationPoolData;
 use liquidity_pools::Pool;
@@ -62,6 +62,8 @@ fn create_pool_should_work() {
 					collateral_factor: Rate::saturating_from_rational(9, 10),
 					borrow_cap: None,
 					protocol_interest_threshold: 100000,
+					supply_cap: None,
+					interest_accrual_mode: InterestAccrualMode::Simple,
 				},
 			);
 			assert_eq!(Controller::pause_keepers(DOT), PauseKeeper::all_unpaused());
@@ -718,6 +720,55 @@ fn repay_should_work() {
 		});
 }
 
+#[test]
+fn repay_should_work_when_borrow_rate_is_clamped() {
+	ExtBuilder::default()
+		.pool_with_params(DOT, Balance::zero(), Rate::saturating_from_rational(1, 1), TEN_THOUSAND)
+		.set_minterest_model_params(vec![(
+			DOT,
+			MinterestModelData {
+				kink: Rate::saturating_from_rational(8, 10),
+				base_rate_per_block: Rate::zero(),
+				multiplier_per_block: Rate::saturating_from_rational(9, 1_000_000_000),
+				jump_multiplier_per_block: Rate::saturating_from_rational(207, 1_000_000_000),
+			},
+		)])
+		.build()
+		.execute_with(|| {
+			// Alice deposited 60 DOT to the pool and borrowed 30 DOT from the pool.
+			assert_ok!(TestMinterestProtocol::deposit_underlying(
+				alice_origin(),
+				DOT,
+				dollars(60_u128)
+			));
+			assert_ok!(TestMinterestProtocol::borrow(alice_origin(), DOT, dollars(30_u128)));
+
+			// The borrow rate given by the interest rate model exceeds the maximum borrow rate.
+			assert_ok!(Controller::set_max_borrow_rate(
+				alice_origin(),
+				DOT,
+				Rate::saturating_from_rational(1, 1_000_000_000_000_u128)
+			));
+			System::set_block_number(10);
+			assert_noop!(
+				TestMinterestProtocol::repay(alice_origin(), DOT, dollars(10_u128)),
+				Error::<Test>::AccrueInterestFailed
+			);
+
+			assert_ok!(Controller::switch_borrow_rate_clamping(alice_origin(), DOT, true));
+			assert_ok!(TestMinterestProtocol::repay(alice_origin(), DOT, dollars(10_u128)));
+			assert_ok!(TestMinterestProtocol::deposit_underlying(
+				alice_origin(),
+				DOT,
+				dollars(10_u128)
+			));
+			assert_noop!(
+				TestMinterestProtocol::borrow(alice_origin(), DOT, dollars(10_u128)),
+				controller::Error::<Test>::BorrowRateTooHigh
+			);
+		});
+}
+
 #[test]
 fn repay_all_should_work() {
 	ExtBuilder::default()
@@ -854,6 +905,105 @@ fn repay_on_behalf_should_work() {
 		});
 }
 
+#[test]
+fn liquidate_borrow_should_work() {
+	ExtBuilder::default()
+		.pool_with_params(DOT, Balance::zero(), Rate::one(), TEN_THOUSAND)
+		.pool_with_params(ETH, Balance::zero(), Rate::one(), TEN_THOUSAND)
+		.build()
+		.execute_with(|| {
+			// Bob deposited 100 DOT to the pool and borrowed 80 ETH.
+			assert_ok!(TestMinterestProtocol::deposit_underlying(
+				bob_origin(),
+				DOT,
+				ONE_HUNDRED
+			));
+			assert_ok!(TestMinterestProtocol::borrow(bob_origin(), ETH, dollars(80_u128)));
+
+			// Bob's loan is safe.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, ETH, DOT, dollars(10_u128)),
+				controller::Error::<Test>::InsufficientShortfall
+			);
+
+			// Collateral factor of the DOT pool decreased to 50%.
+			// Bob's collateral = 100 * 0.5 = 50$, borrow = 80$, shortfall = 30$.
+			assert_ok!(Controller::set_collateral_factor(
+				alice_origin(),
+				DOT,
+				Rate::saturating_from_rational(1, 2)
+			));
+			assert_ok!(TestRiskManager::set_liquidation_fee(
+				alice_origin(),
+				ETH,
+				Rate::saturating_from_rational(5, 100)
+			));
+
+			// Bob cannot liquidate his own loan.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(bob_origin(), BOB, ETH, DOT, dollars(10_u128)),
+				Error::<Test>::CannotLiquidateSelf
+			);
+
+			// MDOT is wrong CurrencyId for underlying assets.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, ETH, MDOT, dollars(10_u128)),
+				Error::<Test>::NotValidUnderlyingAssetId
+			);
+
+			// BTC pool does not exist.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, BTC, DOT, dollars(10_u128)),
+				Error::<Test>::PoolNotFound
+			);
+
+			// ETH pool is not enabled as collateral for Bob.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, ETH, ETH, dollars(10_u128)),
+				controller::Error::<Test>::PoolIsNotCollateral
+			);
+
+			// Alice cannot repay more than 80 * 0.5 = 40 ETH.
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, ETH, DOT, dollars(41_u128)),
+				controller::Error::<Test>::TooMuchRepay
+			);
+
+			// Whitelist Mode is enabled. In whitelist mode, only members
+			// from whitelist can work with protocol.
+			assert_ok!(TestWhitelist::switch_whitelist_mode(alice_origin(), true));
+			assert_noop!(
+				TestMinterestProtocol::liquidate_borrow(alice_origin(), BOB, ETH, DOT, dollars(40_u128)),
+				BadOrigin
+			);
+			assert_ok!(TestWhitelist::switch_whitelist_mode(alice_origin(), false));
+
+			// Alice repaid 40 ETH for Bob and seized 40 * 1.05 = 42 MDOT.
+			assert_ok!(TestMinterestProtocol::liquidate_borrow(
+				alice_origin(),
+				BOB,
+				ETH,
+				DOT,
+				dollars(40_u128)
+			));
+			let expected_event = Event::TestMinterestProtocol(crate::Event::LiquidatedBorrow(
+				ALICE,
+				BOB,
+				ETH,
+				dollars(40_u128),
+				DOT,
+				dollars(42_u128),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			assert_eq!(Currencies::free_balance(ETH, &ALICE), dollars(60_u128));
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), dollars(42_u128));
+			assert_eq!(Currencies::free_balance(MDOT, &BOB), dollars(58_u128));
+			assert_eq!(TestPools::pool_user_data(ETH, BOB).borrowed, dollars(40_u128));
+			assert_eq!(TestPools::get_pool_available_liquidity(ETH), dollars(9_960_u128));
+		});
+}
+
 #[test]
 fn enable_is_collateral_should_work() {
 	ExtBuilder::default()
@@ -953,6 +1103,62 @@ fn disable_is_collateral_should_work() {
 		});
 }
 
+#[test]
+fn set_user_emode_should_work() {
+	ExtBuilder::default()
+		.pool_with_params(DOT, Balance::zero(), Rate::saturating_from_rational(1, 1), TEN_THOUSAND)
+		.pool_with_params(ETH, Balance::zero(), Rate::saturating_from_rational(1, 1), TEN_THOUSAND)
+		.build()
+		.execute_with(|| {
+			// Alice deposit 60 ETH and enable as collateral her ETH pool.
+			assert_ok!(TestMinterestProtocol::deposit_underlying(
+				alice_origin(),
+				ETH,
+				dollars(60_u128)
+			));
+			assert_ok!(TestMinterestProtocol::enable_is_collateral(alice_origin(), ETH));
+
+			assert_noop!(
+				TestMinterestProtocol::set_user_emode(alice_origin(), Some(1)),
+				controller::Error::<Test>::EModeCategoryNotFound
+			);
+			assert_ok!(Controller::set_emode_category(
+				alice_origin(),
+				1,
+				EModeCategory {
+					collateral_factor: Rate::saturating_from_rational(95, 100),
+					liquidation_threshold: Rate::saturating_from_rational(5, 100),
+				}
+			));
+
+			// ETH pool doesn't belong to the category.
+			assert_noop!(
+				TestMinterestProtocol::set_user_emode(alice_origin(), Some(1)),
+				controller::Error::<Test>::EModeCategoryMismatch
+			);
+			assert_ok!(Controller::set_pool_emode_category(alice_origin(), ETH, Some(1)));
+
+			// Whitelist Mode is enabled. In whitelist mode, only members
+			// from whitelist can work with protocol.
+			assert_ok!(TestWhitelist::switch_whitelist_mode(alice_origin(), true));
+			assert_noop!(
+				TestMinterestProtocol::set_user_emode(alice_origin(), Some(1)),
+				BadOrigin
+			);
+			assert_ok!(TestWhitelist::switch_whitelist_mode(alice_origin(), false));
+
+			assert_ok!(TestMinterestProtocol::set_user_emode(alice_origin(), Some(1)));
+			let expected_event = Event::TestMinterestProtocol(crate::Event::UserEModeSet(ALICE, Some(1)));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Controller::user_emode_category(ALICE), Some(1));
+
+			assert_ok!(TestMinterestProtocol::set_user_emode(alice_origin(), None));
+			let expected_event = Event::TestMinterestProtocol(crate::Event::UserEModeSet(ALICE, None));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Controller::user_emode_category(ALICE), None);
+		});
+}
+
 #[test]
 fn transfer_wrapped_should_work() {
 	ExtBuilder::default()
@@ -57,7 +57,9 @@ pub trait WeightInfo {
 	fn transfer_wrapped() -> Weight;
 	fn enable_is_collateral() -> Weight;
 	fn disable_is_collateral() -> Weight;
+	fn set_user_emode() -> Weight;
 	fn claim_mnt() -> Weight;
+	fn liquidate_borrow() -> Weight;
 }
 
 /// Weights for minterest_protocol using the Minterest node and recommended hardware.
@@ -123,11 +125,21 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(31 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_user_emode() -> Weight {
+		(398_540_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn claim_mnt() -> Weight {
 		(1_145_847_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(35 as Weight))
 			.saturating_add(T::DbWeight::get().writes(15 as Weight))
 	}
+	fn liquidate_borrow() -> Weight {
+		(1_052_418_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(45 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -192,9 +204,19 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(31 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	fn set_user_emode() -> Weight {
+		(398_540_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(30 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	fn claim_mnt() -> Weight {
 		(1_145_847_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(35 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(15 as Weight))
 	}
+	fn liquidate_borrow() -> Weight {
+		(1_052_418_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(45 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
 }
@@ -556,9 +556,10 @@ impl<T: Config> MntManager<T::AccountId> for Pallet<T> {
 		let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
 		let pool_supply_wrapped = T::MultiCurrency::total_issuance(wrapped_id);
 
-		let pool_borrow_in_usd = T::LiquidityPoolsManager::underlying_to_usd(pool_borrow_underlying, oracle_price)?;
+		let pool_borrow_in_usd =
+			T::LiquidityPoolsManager::underlying_to_usd(pool_id, pool_borrow_underlying, oracle_price)?;
 		let pool_tvl_in_usd =
-			T::LiquidityPoolsManager::wrapped_to_usd(pool_supply_wrapped, exchange_rate, oracle_price)?;
+			T::LiquidityPoolsManager::wrapped_to_usd(pool_id, pool_supply_wrapped, exchange_rate, oracle_price)?;
 
 		let mnt_speed = MntSpeedStorage::<T>::get(pool_id);
 		let rate_calculation = |x: Balance| {
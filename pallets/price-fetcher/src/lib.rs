@@ -0,0 +1,347 @@
+//! # Price Fetcher Module
+//!
+//! ## Overview
+//!
+//! Price fetcher runs an offchain worker on the nodes of the oracle operators. Every
+//! `FetchPeriod` blocks the worker requests the prices from the endpoints configured on-chain,
+//! takes the median price of each currency and submits it to `MinterestOracle` with a signed
+//! `feed_values` transaction.
+//!
+//! The transaction is signed with the key of the `orcl` type found in the node keystore, so the
+//! account of this key must be a member of the oracle operators. The key can be added with the
+//! `author_insertKey` RPC.
+//!
+//! ## Response format
+//!
+//! Every endpoint is requested with `GET` and must return a JSON object that maps the token
+//! symbols to their USD prices, given as JSON numbers:
+//!
+//! ``` json
+//! {"DOT": 40.25, "KSM": 412.5, "BTC": 48000, "ETH": 3.1e3}
+//! ```
+//!
+//! Symbols of the currencies that are not underlying assets of the protocol are ignored, as well
+//! as prices with more than 18 decimal places. Endpoints that can't be reached or return an
+//! invalid response are skipped.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+#![allow(clippy::upper_case_acronyms)]
+
+use frame_support::{log, pallet_prelude::*, transactional};
+use frame_system::{
+	offchain::{AppCrypto, CreateSignedTransaction, SendSignedTransaction, Signer},
+	pallet_prelude::*,
+};
+use lite_json::{JsonValue, NumberValue};
+use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, CurrencyId, OffchainErr, Price};
+pub use module::*;
+use sp_core::crypto::KeyTypeId;
+use sp_runtime::{
+	offchain::{
+		http,
+		storage_lock::{StorageLock, Time},
+		Duration,
+	},
+	traits::{CheckedAdd, CheckedDiv, Zero},
+	FixedPointNumber,
+};
+use sp_std::{prelude::*, str};
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+
+pub mod weights;
+pub use weights::WeightInfo;
+
+/// The key type of the oracle operators keys.
+pub const KEY_TYPE: KeyTypeId = KeyTypeId(*b"orcl");
+
+/// The maximum length of the endpoint URL.
+pub const MAX_ENDPOINT_LENGTH: usize = 256;
+
+const OFFCHAIN_PRICE_FETCHER_LOCK: &[u8] = b"pallets/price-fetcher/lock/";
+
+/// Time to wait for the response of the endpoint, in milliseconds.
+const FETCH_TIMEOUT_PERIOD: u64 = 3_000;
+
+/// The application crypto of the oracle operators keys.
+pub mod crypto {
+	use super::KEY_TYPE;
+	use sp_core::sr25519::Signature as Sr25519Signature;
+	use sp_runtime::{
+		app_crypto::{app_crypto, sr25519},
+		traits::Verify,
+		MultiSignature, MultiSigner,
+	};
+	app_crypto!(sr25519, KEY_TYPE);
+
+	pub struct OracleAuthId;
+
+	impl frame_system::offchain::AppCrypto<MultiSigner, MultiSignature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+
+	impl frame_system::offchain::AppCrypto<<Sr25519Signature as Verify>::Signer, Sr25519Signature> for OracleAuthId {
+		type RuntimeAppPublic = Public;
+		type GenericSignature = sp_core::sr25519::Signature;
+		type GenericPublic = sp_core::sr25519::Public;
+	}
+}
+
+/// The instance of the oracle fed by the price fetcher.
+pub type MinterestOracleInstance = orml_oracle::Instance1;
+
+type OracleCall<T> = orml_oracle::Call<T, MinterestOracleInstance>;
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config:
+		frame_system::Config
+		+ orml_oracle::Config<MinterestOracleInstance, OracleKey = CurrencyId, OracleValue = Price>
+		+ CreateSignedTransaction<OracleCall<Self>>
+	{
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The identifier type for the oracle operator key.
+		type AuthorityId: AppCrypto<Self::Public, Self::Signature>;
+
+		/// The number of blocks between two price fetches.
+		#[pallet::constant]
+		type FetchPeriod: Get<Self::BlockNumber>;
+
+		/// The maximum number of the price endpoints.
+		#[pallet::constant]
+		type MaxEndpoints: Get<u32>;
+
+		/// The origin which may update the price endpoints.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The number of endpoints exceeds `MaxEndpoints`.
+		TooManyEndpoints,
+		/// The endpoint is not a valid UTF-8 string or is too long.
+		InvalidEndpoint,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The price endpoints have been changed. \[endpoints\]
+		EndpointsChanged(Vec<Vec<u8>>),
+	}
+
+	/// The URLs the prices are fetched from.
+	#[pallet::storage]
+	#[pallet::getter(fn endpoints)]
+	pub type Endpoints<T: Config> = StorageValue<_, Vec<Vec<u8>>, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn offchain_worker(now: T::BlockNumber) {
+			let fetch_period = T::FetchPeriod::get();
+			if fetch_period.is_zero() || !(now % fetch_period).is_zero() {
+				return;
+			}
+			if let Err(error) = Self::_offchain_worker() {
+				log::info!(
+					target: "PriceFetcher offchain worker",
+					"cannot run offchain worker at {:?}: {:?}",
+					now,
+					error,
+				);
+			} else {
+				log::debug!(
+					target: "PriceFetcher offchain worker",
+					" PriceFetcher offchain worker start at block: {:?} already done!",
+					now,
+				);
+			}
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Set the URLs the prices are fetched from.
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `endpoints`: the URLs of the endpoints, an empty list stops fetching the prices.
+		#[pallet::weight((T::WeightInfo::set_endpoints(endpoints.len() as u32), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_endpoints(origin: OriginFor<T>, endpoints: Vec<Vec<u8>>) -> DispatchResultWithPostInfo {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			ensure!(
+				endpoints.len() <= T::MaxEndpoints::get() as usize,
+				Error::<T>::TooManyEndpoints
+			);
+			ensure!(
+				endpoints
+					.iter()
+					.all(|endpoint| endpoint.len() <= MAX_ENDPOINT_LENGTH && str::from_utf8(endpoint).is_ok()),
+				Error::<T>::InvalidEndpoint
+			);
+
+			Endpoints::<T>::put(endpoints.clone());
+			Self::deposit_event(Event::EndpointsChanged(endpoints));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	fn _offchain_worker() -> Result<(), OffchainErr> {
+		let endpoints = Self::endpoints();
+		if endpoints.is_empty() {
+			return Ok(());
+		}
+		let signer = Signer::<T, T::AuthorityId>::any_account();
+		if !signer.can_sign() {
+			return Err(OffchainErr::NoSigningKey);
+		}
+		let mut lock = StorageLock::<Time>::new(&OFFCHAIN_PRICE_FETCHER_LOCK);
+		// If the prices are already being fetched should be returned OffchainLock error.
+		let _guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+
+		let responses: Vec<Vec<(CurrencyId, Price)>> = endpoints
+			.iter()
+			.filter_map(|endpoint| {
+				Self::fetch_prices(endpoint)
+					.map_err(|error| {
+						log::warn!(
+							target: "PriceFetcher offchain worker",
+							"cannot fetch prices from {:?}: {:?}",
+							str::from_utf8(endpoint).unwrap_or_default(),
+							error,
+						);
+					})
+					.ok()
+			})
+			.collect();
+		let values = Self::median_prices(responses);
+		if values.is_empty() {
+			return Err(OffchainErr::PriceFetchError);
+		}
+
+		match signer.send_signed_transaction(|_account| OracleCall::<T>::feed_values(values.clone())) {
+			Some((_, Ok(()))) => Ok(()),
+			_ => Err(OffchainErr::SubmitTransaction),
+		}
+	}
+
+	/// Requests the prices from the endpoint and parses the response.
+	fn fetch_prices(endpoint: &[u8]) -> Result<Vec<(CurrencyId, Price)>, http::Error> {
+		let url = str::from_utf8(endpoint).map_err(|_| http::Error::Unknown)?;
+		let deadline = sp_io::offchain::timestamp().add(Duration::from_millis(FETCH_TIMEOUT_PERIOD));
+		let pending = http::Request::get(url)
+			.deadline(deadline)
+			.send()
+			.map_err(|_| http::Error::IoError)?;
+		let response = pending.try_wait(deadline).map_err(|_| http::Error::DeadlineReached)??;
+		if response.code != 200 {
+			return Err(http::Error::Unknown);
+		}
+		let body = response.body().collect::<Vec<u8>>();
+		let body = str::from_utf8(&body).map_err(|_| http::Error::Unknown)?;
+		Self::parse_prices(body).ok_or(http::Error::Unknown)
+	}
+
+	/// Parses the JSON object that maps the token symbols to their prices.
+	/// Returns the prices of the underlying assets of the protocol.
+	pub(crate) fn parse_prices(body: &str) -> Option<Vec<(CurrencyId, Price)>> {
+		let prices = match lite_json::parse_json(body).ok()? {
+			JsonValue::Object(prices) => prices,
+			_ => return None,
+		};
+		let prices = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter_map(|currency_id| {
+				let symbol = match currency_id {
+					CurrencyId::UnderlyingAsset(symbol) => symbol.symbol(),
+					_ => return None,
+				};
+				let price = prices
+					.iter()
+					.find(|(key, _)| key.iter().copied().eq(symbol.chars()))
+					.and_then(|(_, value)| match value {
+						JsonValue::Number(number) => Self::number_to_price(number),
+						_ => None,
+					})?;
+				Some((currency_id, price))
+			})
+			.collect();
+		Some(prices)
+	}
+
+	/// Converts the JSON number to the price: (integer + fraction / 10^fraction_length) *
+	/// 10^exponent
+	fn number_to_price(number: &NumberValue) -> Option<Price> {
+		if number.negative || number.fraction_length > 18 {
+			return None;
+		}
+		let decimals = Price::accuracy().checked_div(10_u128.checked_pow(number.fraction_length)?)?;
+		let inner = (number.integer as u128)
+			.checked_mul(Price::accuracy())?
+			.checked_add((number.fraction as u128).checked_mul(decimals)?)?;
+		let exponent = 10_u128.checked_pow(number.exponent.checked_abs()? as u32)?;
+		let inner = if number.exponent >= 0 {
+			inner.checked_mul(exponent)?
+		} else {
+			inner.checked_div(exponent)?
+		};
+		Some(Price::from_inner(inner))
+	}
+
+	/// Takes the median of the prices of each currency returned by the endpoints.
+	pub(crate) fn median_prices(responses: Vec<Vec<(CurrencyId, Price)>>) -> Vec<(CurrencyId, Price)> {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter_map(|currency_id| {
+				let mut prices: Vec<Price> = responses
+					.iter()
+					.filter_map(|response| {
+						response
+							.iter()
+							.find(|(price_currency_id, _)| *price_currency_id == currency_id)
+							.map(|(_, price)| *price)
+					})
+					.collect();
+				Self::median(&mut prices).map(|price| (currency_id, price))
+			})
+			.collect()
+	}
+
+	/// Returns the median of the prices. For an even number of prices it is the average of the
+	/// two middle prices.
+	fn median(prices: &mut Vec<Price>) -> Option<Price> {
+		if prices.is_empty() {
+			return None;
+		}
+		prices.sort();
+		let middle = prices.len() / 2;
+		if prices.len() % 2 == 1 {
+			Some(prices[middle])
+		} else {
+			prices[middle - 1]
+				.checked_add(&prices[middle])?
+				.checked_div(&Price::saturating_from_integer(2))
+		}
+	}
+}
@@ -0,0 +1,141 @@
+/// Mocks for the price-fetcher pallet.
+use super::*;
+use crate as price_fetcher;
+use frame_support::{ord_parameter_types, parameter_types, traits::SortedMembers};
+use frame_system::EnsureSignedBy;
+use minterest_primitives::{Balance, Moment};
+use sp_core::{sr25519, H256};
+use sp_io::TestExternalities;
+use sp_runtime::{
+	testing::{Header, TestXt},
+	traits::{BlakeTwo256, Extrinsic as ExtrinsicT, IdentifyAccount, IdentityLookup, Verify},
+};
+pub use test_helper::*;
+
+type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
+type Block = frame_system::mocking::MockBlock<Test>;
+
+type Signature = sr25519::Signature;
+pub type AccountId = <<Signature as Verify>::Signer as IdentifyAccount>::AccountId;
+pub type Extrinsic = TestXt<Call, ()>;
+
+// Configure a mock runtime to test the pallet.
+frame_support::construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		MinterestOracle: orml_oracle::<Instance1>::{Pallet, Storage, Call, Event<T>},
+		TestPriceFetcher: price_fetcher::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+mock_impl_system_config!(Test, AccountId);
+
+parameter_types! {
+	pub const MinimumCount: u32 = 1;
+	pub const ExpiresIn: Moment = 1000 * 60 * 60; // 60 mins
+	pub const MaxHasDispatchedSize: u32 = 100;
+	pub RootOperatorAccountId: AccountId = sr25519::Public([0xff; 32]);
+}
+
+pub struct MockTime;
+impl frame_support::traits::Time for MockTime {
+	type Moment = Moment;
+
+	fn now() -> Moment {
+		Moment::zero()
+	}
+}
+
+pub struct MockOracleMembers;
+impl SortedMembers<AccountId> for MockOracleMembers {
+	fn sorted_members() -> Vec<AccountId> {
+		vec![]
+	}
+}
+
+impl orml_oracle::Config<MinterestOracleInstance> for Test {
+	type Event = Event;
+	type OnNewData = ();
+	type CombineData = orml_oracle::DefaultCombineData<Test, MinimumCount, ExpiresIn, MinterestOracleInstance>;
+	type Time = MockTime;
+	type OracleKey = CurrencyId;
+	type OracleValue = Price;
+	type RootOperatorAccountId = RootOperatorAccountId;
+	type WeightInfo = ();
+	type Members = MockOracleMembers;
+	type MaxHasDispatchedSize = MaxHasDispatchedSize;
+}
+
+impl frame_system::offchain::SigningTypes for Test {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::SendTransactionTypes<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	type OverarchingCall = Call;
+	type Extrinsic = Extrinsic;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Test
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		_public: <Signature as Verify>::Signer,
+		_account: AccountId,
+		nonce: u64,
+	) -> Option<(Call, <Extrinsic as ExtrinsicT>::SignaturePayload)> {
+		Some((call, (nonce, ())))
+	}
+}
+
+ord_parameter_types! {
+	pub const ZeroAdmin: AccountId = sr25519::Public([0u8; 32]);
+}
+
+parameter_types! {
+	pub const FetchPeriod: u64 = 10;
+	pub const MaxEndpoints: u32 = 3;
+}
+
+impl price_fetcher::Config for Test {
+	type Event = Event;
+	type AuthorityId = crypto::OracleAuthId;
+	type FetchPeriod = FetchPeriod;
+	type MaxEndpoints = MaxEndpoints;
+	type UpdateOrigin = EnsureSignedBy<ZeroAdmin, AccountId>;
+	type WeightInfo = ();
+}
+
+pub fn admin() -> Origin {
+	Origin::signed(ZeroAdmin::get())
+}
+
+pub fn alice() -> Origin {
+	Origin::signed(sr25519::Public([1u8; 32]))
+}
+
+pub struct ExternalityBuilder;
+
+impl Default for ExternalityBuilder {
+	fn default() -> Self {
+		Self
+	}
+}
+
+impl ExternalityBuilder {
+	pub fn build(self) -> TestExternalities {
+		let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let mut ext = TestExternalities::new(storage);
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
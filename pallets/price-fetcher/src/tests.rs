@@ -0,0 +1,164 @@
+//! Tests for the price-fetcher pallet.
+
+use super::*;
+use codec::Decode;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use sp_core::offchain::{
+	testing::{PendingRequest, TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
+use sp_keystore::{testing::KeyStore, KeystoreExt, SyncCryptoStore};
+use sp_runtime::{traits::BadOrigin, RuntimeAppPublic};
+use std::sync::Arc;
+use test_helper::offchain_ext::OffChainExtWithHooks;
+
+const PHRASE: &str = "news slush supreme milk chapter athlete soap sausage put clutch what kitten";
+
+fn price(value: u128) -> Price {
+	Price::saturating_from_integer(value)
+}
+
+fn expect_request(state: &mut sp_core::offchain::testing::OffchainState, uri: &str, response: &[u8]) {
+	state.expect_request(PendingRequest {
+		method: "GET".into(),
+		uri: uri.into(),
+		response: Some(response.to_vec()),
+		sent: true,
+		..Default::default()
+	});
+}
+
+#[test]
+fn set_endpoints_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let endpoints = vec![b"http://localhost:8080/prices".to_vec()];
+		assert_ok!(TestPriceFetcher::set_endpoints(admin(), endpoints.clone()));
+		assert_eq!(TestPriceFetcher::endpoints(), endpoints.clone());
+		let expected_event = Event::TestPriceFetcher(crate::Event::EndpointsChanged(endpoints.clone()));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		assert_noop!(TestPriceFetcher::set_endpoints(alice(), endpoints), BadOrigin);
+		assert_noop!(
+			TestPriceFetcher::set_endpoints(admin(), vec![b"http://localhost".to_vec(); 4]),
+			Error::<Test>::TooManyEndpoints
+		);
+		assert_noop!(
+			TestPriceFetcher::set_endpoints(admin(), vec![vec![0xff, 0xfe]]),
+			Error::<Test>::InvalidEndpoint
+		);
+		assert_noop!(
+			TestPriceFetcher::set_endpoints(admin(), vec![vec![b'a'; MAX_ENDPOINT_LENGTH + 1]]),
+			Error::<Test>::InvalidEndpoint
+		);
+
+		assert_ok!(TestPriceFetcher::set_endpoints(admin(), vec![]));
+		assert_eq!(TestPriceFetcher::endpoints(), Vec::<Vec<u8>>::new());
+	});
+}
+
+#[test]
+fn parse_prices_should_work() {
+	assert_eq!(
+		TestPriceFetcher::parse_prices(r#"{"DOT": 40.25, "KSM": 412, "BTC": 4.8e4, "ETH": 31e-1, "USDT": 1}"#),
+		Some(vec![
+			(DOT, Price::saturating_from_rational(4025, 100)),
+			(KSM, price(412)),
+			(BTC, price(48_000)),
+			(ETH, Price::saturating_from_rational(31, 10)),
+			(USDT, price(1)),
+		])
+	);
+	// Negative prices, prices that are not numbers, unknown symbols and wrapped tokens are
+	// ignored.
+	assert_eq!(
+		TestPriceFetcher::parse_prices(r#"{"DOT": -40, "KSM": "412", "ABC": 1, "MBTC": 48000, "ETH": 0.5}"#),
+		Some(vec![(ETH, Price::saturating_from_rational(1, 2))])
+	);
+	assert_eq!(TestPriceFetcher::parse_prices(r#"[{"DOT": 40}]"#), None);
+	assert_eq!(TestPriceFetcher::parse_prices("DOT: 40"), None);
+}
+
+#[test]
+fn median_prices_should_work() {
+	assert_eq!(
+		TestPriceFetcher::median_prices(vec![
+			vec![(DOT, price(40)), (BTC, price(48_000))],
+			vec![(DOT, price(42)), (BTC, price(47_000)), (ETH, price(3_000))],
+			vec![(DOT, price(39)), (ETH, price(3_100))],
+		]),
+		vec![(DOT, price(40)), (BTC, price(47_500)), (ETH, price(3_050))]
+	);
+	assert_eq!(TestPriceFetcher::median_prices(vec![]), vec![]);
+}
+
+#[test]
+fn offchain_worker_should_feed_median_prices() {
+	let mut ext = ExternalityBuilder::default().build();
+	let (offchain, state) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	let keystore = KeyStore::new();
+	SyncCryptoStore::sr25519_generate_new(&keystore, crypto::Public::ID, Some(&format!("{}/oracle", PHRASE))).unwrap();
+	ext.register_extension(OffchainDbExt::new(OffChainExtWithHooks::new(offchain.clone())));
+	ext.register_extension(OffchainWorkerExt::new(OffChainExtWithHooks::new(offchain)));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(keystore)));
+
+	{
+		let mut state = state.write();
+		expect_request(&mut state, "http://localhost:8080/a", br#"{"DOT": 40, "BTC": 48000}"#);
+		expect_request(&mut state, "http://localhost:8080/b", br#"{"DOT": 42, "BTC": 47000}"#);
+		expect_request(&mut state, "http://localhost:8080/c", br#"{"DOT": 39}"#);
+	}
+
+	ext.execute_with(|| {
+		assert_ok!(TestPriceFetcher::set_endpoints(
+			admin(),
+			vec![
+				b"http://localhost:8080/a".to_vec(),
+				b"http://localhost:8080/b".to_vec(),
+				b"http://localhost:8080/c".to_vec(),
+			]
+		));
+		assert_ok!(TestPriceFetcher::_offchain_worker());
+
+		// 1 signed feed transaction in transactions pool
+		assert_eq!(pool_state.read().transactions.len(), 1);
+		let transaction = pool_state.write().transactions.pop().unwrap();
+		let ex: Extrinsic = Decode::decode(&mut &*transaction).unwrap();
+		assert_eq!(ex.signature, Some((0, ())));
+		assert_eq!(
+			ex.call,
+			Call::MinterestOracle(orml_oracle::Call::feed_values(vec![
+				(DOT, price(40)),
+				(BTC, price(47_500)),
+			]))
+		);
+	});
+}
+
+#[test]
+fn offchain_worker_should_not_feed_without_key() {
+	let mut ext = ExternalityBuilder::default().build();
+	let (offchain, _) = TestOffchainExt::new();
+	let (pool, pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(OffChainExtWithHooks::new(offchain.clone())));
+	ext.register_extension(OffchainWorkerExt::new(OffChainExtWithHooks::new(offchain)));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(KeystoreExt(Arc::new(KeyStore::new())));
+
+	ext.execute_with(|| {
+		// Nothing to do without endpoints.
+		assert_ok!(TestPriceFetcher::_offchain_worker());
+
+		assert_ok!(TestPriceFetcher::set_endpoints(
+			admin(),
+			vec![b"http://localhost:8080/a".to_vec()]
+		));
+		assert!(matches!(
+			TestPriceFetcher::_offchain_worker(),
+			Err(OffchainErr::NoSigningKey)
+		));
+		assert!(pool_state.read().transactions.is_empty());
+	});
+}
@@ -10,7 +10,7 @@
 // The `unnecessary_mut_passed` warning originates from `decl_runtime_apis` macro.
 #![allow(clippy::unnecessary_mut_passed)]
 
-use minterest_primitives::{CurrencyId, Price};
+use minterest_primitives::{BlockNumber, CurrencyId, Moment, Price};
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
@@ -19,5 +19,7 @@ sp_api::decl_runtime_apis! {
 		fn  get_current_price(currency_id: CurrencyId) -> Option<Price>;
 		fn  get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>)>;
 		fn  get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)>;
+		fn  get_stale_price_feeds() -> Vec<(CurrencyId, Option<Moment>)>;
+		fn  get_price_history(currency_id: CurrencyId, from_block: BlockNumber, to_block: BlockNumber) -> Vec<(BlockNumber, Price)>;
 	}
 }
@@ -7,7 +7,7 @@
 
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
-use minterest_primitives::{CurrencyId, Price};
+use minterest_primitives::{BlockNumber, CurrencyId, Moment, Price};
 pub use prices_rpc_runtime_api::PricesRuntimeApi;
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
@@ -69,6 +69,50 @@ pub trait PricesRpcApi<BlockHash> {
 	/// ```
 	#[rpc(name = "prices_getAllFreshestPrices")]
 	fn get_all_freshest_prices(&self, at: Option<BlockHash>) -> Result<Vec<(CurrencyId, Option<Price>)>>;
+
+	/// This function returns a Vector containing the currencies whose oracle price is missing
+	/// or older than the maximum price age configured for the currency.
+	///
+	///  - `&self` :  Self reference
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	/// - Vec<(currency_id, timestamp)>: vector of (id, timestamp of the last oracle price) pairs
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"prices_getStalePriceFeeds", "params": []}'
+	/// ```
+	#[rpc(name = "prices_getStalePriceFeeds")]
+	fn get_stale_price_feeds(&self, at: Option<BlockHash>) -> Result<Vec<(CurrencyId, Option<Moment>)>>;
+
+	/// This function returns a Vector containing the oracle prices of the currency accepted by
+	/// the protocol between two blocks. Only the last prices kept in the price history are
+	/// available.
+	///
+	///  - `&self` :  Self reference
+	///  - `currency_id`: currency type.
+	///  - `from_block`: the first block of the range.
+	///  - `to_block`: the last block of the range.
+	///  - `at` : Needed for runtime API use. Runtime API must always be called at a specific block.
+	///
+	/// Return:
+	/// - Vec<(block_number, price)>: vector of (block number, price) pairs ordered by block number
+	///
+	/// # Example:
+	/// ``` ignore
+	/// curl http://localhost:9933 -H "Content-Type:application/json;charset=utf-8" -d '{"jsonrpc":"2.0",
+	/// "id":1, "method":"prices_getPriceHistory", "params": [{"UnderlyingAsset":"DOT"}, 100, 200]}'
+	/// ```
+	#[rpc(name = "prices_getPriceHistory")]
+	fn get_price_history(
+		&self,
+		currency_id: CurrencyId,
+		from_block: BlockNumber,
+		to_block: BlockNumber,
+		at: Option<BlockHash>,
+	) -> Result<Vec<(BlockNumber, Price)>>;
 }
 
 /// Struct that implement 'PricesRpcApi'.
@@ -145,4 +189,37 @@ where
 			data: Some(format!("{:?}", e).into()),
 		})
 	}
+
+	fn get_stale_price_feeds(&self, at: Option<<Block as BlockT>::Hash>) -> Result<Vec<(CurrencyId, Option<Moment>)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_stale_price_feeds(&at).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to get stale price feeds info.".into(),
+			data: Some(format!("{:?}", e).into()),
+		})
+	}
+
+	fn get_price_history(
+		&self,
+		currency_id: CurrencyId,
+		from_block: BlockNumber,
+		to_block: BlockNumber,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Vec<(BlockNumber, Price)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.get_price_history(&at, currency_id, from_block, to_block)
+			.map_err(|e| RpcError {
+				code: ErrorCode::ServerError(Error::RuntimeError.into()),
+				message: "Unable to get price history info.".into(),
+				data: Some(format!("{:?}", e).into()),
+			})
+	}
 }
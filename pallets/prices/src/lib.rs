@@ -4,17 +4,34 @@
 //!
 //! The data from Oracle cannot be used in business, prices module will do some
 //! process and feed prices for Minterest. Process include:
-//!   - specify a fixed price for stable currency;
+//!   - specify a fixed price for stable currency, configured by `StableCurrencyFixedPrice`;
 //!   - feed price in USD;
-//!   - lock/unlock the price data get from oracle.
+//!   - lock/unlock the price data get from oracle;
+//!   - select the price source of each currency: the median of all oracle instances, a single
+//!     oracle instance or the dex time-weighted average prices;
+//!   - discard oracle prices older than the maximum age of the currency. Anyone may report a
+//!     stale feed, which emits an event, and the next fresh price reports its recovery;
+//!   - keep the previous price and pause borrowing when a new oracle price deviates too much
+//!     from it. The condition is cleared by unlocking the price;
+//!   - keep the history of the last `PriceHistoryDepth` oracle prices accepted for each
+//!     currency together with the block numbers, to reconstruct the prices seen by the
+//!     protocol.
+//!
+//! Without a valid price the operations that check the user liquidity, such as borrow and
+//! redeem, fail. Repayments don't depend on prices and keep working. Deposits into pools with a
+//! supply cap are checked against the last accepted price, so they keep working too.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
-use frame_support::{pallet_prelude::*, transactional};
-use minterest_primitives::{currency::CurrencyType::UnderlyingAsset, CurrencyId, Price};
-use orml_traits::{DataFeeder, DataProvider};
-use pallet_traits::PricesManager;
+use frame_support::{pallet_prelude::*, traits::Time, transactional};
+use minterest_primitives::{
+	currency::CurrencyType::UnderlyingAsset, CurrencyId, DataProviderId, Moment, Operation, Price, Rate,
+};
+use orml_oracle::TimestampedValue;
+use orml_traits::{DataFeeder, DataProvider, DataProviderExtended, OnNewData};
+use pallet_traits::{ControllerManager, OracleDataProvider, PricesManager};
+use sp_runtime::traits::{Bounded, CheckedDiv, Saturating, Zero};
 use sp_std::vec::Vec;
 
 pub use module::*;
@@ -28,7 +45,7 @@ pub use weights::WeightInfo;
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
-	use frame_system::pallet_prelude::OriginFor;
+	use frame_system::{ensure_signed, pallet_prelude::OriginFor};
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -36,11 +53,36 @@ pub mod module {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
 		/// The data source, such as Oracle.
-		type Source: DataProvider<CurrencyId, Price> + DataFeeder<CurrencyId, Price, Self::AccountId>;
+		type Source: DataProvider<CurrencyId, Price>
+			+ DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>>
+			+ DataFeeder<CurrencyId, Price, Self::AccountId>;
+
+		/// The oracle instances, which may be selected as the price source of the currency
+		/// instead of the median of all of them provided by `Source`.
+		type Oracles: OracleDataProvider<CurrencyId, TimestampedValue<Price, Moment>>;
+
+		/// The fixed prices of the stable currencies. These currencies are never priced by a
+		/// data source and their prices can't be locked.
+		type StableCurrencyFixedPrice: Get<Vec<(CurrencyId, Price)>>;
+
+		/// The prices derived from the dex market data.
+		type DexSource: DataProvider<CurrencyId, Price>;
 
 		/// The origin which may lock and unlock prices feed to system.
 		type LockOrigin: EnsureOrigin<Self::Origin>;
 
+		/// Time used to determine the age of the source prices.
+		type Time: Time<Moment = Moment>;
+
+		/// Public API of controller pallet, used to pause borrowing when the circuit breaker is
+		/// triggered.
+		type ControllerManager: ControllerManager<Self::AccountId>;
+
+		/// The number of the last prices kept in the history of each currency.
+		/// Zero disables the price history.
+		#[pallet::constant]
+		type PriceHistoryDepth: Get<u32>;
+
 		/// Weight information for the extrinsics.
 		type WeightInfo: WeightInfo;
 	}
@@ -49,6 +91,12 @@ pub mod module {
 	pub enum Error<T> {
 		/// The currency is not enabled in protocol.
 		NotValidUnderlyingAssetId,
+		/// The price of the stable currency is fixed.
+		PriceIsFixed,
+		/// The oracle price of the currency is fresh.
+		PriceFeedNotStale,
+		/// The price feed has already been reported stale.
+		PriceFeedAlreadyReported,
 	}
 
 	#[pallet::event]
@@ -58,6 +106,20 @@ pub mod module {
 		LockPrice(CurrencyId, Price),
 		/// Unlock price. \[currency_id\]
 		UnlockPrice(CurrencyId),
+		/// Price source has been changed. \[currency_id, provider_id\]
+		PriceSourceChanged(CurrencyId, DataProviderId),
+		/// Maximum age of the oracle price has been changed. \[currency_id, max_price_age\]
+		MaxPriceAgeChanged(CurrencyId, Option<Moment>),
+		/// The oracle price is missing or older than the maximum age. \[currency_id\]
+		PriceFeedStale(CurrencyId),
+		/// The oracle price is fresh again. \[currency_id\]
+		PriceFeedRecovered(CurrencyId),
+		/// Maximum deviation of the oracle price has been changed. \[currency_id,
+		/// max_price_deviation\]
+		MaxPriceDeviationChanged(CurrencyId, Option<Rate>),
+		/// The new oracle price deviates too much, the previous price is locked and borrowing
+		/// is paused. \[currency_id, locked_price, rejected_price\]
+		PriceCircuitBreakerTriggered(CurrencyId, Price, Price),
 	}
 
 	/// Mapping from currency id to it's locked price
@@ -65,6 +127,58 @@ pub mod module {
 	#[pallet::getter(fn locked_price_storage)]
 	pub type LockedPriceStorage<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
 
+	/// Mapping from currency id to the data provider of it's price.
+	/// Currencies without an entry are priced by `Source`, the median of all oracle instances.
+	#[pallet::storage]
+	#[pallet::getter(fn price_source)]
+	pub type PriceSource<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, DataProviderId, OptionQuery>;
+
+	/// Mapping from currency id to the maximum age of it's oracle price, in milliseconds.
+	/// Older prices are treated as missing. Currencies without an entry accept prices of any age.
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_age)]
+	pub type MaxPriceAge<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Moment, OptionQuery>;
+
+	/// Mapping from currency id to the last oracle price that passed the deviation check.
+	#[pallet::storage]
+	#[pallet::getter(fn last_accepted_price)]
+	pub type LastAcceptedPrice<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Price, OptionQuery>;
+
+	/// Mapping from currency id to the maximum relative change of it's oracle price.
+	/// Currencies without an entry accept any price change.
+	#[pallet::storage]
+	#[pallet::getter(fn max_price_deviation)]
+	pub type MaxPriceDeviation<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, OptionQuery>;
+
+	/// Ring buffer of the last oracle prices of the currency accepted by the protocol. A price is
+	/// recorded whenever an accepted price differs from the previous one.
+	/// Double mapping from currency id and slot index to the block number and the price.
+	#[pallet::storage]
+	#[pallet::getter(fn price_history)]
+	pub type PriceHistory<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CurrencyId, Twox64Concat, u32, (T::BlockNumber, Price), OptionQuery>;
+
+	/// Mapping from currency id to the slot of the price history to be written next.
+	#[pallet::storage]
+	#[pallet::getter(fn price_history_head)]
+	pub type PriceHistoryHead<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, u32, ValueQuery>;
+
+	/// Currencies whose price has been locked by the circuit breaker.
+	#[pallet::storage]
+	#[pallet::getter(fn circuit_breaker_triggered)]
+	pub type CircuitBreakerTriggered<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// Currencies whose borrowing has been paused by the circuit breaker. Only these pools are
+	/// resumed when the price is unlocked, borrowing paused by other means stays paused.
+	#[pallet::storage]
+	#[pallet::getter(fn borrow_paused_by_circuit_breaker)]
+	pub type BorrowPausedByCircuitBreaker<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// Currencies whose oracle price feed has been reported stale.
+	#[pallet::storage]
+	#[pallet::getter(fn stale_feed)]
+	pub type StaleFeeds<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		#[allow(clippy::type_complexity)]
@@ -109,10 +223,7 @@ pub mod module {
 		pub fn lock_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
 			T::LockOrigin::ensure_origin(origin)?;
 
-			ensure!(
-				currency_id.is_supported_underlying_asset(),
-				Error::<T>::NotValidUnderlyingAssetId
-			);
+			Self::ensure_price_configurable(currency_id)?;
 
 			<Pallet<T> as PricesManager<CurrencyId>>::lock_price(currency_id);
 			Ok(().into())
@@ -128,37 +239,296 @@ pub mod module {
 		pub fn unlock_price(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
 			T::LockOrigin::ensure_origin(origin)?;
 
-			ensure!(
-				currency_id.is_supported_underlying_asset(),
-				Error::<T>::NotValidUnderlyingAssetId
-			);
+			Self::ensure_price_configurable(currency_id)?;
 
 			<Pallet<T> as PricesManager<CurrencyId>>::unlock_price(currency_id);
 			Ok(().into())
 		}
+
+		/// Select the data provider of the currency price.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `provider_id`: `Aggregated` for the median of all oracle instances, `Minterest` or
+		/// `External` for a single oracle instance or `Dex` for the dex time-weighted average
+		/// prices.
+		#[pallet::weight((T::WeightInfo::set_price_source(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_price_source(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			provider_id: DataProviderId,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+
+			Self::ensure_price_configurable(currency_id)?;
+
+			match provider_id {
+				DataProviderId::Aggregated => PriceSource::<T>::remove(currency_id),
+				_ => PriceSource::<T>::insert(currency_id, provider_id),
+			}
+			Self::check_feed_recovery(currency_id);
+			Self::deposit_event(Event::PriceSourceChanged(currency_id, provider_id));
+			Ok(().into())
+		}
+
+		/// Set the maximum age of the oracle price of the currency.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_price_age`: maximum age in milliseconds, `None` accepts prices of any age.
+		#[pallet::weight((T::WeightInfo::set_max_price_age(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_price_age(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_price_age: Option<Moment>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+
+			Self::ensure_price_configurable(currency_id)?;
+
+			MaxPriceAge::<T>::mutate_exists(currency_id, |age| *age = max_price_age);
+			Self::check_feed_recovery(currency_id);
+			Self::deposit_event(Event::MaxPriceAgeChanged(currency_id, max_price_age));
+			Ok(().into())
+		}
+
+		/// Set the maximum relative change of the oracle price of the currency.
+		///
+		/// The dispatch origin of this call must be `LockOrigin`.
+		///
+		/// - `currency_id`: currency type.
+		/// - `max_price_deviation`: maximum change relative to the last accepted price, `None`
+		/// disables the circuit breaker.
+		#[pallet::weight((T::WeightInfo::set_max_price_deviation(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_max_price_deviation(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			max_price_deviation: Option<Rate>,
+		) -> DispatchResultWithPostInfo {
+			T::LockOrigin::ensure_origin(origin)?;
+
+			Self::ensure_price_configurable(currency_id)?;
+
+			MaxPriceDeviation::<T>::mutate_exists(currency_id, |deviation| *deviation = max_price_deviation);
+			Self::deposit_event(Event::MaxPriceDeviationChanged(currency_id, max_price_deviation));
+			Ok(().into())
+		}
+
+		/// Report that the oracle price of the currency is missing or older than the maximum
+		/// age. The feed is reported once, until a fresh price arrives.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: currency type.
+		#[pallet::weight(T::WeightInfo::report_stale_price_feed())]
+		#[transactional]
+		pub fn report_stale_price_feed(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			Self::ensure_price_configurable(currency_id)?;
+			ensure!(!Self::stale_feed(currency_id), Error::<T>::PriceFeedAlreadyReported);
+			ensure!(Self::is_feed_stale(currency_id), Error::<T>::PriceFeedNotStale);
+
+			StaleFeeds::<T>::insert(currency_id, true);
+			Self::deposit_event(Event::PriceFeedStale(currency_id));
+			Ok(().into())
+		}
 	}
 }
 
 impl<T: Config> PricesManager<CurrencyId> for Pallet<T> {
 	/// Get price underlying token in USD.
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
-		// if locked price exists, return it, otherwise return latest price from oracle:
-		Self::locked_price_storage(currency_id).or_else(|| T::Source::get(&currency_id))
+		// if fixed price exists, return it; if locked price exists, return it, otherwise return
+		// latest price from the source:
+		Self::fixed_price(currency_id)
+			.or_else(|| Self::locked_price_storage(currency_id))
+			.or_else(|| Self::get_source_price(currency_id))
+	}
+
+	/// Get the current price, otherwise the last accepted oracle price of the currency.
+	fn get_last_underlying_price(currency_id: CurrencyId) -> Option<Price> {
+		Self::get_underlying_price(currency_id).or_else(|| Self::last_accepted_price(currency_id))
 	}
 
 	/// Locks price when get valid price from source.
 	fn lock_price(currency_id: CurrencyId) {
 		// lock price when get valid price from source
-		if let Some(val) = T::Source::get(&currency_id) {
+		if let Some(val) = Self::get_source_price(currency_id) {
 			LockedPriceStorage::<T>::insert(currency_id, val);
 			<Pallet<T>>::deposit_event(Event::LockPrice(currency_id, val));
 		}
 	}
 
 	/// Unlocks price when get valid price from source.
+	/// Clears the circuit breaker: the current oracle price is accepted and borrowing resumed
+	/// if it was paused by the circuit breaker.
 	fn unlock_price(currency_id: CurrencyId) {
 		LockedPriceStorage::<T>::remove(currency_id);
 		<Pallet<T>>::deposit_event(Event::UnlockPrice(currency_id));
+
+		if CircuitBreakerTriggered::<T>::take(currency_id) {
+			match Self::get_oracle_price(currency_id) {
+				Some(price) => Self::accept_price(currency_id, price.value),
+				None => LastAcceptedPrice::<T>::remove(currency_id),
+			}
+			if BorrowPausedByCircuitBreaker::<T>::take(currency_id) {
+				T::ControllerManager::resume_operation(currency_id, Operation::Borrow);
+			}
+		}
+	}
+}
+
+impl<T: Config> OnNewData<T::AccountId, CurrencyId, Price> for Pallet<T> {
+	/// Checks every new oracle price against the last accepted one and reports the recovery of
+	/// the stale feed.
+	fn on_new_data(_who: &T::AccountId, currency_id: &CurrencyId, _value: &Price) {
+		Self::check_price_deviation(*currency_id);
+		Self::check_feed_recovery(*currency_id);
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Get the fixed price of the stable currency.
+	fn fixed_price(currency_id: CurrencyId) -> Option<Price> {
+		T::StableCurrencyFixedPrice::get()
+			.into_iter()
+			.find(|(stable_currency_id, _)| *stable_currency_id == currency_id)
+			.map(|(_, price)| price)
+	}
+
+	/// Checks that the price of the currency is provided by a data source and can be configured.
+	fn ensure_price_configurable(currency_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			currency_id.is_supported_underlying_asset(),
+			Error::<T>::NotValidUnderlyingAssetId
+		);
+		ensure!(Self::fixed_price(currency_id).is_none(), Error::<T>::PriceIsFixed);
+		Ok(())
+	}
+
+	/// Get the latest price from the source selected for the currency.
+	/// Oracle prices older than the maximum age of the currency are ignored.
+	/// Stable currencies always return their fixed price.
+	fn get_source_price(currency_id: CurrencyId) -> Option<Price> {
+		if let Some(price) = Self::fixed_price(currency_id) {
+			return Some(price);
+		}
+		match Self::price_source(currency_id) {
+			Some(DataProviderId::Dex) => T::DexSource::get(&currency_id),
+			_ => Self::get_oracle_price(currency_id)
+				.filter(|price| !Self::is_outdated(currency_id, price.timestamp))
+				.map(|price| price.value),
+		}
+	}
+
+	/// Get the latest oracle price of the currency: from the oracle instance selected for the
+	/// currency, otherwise the median of all oracle instances.
+	fn get_oracle_price(currency_id: CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		match Self::price_source(currency_id) {
+			Some(provider_id @ DataProviderId::Minterest) | Some(provider_id @ DataProviderId::External) => {
+				T::Oracles::get_no_op(provider_id, &currency_id)
+			}
+			_ => T::Source::get_no_op(&currency_id),
+		}
+	}
+
+	/// Locks the last accepted price and pauses borrowing on the pool if the oracle price
+	/// deviates from it by more than the maximum deviation. Otherwise accepts the oracle price.
+	fn check_price_deviation(currency_id: CurrencyId) {
+		if Self::locked_price_storage(currency_id).is_some() || Self::fixed_price(currency_id).is_some() {
+			return;
+		}
+		let new_price = match Self::get_oracle_price(currency_id) {
+			Some(price) => price.value,
+			None => return,
+		};
+
+		match (
+			Self::last_accepted_price(currency_id),
+			Self::max_price_deviation(currency_id),
+		) {
+			(Some(last_price), Some(max_price_deviation))
+				if Self::price_deviation(last_price, new_price) > max_price_deviation =>
+			{
+				LockedPriceStorage::<T>::insert(currency_id, last_price);
+				CircuitBreakerTriggered::<T>::insert(currency_id, true);
+				if T::ControllerManager::is_operation_allowed(currency_id, Operation::Borrow) {
+					T::ControllerManager::pause_operation(currency_id, Operation::Borrow);
+					BorrowPausedByCircuitBreaker::<T>::insert(currency_id, true);
+				}
+				Self::deposit_event(Event::LockPrice(currency_id, last_price));
+				Self::deposit_event(Event::PriceCircuitBreakerTriggered(currency_id, last_price, new_price));
+			}
+			_ => Self::accept_price(currency_id, new_price),
+		}
+	}
+
+	/// Stores the accepted oracle price and records it in the price history.
+	fn accept_price(currency_id: CurrencyId, price: Price) {
+		LastAcceptedPrice::<T>::insert(currency_id, price);
+		Self::record_price(currency_id, price);
+	}
+
+	/// Records the accepted price of the currency in the price history, if it has changed since
+	/// the last record. Several prices accepted in the same block take a single slot of the
+	/// history.
+	fn record_price(currency_id: CurrencyId, price: Price) {
+		let depth = T::PriceHistoryDepth::get();
+		if depth.is_zero() {
+			return;
+		}
+		let current_block = <frame_system::Pallet<T>>::block_number();
+		let head = Self::price_history_head(currency_id) % depth;
+		let last_slot = head.checked_sub(1).unwrap_or(depth - 1);
+		match Self::price_history(currency_id, last_slot) {
+			Some((block_number, _)) if block_number == current_block => {
+				PriceHistory::<T>::insert(currency_id, last_slot, (current_block, price));
+			}
+			Some((_, last_price)) if last_price == price => {}
+			_ => {
+				PriceHistory::<T>::insert(currency_id, head, (current_block, price));
+				PriceHistoryHead::<T>::insert(currency_id, (head + 1) % depth);
+			}
+		}
+	}
+
+	/// Calculates the relative change of the price: |new_price - last_price| / last_price
+	fn price_deviation(last_price: Price, new_price: Price) -> Rate {
+		let price_change = if new_price > last_price {
+			new_price.saturating_sub(last_price)
+		} else {
+			last_price.saturating_sub(new_price)
+		};
+		price_change.checked_div(&last_price).unwrap_or_else(Rate::max_value)
+	}
+
+	/// Checks if the oracle price of the currency is missing or older than the maximum age.
+	/// Only the currencies with the maximum age priced by the oracle can have a stale feed.
+	fn is_feed_stale(currency_id: CurrencyId) -> bool {
+		Self::max_price_age(currency_id).is_some()
+			&& Self::price_source(currency_id) != Some(DataProviderId::Dex)
+			&& Self::get_oracle_price(currency_id).map_or(true, |price| Self::is_outdated(currency_id, price.timestamp))
+	}
+
+	/// Reports the recovery of the stale feed, once it isn't stale anymore.
+	fn check_feed_recovery(currency_id: CurrencyId) {
+		if Self::stale_feed(currency_id) && !Self::is_feed_stale(currency_id) {
+			StaleFeeds::<T>::remove(currency_id);
+			Self::deposit_event(Event::PriceFeedRecovered(currency_id));
+		}
+	}
+
+	/// Checks if the oracle price with the given timestamp exceeds the maximum age.
+	fn is_outdated(currency_id: CurrencyId, timestamp: Moment) -> bool {
+		Self::max_price_age(currency_id).map_or(false, |max_price_age| {
+			T::Time::now().saturating_sub(timestamp) > max_price_age
+		})
 	}
 }
 
@@ -167,7 +537,38 @@ impl<T: Config> Pallet<T> {
 	pub fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
 		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
 			.into_iter()
-			.map(|currency_id| (currency_id, T::Source::get(&currency_id)))
+			.map(|currency_id| (currency_id, Self::get_source_price(currency_id)))
+			.collect()
+	}
+
+	/// Returns the prices of the currency recorded between `from_block` and
+	/// `to_block` inclusive, ordered by the block number.
+	pub fn get_price_history(
+		currency_id: CurrencyId,
+		from_block: T::BlockNumber,
+		to_block: T::BlockNumber,
+	) -> Vec<(T::BlockNumber, Price)> {
+		let depth = T::PriceHistoryDepth::get();
+		let mut history: Vec<(T::BlockNumber, Price)> = PriceHistory::<T>::iter_prefix(currency_id)
+			// Slots beyond the depth are left over after the depth has been decreased.
+			.filter(|(slot, _)| *slot < depth)
+			.map(|(_, entry)| entry)
+			.filter(|(block_number, _)| *block_number >= from_block && *block_number <= to_block)
+			.collect();
+		history.sort_by_key(|(block_number, _)| *block_number);
+		history
+	}
+
+	/// Returns the currencies priced by the oracle whose price is missing or older than the
+	/// maximum age, together with the timestamp of the last price.
+	pub fn get_stale_price_feeds() -> Vec<(CurrencyId, Option<Moment>)> {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|&currency_id| Self::is_feed_stale(currency_id))
+			.map(|currency_id| {
+				let timestamp = Self::get_oracle_price(currency_id).map(|price| price.timestamp);
+				(currency_id, timestamp)
+			})
 			.collect()
 	}
 }
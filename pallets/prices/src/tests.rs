@@ -1,9 +1,10 @@
 //! Unit tests for the prices module.
 
 use frame_support::{assert_noop, assert_ok};
-use minterest_primitives::Price;
+use minterest_primitives::{DataProviderId, Operation, Price, Rate};
 use module_prices::{Error, Event};
-use pallet_traits::PricesManager;
+use orml_traits::OnNewData;
+use pallet_traits::{ControllerManager, PricesManager};
 use sp_runtime::{
 	traits::{BadOrigin, Zero},
 	FixedPointNumber,
@@ -91,3 +92,458 @@ fn unlock_price_call_should_work() {
 			);
 		});
 }
+
+#[test]
+fn stable_currency_fixed_price_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		// The oracle price of the stable currency is ignored.
+		MockDataProvider::set_price(USDT, Price::saturating_from_rational(105, 100));
+		TestPrices::on_new_data(&ALICE, &USDT, &Price::saturating_from_rational(105, 100));
+		assert_eq!(
+			TestPrices::get_underlying_price(USDT),
+			Some(Price::saturating_from_integer(1))
+		);
+		assert_eq!(TestPrices::last_accepted_price(USDT), None);
+
+		assert_noop!(
+			TestPrices::lock_price(alice_origin(), USDT),
+			Error::<TestRuntime>::PriceIsFixed
+		);
+		assert_noop!(
+			TestPrices::set_price_source(alice_origin(), USDT, DataProviderId::Dex),
+			Error::<TestRuntime>::PriceIsFixed
+		);
+		assert_noop!(
+			TestPrices::set_max_price_age(alice_origin(), USDT, Some(60_000)),
+			Error::<TestRuntime>::PriceIsFixed
+		);
+	});
+}
+
+#[test]
+fn set_price_source_should_work() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, DOT, units(DOT, 100_000))
+		.set_user_balance(ALICE, ETH, 50_000 * DOLLARS)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(TestPrices::set_price_source(alice_origin(), ETH, DataProviderId::Dex));
+			let expected_event = test_engine::Event::TestPrices(Event::PriceSourceChanged(ETH, DataProviderId::Dex));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestPrices::price_source(ETH), Some(DataProviderId::Dex));
+
+			// There is no average price on the dex yet.
+			assert_eq!(TestPrices::get_underlying_price(ETH), None);
+
+			// 1 ETH = 2 DOT = 80 USD
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				DOT,
+				ETH,
+				units(DOT, 100_000),
+				50_000 * DOLLARS
+			));
+			// The next change of the reserves closes the averaging window.
+			System::set_block_number(11);
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), DOT, ETH, units(DOT, 1)));
+			assert_eq!(
+				TestPrices::get_underlying_price(ETH),
+				Some(Price::saturating_from_integer(80))
+			);
+			// The quote currency is priced by the oracle.
+			assert_ok!(TestPrices::set_price_source(alice_origin(), DOT, DataProviderId::Dex));
+			assert_eq!(
+				TestPrices::get_underlying_price(DOT),
+				Some(Price::saturating_from_integer(40))
+			);
+
+			assert_ok!(TestPrices::set_price_source(
+				alice_origin(),
+				ETH,
+				DataProviderId::Aggregated
+			));
+			assert_eq!(TestPrices::price_source(ETH), None);
+			assert_eq!(
+				TestPrices::get_underlying_price(ETH),
+				Some(Price::saturating_from_integer(1_500))
+			);
+
+			assert_noop!(
+				TestPrices::set_price_source(bob_origin(), ETH, DataProviderId::Dex),
+				BadOrigin
+			);
+			assert_noop!(
+				TestPrices::set_price_source(alice_origin(), METH, DataProviderId::Dex),
+				Error::<TestRuntime>::NotValidUnderlyingAssetId
+			);
+		});
+}
+
+#[test]
+fn oracle_instance_price_source_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		MockDataProvider::set_oracle_price(DataProviderId::Minterest, BTC, Price::saturating_from_integer(47_000));
+		MockDataProvider::set_oracle_price(DataProviderId::External, BTC, Price::saturating_from_integer(49_000));
+
+		// The median of all oracle instances is used by default.
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(48_000))
+		);
+
+		assert_ok!(TestPrices::set_price_source(
+			alice_origin(),
+			BTC,
+			DataProviderId::External
+		));
+		assert_eq!(TestPrices::price_source(BTC), Some(DataProviderId::External));
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(49_000))
+		);
+		// The deviation is checked against the selected oracle instance.
+		TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(49_000));
+		assert_eq!(
+			TestPrices::last_accepted_price(BTC),
+			Some(Price::saturating_from_integer(49_000))
+		);
+
+		assert_ok!(TestPrices::set_price_source(
+			alice_origin(),
+			BTC,
+			DataProviderId::Minterest
+		));
+		assert_eq!(
+			TestPrices::get_underlying_price(BTC),
+			Some(Price::saturating_from_integer(47_000))
+		);
+
+		// The oracle instance without a price of the currency.
+		assert_ok!(TestPrices::set_price_source(
+			alice_origin(),
+			ETH,
+			DataProviderId::External
+		));
+		assert_eq!(TestPrices::get_underlying_price(ETH), None);
+	});
+}
+
+#[test]
+fn set_max_price_age_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(TestPrices::set_max_price_age(alice_origin(), BTC, Some(60_000)));
+		let expected_event = test_engine::Event::TestPrices(Event::MaxPriceAgeChanged(BTC, Some(60_000)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(TestPrices::max_price_age(BTC), Some(60_000));
+
+		assert_ok!(TestPrices::set_max_price_age(alice_origin(), BTC, None));
+		assert_eq!(TestPrices::max_price_age(BTC), None);
+
+		assert_noop!(
+			TestPrices::set_max_price_age(bob_origin(), BTC, Some(60_000)),
+			BadOrigin
+		);
+		assert_noop!(
+			TestPrices::set_max_price_age(alice_origin(), MBTC, Some(60_000)),
+			Error::<TestRuntime>::NotValidUnderlyingAssetId
+		);
+	});
+}
+
+#[test]
+fn stale_prices_should_be_ignored() {
+	ExtBuilderNew::default()
+		.set_locked_price(ETH, Price::saturating_from_integer(2_000))
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			MockDataProvider::set_price_timestamp(30_000);
+			MockTime::set_now(100_000);
+			assert_ok!(TestPrices::set_max_price_age(alice_origin(), BTC, Some(60_000)));
+			assert_ok!(TestPrices::set_max_price_age(alice_origin(), ETH, Some(60_000)));
+			assert_ok!(TestPrices::set_max_price_age(alice_origin(), DOT, Some(70_000)));
+
+			assert_eq!(TestPrices::get_underlying_price(BTC), None);
+			// The price age equals to the maximum age.
+			assert_eq!(
+				TestPrices::get_underlying_price(DOT),
+				Some(Price::saturating_from_integer(40))
+			);
+			// Locked prices never become stale.
+			assert_eq!(
+				TestPrices::get_underlying_price(ETH),
+				Some(Price::saturating_from_integer(2_000))
+			);
+			assert_eq!(
+				TestPrices::get_underlying_price(KSM),
+				Some(Price::saturating_from_integer(250))
+			);
+			assert_eq!(
+				TestPrices::get_stale_price_feeds(),
+				vec![(BTC, Some(30_000)), (ETH, Some(30_000))]
+			);
+
+			// Anyone can report the stale feed, but only once.
+			assert_noop!(
+				TestPrices::report_stale_price_feed(bob_origin(), DOT),
+				Error::<TestRuntime>::PriceFeedNotStale
+			);
+			assert_ok!(TestPrices::report_stale_price_feed(bob_origin(), BTC));
+			let stale_event = test_engine::Event::TestPrices(Event::PriceFeedStale(BTC));
+			assert!(System::events().iter().any(|record| record.event == stale_event));
+			assert!(TestPrices::stale_feed(BTC));
+			assert_noop!(
+				TestPrices::report_stale_price_feed(bob_origin(), BTC),
+				Error::<TestRuntime>::PriceFeedAlreadyReported
+			);
+
+			// The price that is still stale doesn't recover the feed.
+			System::reset_events();
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(48_000));
+			assert!(System::events().is_empty());
+
+			// The fresh price recovers the feed.
+			MockDataProvider::set_price_timestamp(90_000);
+			assert_eq!(TestPrices::get_stale_price_feeds(), vec![]);
+			assert_eq!(
+				TestPrices::get_underlying_price(BTC),
+				Some(Price::saturating_from_integer(48_000))
+			);
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(48_000));
+			let recovered_event = test_engine::Event::TestPrices(Event::PriceFeedRecovered(BTC));
+			assert!(System::events().iter().any(|record| record.event == recovered_event));
+			assert!(!TestPrices::stale_feed(BTC));
+		});
+}
+
+#[test]
+fn set_max_price_deviation_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let max_price_deviation = Rate::saturating_from_rational(1, 10);
+		assert_ok!(TestPrices::set_max_price_deviation(
+			alice_origin(),
+			BTC,
+			Some(max_price_deviation)
+		));
+		let expected_event =
+			test_engine::Event::TestPrices(Event::MaxPriceDeviationChanged(BTC, Some(max_price_deviation)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(TestPrices::max_price_deviation(BTC), Some(max_price_deviation));
+
+		assert_ok!(TestPrices::set_max_price_deviation(alice_origin(), BTC, None));
+		assert_eq!(TestPrices::max_price_deviation(BTC), None);
+
+		assert_noop!(
+			TestPrices::set_max_price_deviation(bob_origin(), BTC, Some(max_price_deviation)),
+			BadOrigin
+		);
+		assert_noop!(
+			TestPrices::set_max_price_deviation(alice_origin(), MBTC, Some(max_price_deviation)),
+			Error::<TestRuntime>::NotValidUnderlyingAssetId
+		);
+	});
+}
+
+#[test]
+fn circuit_breaker_should_work() {
+	ExtBuilderNew::default()
+		.set_pause_keeper(BTC, false)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(TestPrices::set_max_price_deviation(
+				alice_origin(),
+				BTC,
+				Some(Rate::saturating_from_rational(1, 10))
+			));
+
+			// The first price is accepted without a check.
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(48_000));
+			assert_eq!(
+				TestPrices::last_accepted_price(BTC),
+				Some(Price::saturating_from_integer(48_000))
+			);
+
+			// The price grows by 10%.
+			MockDataProvider::set_price(BTC, Price::saturating_from_integer(52_800));
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(52_800));
+			assert_eq!(
+				TestPrices::last_accepted_price(BTC),
+				Some(Price::saturating_from_integer(52_800))
+			);
+			assert!(!TestPrices::circuit_breaker_triggered(BTC));
+
+			// The price falls by 25%.
+			MockDataProvider::set_price(BTC, Price::saturating_from_integer(39_600));
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(39_600));
+			let expected_event = test_engine::Event::TestPrices(Event::PriceCircuitBreakerTriggered(
+				BTC,
+				Price::saturating_from_integer(52_800),
+				Price::saturating_from_integer(39_600),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert!(TestPrices::circuit_breaker_triggered(BTC));
+			assert!(TestPrices::borrow_paused_by_circuit_breaker(BTC));
+			assert_eq!(
+				TestPrices::get_underlying_price(BTC),
+				Some(Price::saturating_from_integer(52_800))
+			);
+			assert!(!TestController::is_operation_allowed(BTC, Operation::Borrow));
+			assert!(TestController::is_operation_allowed(BTC, Operation::Repay));
+
+			// The locked price doesn't follow the oracle.
+			MockDataProvider::set_price(BTC, Price::saturating_from_integer(40_000));
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(40_000));
+			assert_eq!(
+				TestPrices::get_underlying_price(BTC),
+				Some(Price::saturating_from_integer(52_800))
+			);
+
+			// Governance accepts the new price and resumes borrowing.
+			assert_ok!(TestPrices::unlock_price(alice_origin(), BTC));
+			assert!(!TestPrices::circuit_breaker_triggered(BTC));
+			assert_eq!(
+				TestPrices::last_accepted_price(BTC),
+				Some(Price::saturating_from_integer(40_000))
+			);
+			assert_eq!(
+				TestPrices::get_underlying_price(BTC),
+				Some(Price::saturating_from_integer(40_000))
+			);
+			assert!(TestController::is_operation_allowed(BTC, Operation::Borrow));
+			assert!(!TestPrices::borrow_paused_by_circuit_breaker(BTC));
+		});
+}
+
+#[test]
+fn circuit_breaker_should_not_resume_borrowing_paused_by_governance() {
+	ExtBuilderNew::default()
+		.set_pause_keeper(BTC, false)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(TestPrices::set_max_price_deviation(
+				alice_origin(),
+				BTC,
+				Some(Rate::saturating_from_rational(1, 10))
+			));
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(48_000));
+
+			// Borrowing is paused by the governance before the circuit breaker triggers.
+			<TestController as ControllerManager<_>>::pause_operation(BTC, Operation::Borrow);
+			MockDataProvider::set_price(BTC, Price::saturating_from_integer(36_000));
+			TestPrices::on_new_data(&ALICE, &BTC, &Price::saturating_from_integer(36_000));
+			assert!(TestPrices::circuit_breaker_triggered(BTC));
+			assert!(!TestPrices::borrow_paused_by_circuit_breaker(BTC));
+
+			// Unlocking the price doesn't resume borrowing.
+			assert_ok!(TestPrices::unlock_price(alice_origin(), BTC));
+			assert!(!TestPrices::circuit_breaker_triggered(BTC));
+			assert!(!TestController::is_operation_allowed(BTC, Operation::Borrow));
+		});
+}
+
+#[test]
+fn price_history_should_work() {
+	ExtBuilderNew::default().build().execute_with(|| {
+		let price = |value: u128| Price::saturating_from_integer(value);
+		let feed_price = |value: u128| {
+			MockDataProvider::set_price(BTC, price(value));
+			TestPrices::on_new_data(&ALICE, &BTC, &price(value));
+		};
+
+		System::set_block_number(1);
+		feed_price(48_000);
+		// Only the last price accepted in the block is kept.
+		System::set_block_number(2);
+		feed_price(49_000);
+		feed_price(50_000);
+		System::set_block_number(3);
+		feed_price(51_000);
+		assert_eq!(
+			TestPrices::get_price_history(BTC, 0, 10),
+			vec![(1, price(48_000)), (2, price(50_000)), (3, price(51_000))]
+		);
+		assert_eq!(TestPrices::get_price_history(BTC, 2, 2), vec![(2, price(50_000))]);
+
+		// The oldest price is overwritten when the history is full.
+		System::set_block_number(4);
+		feed_price(52_000);
+		assert_eq!(
+			TestPrices::get_price_history(BTC, 0, 10),
+			vec![(2, price(50_000)), (3, price(51_000)), (4, price(52_000))]
+		);
+
+		// The prices rejected by the circuit breaker are not recorded.
+		assert_ok!(TestPrices::set_max_price_deviation(
+			alice_origin(),
+			BTC,
+			Some(Rate::saturating_from_rational(1, 10))
+		));
+		System::set_block_number(5);
+		feed_price(30_000);
+		assert_eq!(TestPrices::get_price_history(BTC, 5, 10), vec![]);
+
+		// The price accepted on unlock is recorded.
+		assert_ok!(TestPrices::unlock_price(alice_origin(), BTC));
+		assert_eq!(TestPrices::get_price_history(BTC, 5, 10), vec![(5, price(30_000))]);
+		assert_eq!(TestPrices::get_price_history(DOT, 0, 10), vec![]);
+	});
+}
+
+#[test]
+fn price_history_should_only_record_accepted_prices() {
+	ExtBuilderNew::default()
+		.set_user_balance(ALICE, BTC, units(BTC, 10))
+		.set_user_balance(ALICE, DOT, units(DOT, 12_000))
+		.build()
+		.execute_with(|| {
+			let price = |value: u128| Price::saturating_from_integer(value);
+			let feed_price = |value: u128| {
+				MockDataProvider::set_price(BTC, price(value));
+				TestPrices::on_new_data(&ALICE, &BTC, &price(value));
+			};
+
+			System::set_block_number(1);
+			feed_price(48_000);
+
+			// Locked prices and the oracle prices fed while the price is locked are not accepted.
+			System::set_block_number(2);
+			MockDataProvider::set_price(BTC, price(50_000));
+			assert_ok!(TestPrices::lock_price(alice_origin(), BTC));
+			feed_price(51_000);
+			assert_ok!(TestPrices::unlock_price(alice_origin(), BTC));
+			assert_eq!(TestPrices::get_price_history(BTC, 0, 10), vec![(1, price(48_000))]);
+
+			System::set_block_number(3);
+			feed_price(52_000);
+			assert_eq!(
+				TestPrices::get_price_history(BTC, 0, 10),
+				vec![(1, price(48_000)), (3, price(52_000))]
+			);
+
+			// The dex prices are not recorded.
+			// 1 BTC = 1_200 DOT = 48_000$
+			assert_ok!(TestPrices::set_price_source(alice_origin(), BTC, DataProviderId::Dex));
+			assert_ok!(TestDex::add_liquidity(
+				alice_origin(),
+				BTC,
+				DOT,
+				units(BTC, 10),
+				units(DOT, 12_000)
+			));
+			System::set_block_number(13);
+			assert_ok!(TestDex::remove_liquidity(alice_origin(), BTC, DOT, units(DOT, 1)));
+			assert_eq!(TestPrices::get_underlying_price(BTC), Some(price(48_000)));
+			assert_eq!(
+				TestPrices::get_price_history(BTC, 0, 20),
+				vec![(1, price(48_000)), (3, price(52_000))]
+			);
+
+			// The fixed price of the stable currency is never recorded.
+			assert_eq!(TestPrices::get_price_history(USDT, 0, 20), vec![]);
+		});
+}
@@ -47,6 +47,10 @@ use sp_std::marker::PhantomData;
 pub trait WeightInfo {
 	fn lock_price() -> Weight;
 	fn unlock_price() -> Weight;
+	fn set_price_source() -> Weight;
+	fn set_max_price_age() -> Weight;
+	fn set_max_price_deviation() -> Weight;
+	fn report_stale_price_feed() -> Weight;
 }
 
 /// Weights for module_prices using the Minterest node and recommended hardware.
@@ -61,6 +65,25 @@ impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
 		(26_548_000 as Weight)
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_price_source() -> Weight {
+		(31_207_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_age() -> Weight {
+		(31_386_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_deviation() -> Weight {
+		(25_744_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn report_stale_price_feed() -> Weight {
+		(34_519_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -74,4 +97,23 @@ impl WeightInfo for () {
 		(26_548_000 as Weight)
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
+	fn set_price_source() -> Weight {
+		(31_207_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_age() -> Weight {
+		(31_386_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_deviation() -> Weight {
+		(25_744_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn report_stale_price_feed() -> Weight {
+		(34_519_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -2,36 +2,138 @@
 //!
 //! ## Overview
 //!
-//! TODO
+//! Risk Manager is responsible for the liquidation of unsafe loans.
+//! This module has offchain worker implemented which is running constantly.
+//! Offchain worker checks all borrowers and submits unsigned liquidation transactions
+//! for accounts with a shortfall.
+//! If the collateral of the liquidated user is not enough to cover the loan, the uncovered
+//! part is written off to the bad debt ledger of the pool. The bad debt can be covered by
+//! the governance from the liquidation pools or the protocol interest, otherwise it is
+//! socialized among the suppliers of the pool. The written off loans are excluded from the
+//! exchange rate of the pool right away, so the suppliers can't redeem at the pre-write-off
+//! rate while the bad debt is waiting to be covered.
+//! The collateral seized in pools with configured auction parameters is sold through
+//! Dutch auctions: the price starts above the oracle price and decays each block, bidders
+//! pay in the debt asset and the proceeds go to the liquidation pools.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 #![allow(clippy::upper_case_acronyms)]
 
-use frame_support::{pallet_prelude::*, transactional};
-use frame_system::pallet_prelude::OriginFor;
-use minterest_primitives::{Balance, CurrencyId, Operation, Rate};
+use codec::{Decode, Encode};
+use frame_support::{log, pallet_prelude::*, storage::with_transaction, transactional};
+use frame_system::{
+	offchain::{SendTransactionTypes, SubmitTransaction},
+	pallet_prelude::*,
+};
+use liquidity_pools::Pool;
+use minterest_primitives::{
+	currency::CurrencyType::UnderlyingAsset, Balance, CurrencyId, OffchainErr, Operation, Price, Rate,
+};
 pub use module::*;
-use pallet_traits::{RiskManagerStorageProvider, UserCollateral, UserLiquidationAttemptsManager};
-use sp_runtime::traits::{One, Zero};
+use orml_traits::MultiCurrency;
+use pallet_traits::{
+	Borrowing, ControllerManager, CurrencyConverter, LiquidationPoolsManager, LiquidityPoolStorageProvider,
+	MinterestProtocolManager, PoolsManager, PricesManager, RiskManagerStorageProvider, UserCollateral,
+	UserLiquidationAttemptsManager,
+};
+use sp_core::U256;
+use sp_runtime::{
+	offchain::storage_lock::{StorageLock, Time},
+	traits::{CheckedAdd, CheckedDiv, CheckedMul, CheckedSub, One, Saturating, Zero},
+	transaction_validity::TransactionPriority,
+	DispatchError, FixedPointNumber, RuntimeDebug, TransactionOutcome,
+};
 #[cfg(feature = "std")]
 use sp_std::str;
+use sp_std::{collections::btree_set::BTreeSet, convert::TryInto, result, vec::Vec};
 
+pub use weights::WeightInfo;
+
+pub mod migrations;
 #[cfg(test)]
 mod mock;
 #[cfg(test)]
 mod tests;
+pub mod weights;
+
+type RateResult = result::Result<Rate, DispatchError>;
+type LiquidationResult = result::Result<(Balance, Vec<CurrencyId>, bool), DispatchError>;
+
+pub type AuctionId = u32;
+
+/// Parameters of the Dutch auctions for the collateral seized in the pool.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, Eq, PartialEq, Default)]
+pub struct AuctionParams<BlockNumber> {
+	/// Premium over the oracle price at which the auction starts.
+	pub start_premium: Rate,
+	/// The minimum price of the lot as a fraction of the oracle price at the auction start.
+	pub min_price_ratio: Rate,
+	/// Number of blocks during which the price decays from the start price to the minimum price.
+	pub duration: BlockNumber,
+	/// How many times an expired auction can be restarted. After that the rest of the lot
+	/// is returned to the liquidation pool.
+	pub max_restarts: u32,
+}
+
+/// Dutch auction for the collateral seized during liquidation.
+#[derive(Encode, Decode, Clone, RuntimeDebug, Eq, PartialEq)]
+pub struct CollateralAuction<BlockNumber> {
+	/// The pool of the collateral being sold.
+	pub collateral_pool_id: CurrencyId,
+	/// The pool of the debt asset in which bidders pay.
+	pub debt_pool_id: CurrencyId,
+	/// The remaining amount of the collateral.
+	pub amount: Balance,
+	/// Price of the collateral in the debt asset at the start of the auction.
+	pub start_price: Price,
+	/// Price of the collateral in the debt asset at the end of the auction.
+	pub min_price: Price,
+	/// The block at which the auction was started or restarted.
+	pub start_block: BlockNumber,
+	/// The block at which the auction expires.
+	pub end_block: BlockNumber,
+	/// Number of times the auction has been restarted.
+	pub restarts: u32,
+}
+
+const OFFCHAIN_RISK_MANAGER_WORKER_LOCK: &[u8] = b"pallets/risk-manager/lock/";
 
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
 
 	#[pallet::config]
-	pub trait Config: frame_system::Config {
+	pub trait Config: frame_system::Config + SendTransactionTypes<Call<Self>> {
 		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
 
-		/// Provides functionality for working with a user's collateral pools.
-		type UserCollateral: UserCollateral<Self::AccountId>;
+		/// A configuration for base priority of unsigned transactions.
+		///
+		/// This is exposed so that it can be tuned for particular runtime, when
+		/// multiple pallets send unsigned transactions.
+		type UnsignedPriority: Get<TransactionPriority>;
+
+		/// The `MultiCurrency` implementation.
+		type MultiCurrency: MultiCurrency<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
+
+		/// The price source of currencies
+		type PriceSource: PricesManager<CurrencyId>;
+
+		/// Provides the basic liquidity pools manager and liquidity pool functionality.
+		type LiquidityPoolsManager: LiquidityPoolStorageProvider<Self::AccountId, Pool>
+			+ PoolsManager<Self::AccountId>
+			+ Borrowing<Self::AccountId>
+			+ CurrencyConverter
+			+ UserCollateral<Self::AccountId>;
+
+		/// Provides the basic liquidation pools functionality.
+		type LiquidationPoolsManager: LiquidationPoolsManager<Self::AccountId>;
+
+		/// Public API of controller pallet.
+		type ControllerManager: ControllerManager<Self::AccountId>;
+
+		/// Public API of minterest protocol pallet, used to repay loans and seize collateral.
+		type MinterestProtocolManager: MinterestProtocolManager<Self::AccountId>;
 
 		#[pallet::constant]
 		/// Minimal sum for partial liquidation.
@@ -47,9 +149,21 @@ pub mod module {
 		/// The maximum liquidation fee.
 		type MaxLiquidationFee: Get<Rate>;
 
-		/// The origin which may update risk manager parameters. Root or
+		/// The origin which may cover and socialize the bad debt of the pools. Root or
 		/// Half Minterest Council can always do this.
 		type RiskManagerUpdateOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may update the liquidation and auction parameters. Expected to be
+		/// root, so that the changes are delayed by the timelock.
+		type RiskParameterOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The account that holds the collateral lots of the active auctions. The lots are kept
+		/// apart from the liquidation pools, so that balancing of the liquidation pools can't
+		/// swap them away.
+		type AuctionAccountId: Get<Self::AccountId>;
+
+		/// Weight information for the extrinsics.
+		type RiskManagerWeightInfo: WeightInfo;
 	}
 
 	#[pallet::error]
@@ -58,8 +172,44 @@ pub mod module {
 		NotValidUnderlyingAssetId,
 		/// Liquidation fee can't be greater than 0.5.
 		InvalidLiquidationFeeValue,
+		/// Liquidation threshold, the step of partial liquidations, together with the collateral
+		/// factor of the pool can't exceed one.
+		InvalidLiquidationThresholdValue,
 		/// Risk manager storage (liquidation_fee, liquidation_threshold) is already created.
 		RiskManagerParamsAlreadyCreated,
+		/// Pool not found.
+		PoolNotFound,
+		/// Feed price is invalid.
+		InvalidFeedPrice,
+		/// Number overflow in calculation.
+		NumOverflow,
+		/// The user's loan is solvent and can't be liquidated.
+		SolventUserLoan,
+		/// The user has no borrow in the liquidated pool.
+		ZeroBorrowBalance,
+		/// The user has no collateral that can be seized.
+		NoCollateralToSeize,
+		/// The amount to cover is greater than the bad debt of the pool.
+		CoverAmountTooBig,
+		/// Not enough balance in the liquidation pool to cover the bad debt.
+		NotEnoughLiquidationPoolBalance,
+		/// Not enough protocol interest in the pool to cover the bad debt.
+		NotEnoughProtocolInterest,
+		/// The pool has no bad debt.
+		ZeroBadDebt,
+		/// Auction duration and the minimum price must be non-zero and the minimum price can't
+		/// exceed the start price.
+		InvalidAuctionParams,
+		/// Auction with the specified ID doesn't exist.
+		AuctionNotFound,
+		/// The auction has expired and must be restarted.
+		AuctionExpired,
+		/// The auction hasn't expired yet.
+		AuctionNotExpired,
+		/// The payment for the bid is zero.
+		ZeroAuctionPayment,
+		/// Transaction with zero balance is not allowed.
+		ZeroBalanceTransaction,
 	}
 
 	#[pallet::event]
@@ -67,8 +217,31 @@ pub mod module {
 	pub enum Event<T: Config> {
 		/// Liquidation fee has been successfully changed: \[liquidation_fee\]
 		LiquidationFeeUpdated(Rate),
-		/// Liquidation threshold has been successfully changed: \[threshold\]
-		LiquidationThresholdUpdated(Rate),
+		/// Liquidation threshold has been successfully changed: \[pool_id, threshold\]
+		LiquidationThresholdUpdated(CurrencyId, Rate),
+		/// Unsafe loan has been successfully liquidated: \[who, liquidated_pool_id, repay_amount,
+		/// seized_pools, partial_liquidation\]
+		LiquidateUnsafeLoan(T::AccountId, CurrencyId, Balance, Vec<CurrencyId>, bool),
+		/// The uncovered part of the loan has been written off to the bad debt:
+		/// \[who, pool_id, bad_debt_amount\]
+		BadDebtWrittenOff(T::AccountId, CurrencyId, Balance),
+		/// Bad debt has been covered from the liquidation pool: \[pool_id, amount\]
+		BadDebtCoveredFromLiquidationPool(CurrencyId, Balance),
+		/// Bad debt has been covered from the protocol interest: \[pool_id, amount\]
+		BadDebtCoveredFromProtocolInterest(CurrencyId, Balance),
+		/// Bad debt has been socialized among the suppliers of the pool: \[pool_id, amount\]
+		BadDebtSocialized(CurrencyId, Balance),
+		/// Auction parameters have been successfully changed: \[pool_id, auction_params\]
+		AuctionParamsUpdated(CurrencyId, Option<AuctionParams<T::BlockNumber>>),
+		/// Auction for the seized collateral has been started: \[auction_id, collateral_pool_id,
+		/// debt_pool_id, amount, start_price\]
+		AuctionStarted(AuctionId, CurrencyId, CurrencyId, Balance, Price),
+		/// A part of the lot has been bought: \[auction_id, who, amount, payment\]
+		AuctionBid(AuctionId, T::AccountId, Balance, Balance),
+		/// Expired auction has been restarted: \[auction_id, start_price\]
+		AuctionRestarted(AuctionId, Price),
+		/// Auction has been closed: \[auction_id\]
+		AuctionClosed(AuctionId),
 	}
 
 	/// The additional collateral which is taken from borrowers as a penalty for being liquidated.
@@ -76,20 +249,38 @@ pub mod module {
 	#[pallet::getter(fn liquidation_fee)]
 	pub(crate) type LiquidationFee<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
 
-	/// Step used in liquidation to protect the user from micro liquidations.
+	/// Step used in liquidation to protect the user from micro liquidations. Set for each pool.
 	#[pallet::storage]
 	#[pallet::getter(fn liquidation_threshold)]
-	pub(crate) type LiquidationThreshold<T: Config> = StorageValue<_, Rate, ValueQuery>;
+	pub(crate) type LiquidationThreshold<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, ValueQuery>;
 
 	/// Counter of the number of partial liquidations at the user.
 	#[pallet::storage]
 	#[pallet::getter(fn user_liquidation_attempts)]
-	pub(crate) type UserLiquidationAttempts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u8, ValueQuery>;
+	pub type UserLiquidationAttempts<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u8, ValueQuery>;
+
+	/// Parameters of the collateral auctions. Seized collateral is auctioned only in pools
+	/// which have these parameters set.
+	#[pallet::storage]
+	#[pallet::getter(fn auction_params)]
+	pub type CollateralAuctionParams<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, AuctionParams<T::BlockNumber>, OptionQuery>;
+
+	/// Active collateral auctions.
+	#[pallet::storage]
+	#[pallet::getter(fn auctions)]
+	pub type Auctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, CollateralAuction<T::BlockNumber>, OptionQuery>;
+
+	/// The ID of the next collateral auction.
+	#[pallet::storage]
+	#[pallet::getter(fn next_auction_id)]
+	pub type NextAuctionId<T: Config> = StorageValue<_, AuctionId, ValueQuery>;
 
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub liquidation_fee: Vec<(CurrencyId, Rate)>,
-		pub liquidation_threshold: Rate,
+		pub liquidation_threshold: Vec<(CurrencyId, Rate)>,
 		pub _phantom: sp_std::marker::PhantomData<T>,
 	}
 
@@ -98,7 +289,7 @@ pub mod module {
 		fn default() -> Self {
 			GenesisConfig {
 				liquidation_fee: vec![],
-				liquidation_threshold: Rate::default(),
+				liquidation_threshold: vec![],
 				_phantom: PhantomData,
 			}
 		}
@@ -111,7 +302,9 @@ pub mod module {
 				Pallet::<T>::is_valid_liquidation_fee(*liquidation_fee);
 				LiquidationFee::<T>::insert(pool_id, liquidation_fee)
 			});
-			LiquidationThreshold::<T>::put(self.liquidation_threshold);
+			self.liquidation_threshold
+				.iter()
+				.for_each(|(pool_id, threshold)| LiquidationThreshold::<T>::insert(pool_id, threshold));
 		}
 	}
 
@@ -119,7 +312,31 @@ pub mod module {
 	pub struct Pallet<T>(PhantomData<T>);
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {}
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Moves the global liquidation threshold into the per-pool storage map.
+		fn on_runtime_upgrade() -> Weight {
+			migrations::migrate_liquidation_threshold_to_map::<T>()
+		}
+
+		/// Runs after every block. Start offchain worker to check unsafe loans and
+		/// submit liquidation transactions.
+		fn offchain_worker(now: T::BlockNumber) {
+			if let Err(error) = Self::_offchain_worker(now) {
+				log::info!(
+					target: "RiskManager offchain worker",
+					"cannot run offchain worker at {:?}: {:?}",
+					now,
+					error,
+				);
+			} else {
+				log::debug!(
+					target: "RiskManager offchain worker",
+					" RiskManager offchain worker start at block: {:?} already done!",
+					now,
+				);
+			}
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -128,15 +345,15 @@ pub mod module {
 		/// - `pool_id`: PoolID for which the parameter value is being set.
 		/// - `liquidation_fee`: new liquidation fee value.
 		///
-		/// The dispatch origin of this call must be 'RiskManagerUpdateOrigin'.
-		#[pallet::weight(0)]
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::set_liquidation_fee())]
 		#[transactional]
 		pub fn set_liquidation_fee(
 			origin: OriginFor<T>,
 			pool_id: CurrencyId,
 			liquidation_fee: Rate,
 		) -> DispatchResultWithPostInfo {
-			T::RiskManagerUpdateOrigin::ensure_origin(origin)?;
+			T::RiskParameterOrigin::ensure_origin(origin)?;
 			ensure!(
 				pool_id.is_supported_underlying_asset(),
 				Error::<T>::NotValidUnderlyingAssetId
@@ -155,21 +372,227 @@ pub mod module {
 		/// - `pool_id`: PoolID for which the parameter value is being set.
 		/// - `threshold`: new threshold.
 		///
-		/// The dispatch origin of this call must be 'RiskManagerUpdateOrigin'.
-		#[pallet::weight(0)]
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::set_liquidation_threshold())]
 		#[transactional]
 		pub fn set_liquidation_threshold(
 			origin: OriginFor<T>,
 			pool_id: CurrencyId,
 			threshold: Rate,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
+			ensure!(
+				pool_id.is_supported_underlying_asset(),
+				Error::<T>::NotValidUnderlyingAssetId
+			);
+			ensure!(
+				Self::is_valid_liquidation_threshold(pool_id, threshold),
+				Error::<T>::InvalidLiquidationThresholdValue
+			);
+			LiquidationThreshold::<T>::insert(pool_id, threshold);
+			Self::deposit_event(Event::LiquidationThresholdUpdated(pool_id, threshold));
+			Ok(().into())
+		}
+
+		/// Liquidate an unsafe loan of the borrower.
+		///
+		/// - `who`: the account with an unsafe loan.
+		/// - `pool_id`: the currency ID of the pool with the borrow to repay.
+		///
+		/// The dispatch origin of this call must be _None_.
+		#[pallet::weight(T::RiskManagerWeightInfo::liquidate())]
+		#[transactional]
+		pub fn liquidate(origin: OriginFor<T>, who: T::AccountId, pool_id: CurrencyId) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			Self::liquidate_unsafe_loan(who, pool_id)?;
+			Ok(().into())
+		}
+
+		/// Cover the bad debt of the pool with the underlying assets of the liquidation pool.
+		///
+		/// - `pool_id`: the currency ID of the pool with the bad debt.
+		/// - `amount`: the amount of the bad debt to cover.
+		///
+		/// The dispatch origin of this call must be 'RiskManagerUpdateOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::cover_bad_debt_from_liquidation_pool())]
+		#[transactional]
+		pub fn cover_bad_debt_from_liquidation_pool(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
 		) -> DispatchResultWithPostInfo {
 			T::RiskManagerUpdateOrigin::ensure_origin(origin)?;
+			let new_bad_debt = Self::bad_debt_after_cover(pool_id, amount)?;
+			ensure!(
+				amount <= T::LiquidationPoolsManager::get_pool_available_liquidity(pool_id),
+				Error::<T>::NotEnoughLiquidationPoolBalance
+			);
+
+			T::MultiCurrency::transfer(
+				pool_id,
+				&T::LiquidationPoolsManager::pools_account_id(),
+				&T::LiquidityPoolsManager::pools_account_id(),
+				amount,
+			)?;
+			T::LiquidityPoolsManager::set_pool_bad_debt(pool_id, new_bad_debt);
+
+			Self::deposit_event(Event::BadDebtCoveredFromLiquidationPool(pool_id, amount));
+			Ok(().into())
+		}
+
+		/// Cover the bad debt of the pool with the protocol interest held in this pool.
+		///
+		/// - `pool_id`: the currency ID of the pool with the bad debt.
+		/// - `amount`: the amount of the bad debt to cover.
+		///
+		/// The dispatch origin of this call must be 'RiskManagerUpdateOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::cover_bad_debt_from_protocol_interest())]
+		#[transactional]
+		pub fn cover_bad_debt_from_protocol_interest(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			T::RiskManagerUpdateOrigin::ensure_origin(origin)?;
+			let new_bad_debt = Self::bad_debt_after_cover(pool_id, amount)?;
+			let new_protocol_interest = T::LiquidityPoolsManager::get_pool_protocol_interest(pool_id)
+				.checked_sub(amount)
+				.ok_or(Error::<T>::NotEnoughProtocolInterest)?;
+
+			T::LiquidityPoolsManager::set_pool_protocol_interest(pool_id, new_protocol_interest);
+			T::LiquidityPoolsManager::set_pool_bad_debt(pool_id, new_bad_debt);
+
+			Self::deposit_event(Event::BadDebtCoveredFromProtocolInterest(pool_id, amount));
+			Ok(().into())
+		}
+
+		/// Socialize the remaining bad debt of the pool among its suppliers. The bad debt is
+		/// removed from the ledger. The exchange rate of the pool doesn't change, since the
+		/// written off loans have already been excluded from it.
+		///
+		/// - `pool_id`: the currency ID of the pool with the bad debt.
+		///
+		/// The dispatch origin of this call must be 'RiskManagerUpdateOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::socialize_bad_debt())]
+		#[transactional]
+		pub fn socialize_bad_debt(origin: OriginFor<T>, pool_id: CurrencyId) -> DispatchResultWithPostInfo {
+			T::RiskManagerUpdateOrigin::ensure_origin(origin)?;
+			Self::ensure_pool_is_valid(pool_id)?;
+			let bad_debt = T::LiquidityPoolsManager::get_pool_bad_debt(pool_id);
+			ensure!(!bad_debt.is_zero(), Error::<T>::ZeroBadDebt);
+
+			T::LiquidityPoolsManager::set_pool_bad_debt(pool_id, Balance::zero());
+
+			Self::deposit_event(Event::BadDebtSocialized(pool_id, bad_debt));
+			Ok(().into())
+		}
+
+		/// Set parameters of the Dutch auctions for the collateral seized in the pool.
+		///
+		/// - `pool_id`: PoolID for which the parameters are being set.
+		/// - `auction_params`: new auction parameters, `None` disables the auctions.
+		///
+		/// The dispatch origin of this call must be 'RiskParameterOrigin'.
+		#[pallet::weight(T::RiskManagerWeightInfo::set_auction_params())]
+		#[transactional]
+		pub fn set_auction_params(
+			origin: OriginFor<T>,
+			pool_id: CurrencyId,
+			auction_params: Option<AuctionParams<T::BlockNumber>>,
+		) -> DispatchResultWithPostInfo {
+			T::RiskParameterOrigin::ensure_origin(origin)?;
 			ensure!(
 				pool_id.is_supported_underlying_asset(),
 				Error::<T>::NotValidUnderlyingAssetId
 			);
-			LiquidationThreshold::<T>::put(threshold);
-			Self::deposit_event(Event::LiquidationThresholdUpdated(threshold));
+			if let Some(params) = auction_params {
+				ensure!(Self::is_valid_auction_params(&params), Error::<T>::InvalidAuctionParams);
+			}
+			CollateralAuctionParams::<T>::mutate_exists(pool_id, |x| *x = auction_params);
+			Self::deposit_event(Event::AuctionParamsUpdated(pool_id, auction_params));
+			Ok(().into())
+		}
+
+		/// Buy a part of the collateral lot at the current auction price.
+		///
+		/// - `auction_id`: the ID of the auction.
+		/// - `amount`: the amount of the collateral to buy. Capped by the remaining lot.
+		///
+		/// The payment in the debt asset is rounded up and transferred to the liquidation pools.
+		#[pallet::weight(T::RiskManagerWeightInfo::bid())]
+		#[transactional]
+		pub fn bid(
+			origin: OriginFor<T>,
+			auction_id: AuctionId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			ensure!(!amount.is_zero(), Error::<T>::ZeroBalanceTransaction);
+			let mut auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let price = Self::get_auction_price(&auction, <frame_system::Pallet<T>>::block_number())?;
+
+			let amount = amount.min(auction.amount);
+			let payment = Self::get_auction_payment(&auction, amount, price)?;
+			ensure!(!payment.is_zero(), Error::<T>::ZeroAuctionPayment);
+
+			T::MultiCurrency::transfer(
+				auction.debt_pool_id,
+				&who,
+				&T::LiquidationPoolsManager::pools_account_id(),
+				payment,
+			)?;
+			T::MultiCurrency::transfer(auction.collateral_pool_id, &T::AuctionAccountId::get(), &who, amount)?;
+			Self::deposit_event(Event::AuctionBid(auction_id, who, amount, payment));
+
+			auction.amount -= amount;
+			if auction.amount.is_zero() {
+				Auctions::<T>::remove(auction_id);
+				Self::deposit_event(Event::AuctionClosed(auction_id));
+			} else {
+				Auctions::<T>::insert(auction_id, auction);
+			}
+			Ok(().into())
+		}
+
+		/// Restart an expired auction at the current oracle price. If the maximum number of
+		/// restarts is reached or the auctions are disabled in the pool, the auction is closed
+		/// and the rest of the lot is returned to the liquidation pool.
+		///
+		/// - `auction_id`: the ID of the auction.
+		#[pallet::weight(T::RiskManagerWeightInfo::restart_auction())]
+		#[transactional]
+		pub fn restart_auction(origin: OriginFor<T>, auction_id: AuctionId) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+			let auction = Self::auctions(auction_id).ok_or(Error::<T>::AuctionNotFound)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now >= auction.end_block, Error::<T>::AuctionNotExpired);
+
+			match Self::auction_params(auction.collateral_pool_id) {
+				Some(params) if auction.restarts < params.max_restarts => {
+					let restarted_auction = CollateralAuction {
+						restarts: auction.restarts + 1,
+						..Self::new_auction(
+							auction.collateral_pool_id,
+							auction.debt_pool_id,
+							auction.amount,
+							&params,
+						)?
+					};
+					let start_price = restarted_auction.start_price;
+					Auctions::<T>::insert(auction_id, restarted_auction);
+					Self::deposit_event(Event::AuctionRestarted(auction_id, start_price));
+				}
+				_ => {
+					T::MultiCurrency::transfer(
+						auction.collateral_pool_id,
+						&T::AuctionAccountId::get(),
+						&T::LiquidationPoolsManager::pools_account_id(),
+						auction.amount,
+					)?;
+					Auctions::<T>::remove(auction_id);
+					Self::deposit_event(Event::AuctionClosed(auction_id));
+				}
+			}
 			Ok(().into())
 		}
 	}
@@ -177,13 +600,259 @@ pub mod module {
 
 // Private functions
 impl<T: Config> Pallet<T> {
+	fn _offchain_worker(_now: T::BlockNumber) -> Result<(), OffchainErr> {
+		// Check if we are a potential validator
+		if !sp_io::offchain::is_validator() {
+			return Err(OffchainErr::NotValidator);
+		}
+		let mut lock = StorageLock::<Time>::new(&OFFCHAIN_RISK_MANAGER_WORKER_LOCK);
+		// If the check of unsafe loans already started should be returned OffchainLock error.
+		// To prevent any race condition situations.
+		let _guard = lock.try_lock().map_err(|_| OffchainErr::OffchainLock)?;
+		Self::process_unsafe_loans().map_err(|_| OffchainErr::CheckFail)?;
+		Ok(())
+	}
+
+	/// Walks through the borrowers of all pools and submits an unsigned liquidation
+	/// transaction for each account with a shortfall.
+	/// Each account is liquidated in no more than one pool per run.
+	fn process_unsafe_loans() -> DispatchResult {
+		let mut checked_accounts: BTreeSet<T::AccountId> = BTreeSet::new();
+
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|pool_id| T::LiquidityPoolsManager::pool_exists(pool_id))
+			.try_for_each(|pool_id| -> DispatchResult {
+				T::LiquidityPoolsManager::get_pool_members_with_loans(pool_id)?
+					.into_iter()
+					.for_each(|borrower| {
+						if !checked_accounts.insert(borrower.clone()) {
+							return;
+						}
+						if Self::is_unsafe_loan(&borrower, pool_id) {
+							Self::submit_unsigned_tx(borrower, pool_id);
+						}
+					});
+				Ok(())
+			})
+	}
+
+	/// Checks if the account has a shortfall.
+	/// Checks if the loan has a shortfall after the interest is accrued in the pools of the
+	/// borrower, as it is done by the liquidation. The accrual is not persisted.
+	fn is_unsafe_loan(who: &T::AccountId, pool_id: CurrencyId) -> bool {
+		with_transaction(|| {
+			let is_unsafe = Self::accrue_interest_in_user_pools(who).map_or(false, |_| {
+				T::ControllerManager::get_hypothetical_account_liquidity(who, pool_id, 0, 0)
+					.map_or(false, |(_, shortfall)| !shortfall.is_zero())
+			});
+			TransactionOutcome::Rollback(is_unsafe)
+		})
+	}
+
+	/// Accrues interest in the pools the user uses so that loans and collateral are up to date.
+	/// The pools the user doesn't use can't block the liquidation.
+	fn accrue_interest_in_user_pools(who: &T::AccountId) -> DispatchResult {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|&pool_id| {
+				T::LiquidityPoolsManager::pool_exists(&pool_id)
+					&& (T::LiquidityPoolsManager::is_pool_collateral(who, pool_id)
+						|| !T::LiquidityPoolsManager::get_user_borrow_balance(who, pool_id).is_zero())
+			})
+			.try_for_each(T::ControllerManager::accrue_interest_rate)
+	}
+
+	fn submit_unsigned_tx(who: T::AccountId, pool_id: CurrencyId) {
+		let call = Call::<T>::liquidate(who.clone(), pool_id);
+		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+			log::info!(
+				target: "risk-manager offchain worker",
+				"submit unsigned liquidation tx for \n AccountId {:?} and CurrencyId {:?} \nfailed!",
+				who, pool_id,
+			);
+		}
+	}
+
 	/// Checks if liquidation_fee <= 0.5
 	fn is_valid_liquidation_fee(liquidation_fee: Rate) -> bool {
 		liquidation_fee <= T::MaxLiquidationFee::get()
 	}
 
+	/// Checks if liquidation_threshold <= 1 - collateral_factor of the pool.
+	/// A partial liquidation step can't exceed the share of the collateral that doesn't back
+	/// the borrowing power of the user.
+	fn is_valid_liquidation_threshold(pool_id: CurrencyId, liquidation_threshold: Rate) -> bool {
+		Rate::one()
+			.checked_sub(&T::ControllerManager::get_collateral_factor(pool_id))
+			.map_or(false, |max_threshold| liquidation_threshold <= max_threshold)
+	}
+
+	/// Checks that the pool is enabled in protocol and created.
+	fn ensure_pool_is_valid(pool_id: CurrencyId) -> DispatchResult {
+		ensure!(
+			pool_id.is_supported_underlying_asset(),
+			Error::<T>::NotValidUnderlyingAssetId
+		);
+		ensure!(
+			T::LiquidityPoolsManager::pool_exists(&pool_id),
+			Error::<T>::PoolNotFound
+		);
+		Ok(())
+	}
+
+	/// Calculates the bad debt of the pool after covering `amount` of it.
+	fn bad_debt_after_cover(pool_id: CurrencyId, amount: Balance) -> result::Result<Balance, DispatchError> {
+		Self::ensure_pool_is_valid(pool_id)?;
+		let bad_debt = T::LiquidityPoolsManager::get_pool_bad_debt(pool_id);
+		ensure!(!bad_debt.is_zero(), Error::<T>::ZeroBadDebt);
+		bad_debt
+			.checked_sub(amount)
+			.ok_or_else(|| Error::<T>::CoverAmountTooBig.into())
+	}
+
+	/// Writes off the remaining loans of the user in all pools to the bad debt.
+	/// Called when all of the user's collateral has been seized.
+	fn write_off_bad_debt(who: &T::AccountId) -> DispatchResult {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.filter(|pool_id| T::LiquidityPoolsManager::pool_exists(pool_id))
+			.try_for_each(|pool_id| -> DispatchResult {
+				let account_borrows = T::ControllerManager::borrow_balance_stored(who, pool_id)?;
+				if account_borrows.is_zero() {
+					return Ok(());
+				}
+				T::LiquidityPoolsManager::update_state_on_bad_debt(who, pool_id, account_borrows, account_borrows)?;
+				T::ControllerManager::decrease_isolation_mode_debt(who, pool_id, account_borrows, account_borrows)?;
+				Self::deposit_event(Event::BadDebtWrittenOff(who.clone(), pool_id, account_borrows));
+				Ok(())
+			})
+	}
+
+	/// Checks if auction duration is non-zero and 0 < min_price_ratio <= 1 + start_premium.
+	fn is_valid_auction_params(params: &AuctionParams<T::BlockNumber>) -> bool {
+		!params.duration.is_zero()
+			&& !params.min_price_ratio.is_zero()
+			&& Rate::one()
+				.checked_add(&params.start_premium)
+				.map_or(false, |max_ratio| params.min_price_ratio <= max_ratio)
+	}
+
+	/// Creates a new auction starting at the current block.
+	/// start_price = collateral_price / debt_price * (1 + start_premium),
+	/// min_price = collateral_price / debt_price * min_price_ratio.
+	fn new_auction(
+		collateral_pool_id: CurrencyId,
+		debt_pool_id: CurrencyId,
+		amount: Balance,
+		params: &AuctionParams<T::BlockNumber>,
+	) -> result::Result<CollateralAuction<T::BlockNumber>, DispatchError> {
+		let collateral_price =
+			T::PriceSource::get_underlying_price(collateral_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let debt_price = T::PriceSource::get_underlying_price(debt_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let oracle_price = collateral_price
+			.checked_div(&debt_price)
+			.ok_or(Error::<T>::NumOverflow)?;
+		let start_price = Rate::one()
+			.checked_add(&params.start_premium)
+			.and_then(|v| oracle_price.checked_mul(&v))
+			.ok_or(Error::<T>::NumOverflow)?;
+		let min_price = oracle_price
+			.checked_mul(&params.min_price_ratio)
+			.ok_or(Error::<T>::NumOverflow)?;
+		let start_block = <frame_system::Pallet<T>>::block_number();
+
+		Ok(CollateralAuction {
+			collateral_pool_id,
+			debt_pool_id,
+			amount,
+			start_price,
+			min_price,
+			start_block,
+			end_block: start_block.saturating_add(params.duration),
+			restarts: 0,
+		})
+	}
+
+	/// Starts an auction for the collateral seized in favor of the liquidation pools, if the
+	/// auctions are enabled in the collateral pool. The lot is moved from the liquidation pools
+	/// to the auction account.
+	fn start_auction(collateral_pool_id: CurrencyId, debt_pool_id: CurrencyId, amount: Balance) -> DispatchResult {
+		if collateral_pool_id == debt_pool_id {
+			return Ok(());
+		}
+		let params = match Self::auction_params(collateral_pool_id) {
+			Some(params) => params,
+			None => return Ok(()),
+		};
+
+		let auction = Self::new_auction(collateral_pool_id, debt_pool_id, amount, &params)?;
+		let start_price = auction.start_price;
+		let auction_id = Self::next_auction_id();
+		NextAuctionId::<T>::put(auction_id.checked_add(1).ok_or(Error::<T>::NumOverflow)?);
+		T::MultiCurrency::transfer(
+			collateral_pool_id,
+			&T::LiquidationPoolsManager::pools_account_id(),
+			&T::AuctionAccountId::get(),
+			amount,
+		)?;
+		Auctions::<T>::insert(auction_id, auction);
+
+		Self::deposit_event(Event::AuctionStarted(
+			auction_id,
+			collateral_pool_id,
+			debt_pool_id,
+			amount,
+			start_price,
+		));
+		Ok(())
+	}
+
+	/// Calculates the current price of the auction. The price decays linearly from
+	/// `start_price` to `min_price` during the auction:
+	/// price = start_price - (start_price - min_price) * (now - start_block) / duration.
+	fn get_auction_price(auction: &CollateralAuction<T::BlockNumber>, now: T::BlockNumber) -> RateResult {
+		ensure!(now < auction.end_block, Error::<T>::AuctionExpired);
+
+		let elapsed = TryInto::<u128>::try_into(now - auction.start_block).or(Err(Error::<T>::NumOverflow))?;
+		let duration =
+			TryInto::<u128>::try_into(auction.end_block - auction.start_block).or(Err(Error::<T>::NumOverflow))?;
+		let decay = auction
+			.start_price
+			.checked_sub(&auction.min_price)
+			.and_then(|v| v.checked_mul(&Rate::saturating_from_rational(elapsed, duration)))
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		Ok(auction.start_price - decay)
+	}
+
+	/// Calculates the payment in the debt asset for `amount` of the auctioned collateral:
+	/// payment = amount * price, taking into account the decimals of both assets.
+	/// The payment is rounded up, so the collateral is never sold below the auction price.
+	fn get_auction_payment(
+		auction: &CollateralAuction<T::BlockNumber>,
+		amount: Balance,
+		price: Price,
+	) -> result::Result<Balance, DispatchError> {
+		let normalized_amount = auction
+			.collateral_pool_id
+			.normalize_amount(amount)
+			.ok_or(Error::<T>::NumOverflow)?;
+		// The smallest unit of the debt asset in the normalized amounts.
+		let debt_unit = auction
+			.debt_pool_id
+			.normalize_amount(Balance::one())
+			.filter(|x| !x.is_zero())
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		let numerator = U256::from(normalized_amount) * U256::from(price.into_inner());
+		let denominator = U256::from(Price::accuracy()) * U256::from(debt_unit);
+		let payment = (numerator + denominator - U256::one()) / denominator;
+
+		TryInto::<Balance>::try_into(payment).map_err(|_| Error::<T>::NumOverflow.into())
+	}
+
 	/// Increases the parameter liquidation_attempts by one for user.
-	#[allow(dead_code)] // FIXME
 	fn user_liquidation_attempts_increase_by_one(who: &T::AccountId) {
 		UserLiquidationAttempts::<T>::mutate(who, |p| *p += u8::one())
 	}
@@ -192,6 +861,179 @@ impl<T: Config> Pallet<T> {
 	fn user_liquidation_attempts_reset_to_zero(who: &T::AccountId) {
 		UserLiquidationAttempts::<T>::mutate(who, |p| *p = u8::zero())
 	}
+
+	/// Calculates the value of the user's wrapped tokens in the pool in USD.
+	fn get_user_supply_in_usd(who: &T::AccountId, pool_id: CurrencyId) -> result::Result<Balance, DispatchError> {
+		let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+		let user_supply_wrap = T::MultiCurrency::free_balance(wrapped_id, &who);
+		let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
+		let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		T::LiquidityPoolsManager::wrapped_to_usd(pool_id, user_supply_wrap, exchange_rate, oracle_price)
+	}
+}
+
+// Public API
+impl<T: Config> Pallet<T> {
+	/// Liquidates an unsafe loan of the borrower in the specified pool.
+	/// The loan is repaid from the liquidation pools, and the borrower's collateral plus the
+	/// liquidation fee is seized in favor of the liquidation pools.
+	/// A partial liquidation occurs if the loan in USD is greater than or equal to
+	/// `PartialLiquidationMinSum` and the user has less than `PartialLiquidationMaxAttempts`
+	/// partial liquidations, otherwise the loan is liquidated in full.
+	///
+	/// - `borrower`: the account with an unsafe loan.
+	/// - `liquidated_pool_id`: the currency ID of the pool with the borrow to repay.
+	///
+	/// Returns (`repay_amount`, `seized_pools`, `is_partial_liquidation`).
+	#[transactional]
+	pub fn liquidate_unsafe_loan(borrower: T::AccountId, liquidated_pool_id: CurrencyId) -> LiquidationResult {
+		ensure!(
+			liquidated_pool_id.is_supported_underlying_asset(),
+			Error::<T>::NotValidUnderlyingAssetId
+		);
+		ensure!(
+			T::LiquidityPoolsManager::pool_exists(&liquidated_pool_id),
+			Error::<T>::PoolNotFound
+		);
+
+		Self::accrue_interest_in_user_pools(&borrower)?;
+
+		let (_, shortfall) =
+			T::ControllerManager::get_hypothetical_account_liquidity(&borrower, liquidated_pool_id, 0, 0)?;
+		ensure!(!shortfall.is_zero(), Error::<T>::SolventUserLoan);
+
+		let total_borrow_underlying = T::ControllerManager::borrow_balance_stored(&borrower, liquidated_pool_id)?;
+		ensure!(!total_borrow_underlying.is_zero(), Error::<T>::ZeroBorrowBalance);
+
+		let liquidated_pool_price =
+			T::PriceSource::get_underlying_price(liquidated_pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+		let total_borrow_usd = T::LiquidityPoolsManager::underlying_to_usd(
+			liquidated_pool_id,
+			total_borrow_underlying,
+			liquidated_pool_price,
+		)?;
+
+		let is_partial_liquidation = total_borrow_usd >= T::PartialLiquidationMinSum::get()
+			&& Self::user_liquidation_attempts(&borrower) < T::PartialLiquidationMaxAttempts::get();
+
+		let fee_factor = Rate::one()
+			.checked_add(&Self::liquidation_fee(liquidated_pool_id))
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		// In case of partial liquidation, the shortfall is covered with a step of
+		// `liquidation_threshold` in order to protect the user from micro liquidations.
+		// The users in e-mode get the liquidation threshold of their category.
+		let liquidation_threshold =
+			T::ControllerManager::get_user_emode_liquidation_threshold(&borrower, liquidated_pool_id)
+				.unwrap_or_else(|| Self::liquidation_threshold(liquidated_pool_id));
+		let mut repay_usd = if is_partial_liquidation {
+			Rate::one()
+				.checked_add(&liquidation_threshold)
+				.and_then(|v| Rate::from_inner(shortfall).checked_mul(&v))
+				.map(|x| x.into_inner())
+				.ok_or(Error::<T>::NumOverflow)?
+				.min(total_borrow_usd)
+		} else {
+			total_borrow_usd
+		};
+
+		// seize_usd = repay_usd * (1 + liquidation_fee)
+		let mut seize_usd = Rate::from_inner(repay_usd)
+			.checked_mul(&fee_factor)
+			.map(|x| x.into_inner())
+			.ok_or(Error::<T>::NumOverflow)?;
+
+		let collateral_pools = T::LiquidityPoolsManager::get_user_collateral_pools(&borrower)?;
+		let total_collateral_usd = collateral_pools.iter().try_fold(
+			Balance::zero(),
+			|acc, &pool_id| -> result::Result<Balance, DispatchError> {
+				let collateral_usd = Self::get_user_supply_in_usd(&borrower, pool_id)?;
+				acc.checked_add(collateral_usd)
+					.ok_or_else(|| Error::<T>::NumOverflow.into())
+			},
+		)?;
+		ensure!(!total_collateral_usd.is_zero(), Error::<T>::NoCollateralToSeize);
+
+		// The user's collateral is not enough to cover the loan with the liquidation fee,
+		// so we seize all collateral and repay the corresponding part of the loan.
+		let is_collateral_exhausted = seize_usd > total_collateral_usd;
+		if is_collateral_exhausted {
+			seize_usd = total_collateral_usd;
+			repay_usd = Rate::from_inner(seize_usd)
+				.checked_div(&fee_factor)
+				.map(|x| x.into_inner())
+				.ok_or(Error::<T>::NumOverflow)?;
+		}
+
+		let repay_underlying = if repay_usd == total_borrow_usd {
+			total_borrow_underlying
+		} else {
+			T::LiquidityPoolsManager::usd_to_underlying(liquidated_pool_id, repay_usd, liquidated_pool_price)?
+		};
+
+		let repay_amount = T::MinterestProtocolManager::do_repay_fresh(
+			&T::LiquidationPoolsManager::pools_account_id(),
+			&borrower,
+			liquidated_pool_id,
+			repay_underlying,
+			false,
+		)?;
+
+		let mut seized_pools: Vec<CurrencyId> = Vec::new();
+		for pool_id in collateral_pools.into_iter() {
+			if seize_usd.is_zero() {
+				break;
+			}
+
+			let oracle_price = T::PriceSource::get_underlying_price(pool_id).ok_or(Error::<T>::InvalidFeedPrice)?;
+			let user_supply_usd = Self::get_user_supply_in_usd(&borrower, pool_id)?;
+			if user_supply_usd.is_zero() {
+				continue;
+			}
+
+			let seize_underlying = if seize_usd >= user_supply_usd {
+				seize_usd -= user_supply_usd;
+				let wrapped_id = pool_id.wrapped_asset().ok_or(Error::<T>::NotValidUnderlyingAssetId)?;
+				let exchange_rate = T::LiquidityPoolsManager::get_exchange_rate(pool_id)?;
+				T::LiquidityPoolsManager::wrapped_to_underlying(
+					T::MultiCurrency::free_balance(wrapped_id, &borrower),
+					exchange_rate,
+				)?
+			} else {
+				let seize_underlying = T::LiquidityPoolsManager::usd_to_underlying(pool_id, seize_usd, oracle_price)?;
+				seize_usd = Balance::zero();
+				seize_underlying
+			};
+			if seize_underlying.is_zero() {
+				break;
+			}
+
+			T::MinterestProtocolManager::do_seize(&borrower, pool_id, seize_underlying)?;
+			Self::start_auction(pool_id, liquidated_pool_id, seize_underlying)?;
+			seized_pools.push(pool_id);
+		}
+
+		// The user has no collateral left, so the remaining loans can't be covered.
+		if is_collateral_exhausted {
+			Self::write_off_bad_debt(&borrower)?;
+		}
+
+		if is_partial_liquidation {
+			Self::user_liquidation_attempts_increase_by_one(&borrower);
+		} else {
+			Self::user_liquidation_attempts_reset_to_zero(&borrower);
+		}
+
+		Self::deposit_event(Event::LiquidateUnsafeLoan(
+			borrower,
+			liquidated_pool_id,
+			repay_amount,
+			seized_pools.clone(),
+			is_partial_liquidation,
+		));
+
+		Ok((repay_amount, seized_pools, is_partial_liquidation))
+	}
 }
 
 impl<T: Config> RiskManagerStorageProvider for Pallet<T> {
@@ -204,13 +1046,22 @@ impl<T: Config> RiskManagerStorageProvider for Pallet<T> {
 			Self::is_valid_liquidation_fee(liquidation_fee),
 			Error::<T>::InvalidLiquidationFeeValue
 		);
+		ensure!(
+			Self::is_valid_liquidation_threshold(pool_id, liquidation_threshold),
+			Error::<T>::InvalidLiquidationThresholdValue
+		);
 		LiquidationFee::<T>::insert(pool_id, liquidation_fee);
-		LiquidationThreshold::<T>::put(liquidation_threshold);
+		LiquidationThreshold::<T>::insert(pool_id, liquidation_threshold);
 		Ok(())
 	}
 
 	fn remove_pool(pool_id: CurrencyId) {
-		LiquidationFee::<T>::remove(pool_id)
+		LiquidationFee::<T>::remove(pool_id);
+		LiquidationThreshold::<T>::remove(pool_id);
+	}
+
+	fn get_liquidation_fee(pool_id: CurrencyId) -> Rate {
+		Self::liquidation_fee(pool_id)
 	}
 }
 
@@ -223,14 +1074,32 @@ impl<T: Config> UserLiquidationAttemptsManager<T::AccountId> for Pallet<T> {
 	/// Mutates user liquidation attempts depending on user operation.
 	/// If the user makes a deposit to the collateral pool, then attempts are set to zero.
 	fn mutate_depending_operation(pool_id: CurrencyId, who: &T::AccountId, operation: Operation) {
-		if operation == Operation::Deposit && T::UserCollateral::is_pool_collateral(&who, pool_id) {
+		if operation == Operation::Deposit && T::LiquidityPoolsManager::is_pool_collateral(&who, pool_id) {
 			let user_liquidation_attempts = Self::get_user_liquidation_attempts(&who);
 			if !user_liquidation_attempts.is_zero() {
 				Self::user_liquidation_attempts_reset_to_zero(&who);
 			}
-		// Fixme: After implementation of liquidation fix this case and cover with tests
-		} else if operation == Operation::Repay {
-			Self::user_liquidation_attempts_increase_by_one(&who);
+		}
+	}
+}
+
+impl<T: Config> ValidateUnsigned for Pallet<T> {
+	type Call = Call<T>;
+
+	fn validate_unsigned(_source: TransactionSource, call: &Self::Call) -> TransactionValidity {
+		match call {
+			Call::liquidate(who, pool_id) => {
+				if !Self::is_unsafe_loan(who, *pool_id) {
+					return InvalidTransaction::Stale.into();
+				}
+				ValidTransaction::with_tag_prefix("RiskManagerOffchainWorker")
+					.priority(T::UnsignedPriority::get())
+					.and_provides((<frame_system::Pallet<T>>::block_number(), who, pool_id))
+					.longevity(64_u64)
+					.propagate(true)
+					.build()
+			}
+			_ => InvalidTransaction::Call.into(),
 		}
 	}
 }
@@ -0,0 +1,25 @@
+//! Storage migrations for the risk-manager pallet.
+
+use super::*;
+use frame_support::storage::{migration::take_storage_value, StoragePrefixedMap};
+
+/// Moves the single global `LiquidationThreshold` value into the per-pool
+/// `LiquidationThreshold` map. The old value is set for every pool that has a liquidation fee.
+/// Does nothing if the global value has already been migrated.
+pub fn migrate_liquidation_threshold_to_map<T: Config>() -> Weight {
+	let module_prefix = LiquidationThreshold::<T>::module_prefix();
+	let storage_prefix = LiquidationThreshold::<T>::storage_prefix();
+
+	match take_storage_value::<Rate>(module_prefix, storage_prefix, &[]) {
+		Some(threshold) => {
+			let pools: Vec<CurrencyId> = LiquidationFee::<T>::iter().map(|(pool_id, _)| pool_id).collect();
+			pools
+				.iter()
+				.for_each(|pool_id| LiquidationThreshold::<T>::insert(pool_id, threshold));
+
+			let pools_count = pools.len() as Weight;
+			T::DbWeight::get().reads_writes(pools_count + 1, pools_count + 1)
+		}
+		None => T::DbWeight::get().reads(1),
+	}
+}
@@ -1,8 +1,9 @@
 /// Mocks for the RiskManager pallet.
 use super::*;
 use crate as risk_manager;
+use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 use frame_support::{ord_parameter_types, pallet_prelude::GenesisBuild, parameter_types, PalletId};
-use frame_system::EnsureSignedBy;
+use frame_system::{offchain::SendTransactionTypes, EnsureSignedBy};
 use liquidity_pools::{Pool, PoolUserData};
 use minterest_primitives::currency::CurrencyType::{UnderlyingAsset, WrappedToken};
 pub use minterest_primitives::{Balance, Price, Rate};
@@ -10,9 +11,12 @@ use orml_traits::parameter_type_with_key;
 use pallet_traits::PricesManager;
 use sp_core::H256;
 use sp_runtime::{
-	testing::Header,
+	testing::{Header, TestXt},
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup},
+	transaction_validity::TransactionPriority,
+	FixedPointNumber,
 };
+use std::{cell::RefCell, collections::HashMap};
 pub use test_helper::*;
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -29,7 +33,14 @@ frame_support::construct_runtime!(
 		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Call, Event<T>, Config<T>},
 		Currencies: orml_currencies::{Pallet, Call, Event<T>},
+		TestController: controller::{Pallet, Storage, Call, Event, Config<T>},
+		TestMinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
+		TestMinterestProtocol: minterest_protocol::{Pallet, Storage, Call, Event<T>},
 		TestPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
+		TestLiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, Config<T>},
+		TestDex: dex::{Pallet, Storage, Call, Event<T>},
+		TestMntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
+		TestWhitelist: whitelist_module::{Pallet, Storage, Call, Event<T>, Config<T>},
 		TestRiskManager: risk_manager::{Pallet, Storage, Call, Event<T>, Config<T>},
 	}
 );
@@ -41,8 +52,10 @@ ord_parameter_types! {
 parameter_types! {
 	pub const LiquidityPoolsPalletId: PalletId = PalletId(*b"lqdi/min");
 	pub const LiquidationPoolsPalletId: PalletId = PalletId(*b"lqdn/min");
+	pub const MntTokenPalletId: PalletId = PalletId(*b"min/mntt");
 	pub LiquidityPoolAccountId: AccountId = LiquidityPoolsPalletId::get().into_account();
 	pub LiquidationPoolAccountId: AccountId = LiquidationPoolsPalletId::get().into_account();
+	pub MntTokenAccountId: AccountId = MntTokenPalletId::get().into_account();
 	pub InitialExchangeRate: Rate = Rate::one();
 	pub EnabledUnderlyingAssetsIds: Vec<CurrencyId> = CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset);
 	pub EnabledWrappedTokensId: Vec<CurrencyId> = CurrencyId::get_enabled_tokens_in_protocol(WrappedToken);
@@ -53,13 +66,39 @@ mock_impl_balances_config!(Test);
 mock_impl_orml_tokens_config!(Test);
 mock_impl_orml_currencies_config!(Test);
 mock_impl_liquidity_pools_config!(Test);
+mock_impl_liquidation_pools_config!(Test);
+mock_impl_controller_config!(Test, ZeroAdmin);
+mock_impl_minterest_model_config!(Test, ZeroAdmin);
+mock_impl_dex_config!(Test);
+mock_impl_minterest_protocol_config!(Test, ZeroAdmin);
+mock_impl_mnt_token_config!(Test, ZeroAdmin);
 mock_impl_risk_manager_config!(Test, ZeroAdmin);
+mock_impl_whitelist_module_config!(Test, ZeroAdmin);
+
+thread_local! {
+	static UNDERLYING_PRICE: RefCell<HashMap<CurrencyId, Price>> = RefCell::new(
+		[
+			(DOT, Price::one()),
+			(ETH, Price::one()),
+			(BTC, Price::one()),
+			(KSM, Price::one()),
+		]
+		.iter()
+		.cloned()
+		.collect());
+}
 
 pub struct MockPriceSource;
 
+impl MockPriceSource {
+	pub fn set_underlying_price(currency_id: CurrencyId, price: Price) {
+		UNDERLYING_PRICE.with(|v| v.borrow_mut().insert(currency_id, price));
+	}
+}
+
 impl PricesManager<CurrencyId> for MockPriceSource {
-	fn get_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
-		Some(Price::one())
+	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price> {
+		UNDERLYING_PRICE.with(|v| v.borrow().get(&currency_id).copied())
 	}
 
 	fn lock_price(_currency_id: CurrencyId) {}
@@ -67,13 +106,66 @@ impl PricesManager<CurrencyId> for MockPriceSource {
 	fn unlock_price(_currency_id: CurrencyId) {}
 }
 
-#[derive(Default)]
 pub struct ExternalityBuilder {
+	endowed_accounts: Vec<(AccountId, CurrencyId, Balance)>,
 	pools: Vec<(CurrencyId, Pool)>,
 	pool_user_data: Vec<(CurrencyId, AccountId, PoolUserData)>,
+	liquidation_fee: Vec<(CurrencyId, Rate)>,
+	liquidation_threshold: Vec<(CurrencyId, Rate)>,
+}
+
+impl Default for ExternalityBuilder {
+	fn default() -> Self {
+		Self {
+			endowed_accounts: vec![],
+			pools: vec![],
+			pool_user_data: vec![],
+			liquidation_fee: vec![
+				(DOT, Rate::saturating_from_rational(5, 100)),
+				(ETH, Rate::saturating_from_rational(5, 100)),
+				(BTC, Rate::saturating_from_rational(5, 100)),
+				(KSM, Rate::saturating_from_rational(5, 100)),
+			],
+			liquidation_threshold: vec![
+				(DOT, Rate::saturating_from_rational(3, 100)),
+				(ETH, Rate::saturating_from_rational(3, 100)),
+				(BTC, Rate::saturating_from_rational(3, 100)),
+				(KSM, Rate::saturating_from_rational(3, 100)),
+			],
+		}
+	}
 }
 
 impl ExternalityBuilder {
+	pub fn user_balance(mut self, user: AccountId, currency_id: CurrencyId, balance: Balance) -> Self {
+		self.endowed_accounts.push((user, currency_id, balance));
+		self
+	}
+
+	pub fn pool_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
+		self.endowed_accounts
+			.push((TestPools::pools_account_id(), currency_id, balance));
+		self
+	}
+
+	pub fn liquidation_pool_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
+		self.endowed_accounts
+			.push((TestLiquidationPools::pools_account_id(), currency_id, balance));
+		self
+	}
+
+	pub fn pool_borrow_underlying(mut self, pool_id: CurrencyId, borrowed: Balance) -> Self {
+		self.pools.push((
+			pool_id,
+			Pool {
+				borrowed,
+				borrow_index: Rate::one(),
+				protocol_interest: Balance::zero(),
+			},
+		));
+		self
+	}
+
 	pub fn pool_user_data(
 		mut self,
 		pool_id: CurrencyId,
@@ -97,6 +189,12 @@ impl ExternalityBuilder {
 	pub fn build(self) -> sp_io::TestExternalities {
 		let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
 
+		orml_tokens::GenesisConfig::<Test> {
+			balances: self.endowed_accounts,
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
 		liquidity_pools::GenesisConfig::<Test> {
 			pools: self.pools,
 			pool_user_data: self.pool_user_data,
@@ -104,6 +202,43 @@ impl ExternalityBuilder {
 		.assimilate_storage(&mut storage)
 		.unwrap();
 
+		controller::GenesisConfig::<Test> {
+			controller_params: [DOT, ETH, BTC, KSM]
+				.iter()
+				.map(|&pool_id| {
+					(
+						pool_id,
+						ControllerData {
+							last_interest_accrued_block: 1,
+							protocol_interest_factor: Rate::saturating_from_rational(1, 10), // 10%
+							max_borrow_rate: Rate::saturating_from_rational(5, 1000),        // 0.5%
+							collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
+							borrow_cap: None,
+							protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+							supply_cap: None,
+							interest_accrual_mode: InterestAccrualMode::Simple,
+						},
+					)
+				})
+				.collect(),
+			pause_keepers: vec![
+				(DOT, PauseKeeper::all_unpaused()),
+				(ETH, PauseKeeper::all_unpaused()),
+				(BTC, PauseKeeper::all_unpaused()),
+				(KSM, PauseKeeper::all_unpaused()),
+			],
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
+		risk_manager::GenesisConfig::<Test> {
+			liquidation_fee: self.liquidation_fee,
+			liquidation_threshold: self.liquidation_threshold,
+			_phantom: Default::default(),
+		}
+		.assimilate_storage(&mut storage)
+		.unwrap();
+
 		let mut ext: sp_io::TestExternalities = storage.into();
 		ext.execute_with(|| System::set_block_number(1));
 		ext
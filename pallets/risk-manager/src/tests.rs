@@ -1,10 +1,107 @@
 //! Tests for the risk-manager pallet.
 use super::*;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{
+	assert_noop, assert_ok,
+	storage::{
+		migration::{get_storage_value, put_storage_value},
+		StoragePrefixedMap,
+	},
+};
 use minterest_primitives::Operation::Deposit;
 use mock::{Event, *};
+use sp_core::offchain::{
+	testing::{TestOffchainExt, TestTransactionPoolExt},
+	OffchainDbExt, OffchainWorkerExt, TransactionPoolExt,
+};
 use sp_runtime::{traits::BadOrigin, FixedPointNumber};
 
+#[test]
+fn offchain_worker_liquidation_test() {
+	// Alice's collateral: 110 MDOT * 0.9 = 99$, Alice's borrow: 100$.
+	// Bob's collateral: 200 MDOT * 0.9 = 180$, Bob's borrow: 100$.
+	// The offchain worker must send transaction for Alice's loan liquidation only.
+	let mut ext = ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 200 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 310))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 110))
+		.user_balance(BOB, MDOT, units(MDOT, 200))
+		.user_balance(CHARLIE, METH, 1_200 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 100 * DOLLARS, Rate::one(), false)
+		.pool_user_data(DOT, BOB, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, BOB, 100 * DOLLARS, Rate::one(), false)
+		.build();
+	let (offchain, _) = TestOffchainExt::new();
+
+	let (pool, trans_pool_state) = TestTransactionPoolExt::new();
+	ext.register_extension(OffchainDbExt::new(offchain.clone()));
+	ext.register_extension(OffchainWorkerExt::new(offchain));
+	ext.register_extension(TransactionPoolExt::new(pool));
+
+	ext.execute_with(|| {
+		assert_ok!(TestRiskManager::_offchain_worker(0));
+
+		// 1 liquidation transaction in transactions pool
+		assert_eq!(trans_pool_state.read().transactions.len(), 1);
+		let transaction = trans_pool_state.write().transactions.pop().unwrap();
+		let ex: MockExtrinsic = Decode::decode(&mut &*transaction).unwrap();
+		// Called extrinsic input params
+		let (who, pool_id) = match ex.call {
+			crate::mock::Call::TestRiskManager(crate::Call::liquidate(who, pool_id)) => (who, pool_id),
+			e => panic!("Unexpected call: {:?}", e),
+		};
+		assert_eq!(who, ALICE);
+		assert_eq!(pool_id, ETH);
+
+		// Only unsafe loans pass the unsigned transaction validation.
+		assert!(
+			TestRiskManager::validate_unsigned(TransactionSource::Local, &crate::Call::liquidate(ALICE, ETH)).is_ok()
+		);
+		assert_eq!(
+			TestRiskManager::validate_unsigned(TransactionSource::Local, &crate::Call::liquidate(BOB, ETH)),
+			InvalidTransaction::Stale.into()
+		);
+	});
+}
+
+#[test]
+fn validate_unsigned_should_check_loans_with_accrued_interest() {
+	// Bob's collateral: 200 MDOT * 0.9 = 180$, Bob's stored borrow: 100$.
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 100 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 200))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(BOB, MDOT, units(MDOT, 200))
+		.user_balance(CHARLIE, METH, 1_100 * DOLLARS)
+		.pool_user_data(DOT, BOB, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, BOB, 100 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			assert_eq!(
+				TestRiskManager::validate_unsigned(TransactionSource::Local, &crate::Call::liquidate(BOB, ETH)),
+				InvalidTransaction::Stale.into()
+			);
+
+			// The borrow rate is 0.4% per block, so after 250 blocks Bob's borrow is 200$.
+			assert_ok!(TestMinterestModel::set_base_rate(
+				admin_origin(),
+				ETH,
+				Rate::saturating_from_integer(21_024)
+			));
+			System::set_block_number(251);
+			assert!(
+				TestRiskManager::validate_unsigned(TransactionSource::Local, &crate::Call::liquidate(BOB, ETH)).is_ok()
+			);
+
+			// The interest accrued by the validation is not persisted.
+			assert_eq!(TestController::controller_params(ETH).last_interest_accrued_block, 1);
+			assert_eq!(TestPools::pool_user_data(ETH, BOB).borrowed, 100 * DOLLARS);
+		});
+}
+
 #[test]
 fn user_liquidation_attempts_should_work() {
 	ExternalityBuilder::default().build().execute_with(|| {
@@ -78,16 +175,34 @@ fn set_liquidation_fee_should_work() {
 #[test]
 fn set_threshold_should_work() {
 	ExternalityBuilder::default().build().execute_with(|| {
-		// Can be set to 1.0
+		// Can be set to 0.1 = 1 - collateral_factor.
 		assert_ok!(TestRiskManager::set_liquidation_threshold(
 			admin_origin(),
 			DOT,
-			Rate::one()
+			Rate::saturating_from_rational(1, 10)
+		));
+		assert_eq!(
+			TestRiskManager::liquidation_threshold(DOT),
+			Rate::saturating_from_rational(1, 10)
+		);
+		let expected_event = Event::TestRiskManager(crate::Event::LiquidationThresholdUpdated(
+			DOT,
+			Rate::saturating_from_rational(1, 10),
 		));
-		assert_eq!(TestRiskManager::liquidation_threshold(), Rate::one());
-		let expected_event = Event::TestRiskManager(crate::Event::LiquidationThresholdUpdated(Rate::one()));
 		assert!(System::events().iter().any(|record| record.event == expected_event));
 
+		// Threshold of other pools is not changed.
+		assert_eq!(
+			TestRiskManager::liquidation_threshold(ETH),
+			Rate::saturating_from_rational(3, 100)
+		);
+
+		// Can't be greater than 1 - collateral_factor.
+		assert_noop!(
+			TestRiskManager::set_liquidation_threshold(admin_origin(), DOT, Rate::saturating_from_rational(11, 100)),
+			Error::<Test>::InvalidLiquidationThresholdValue
+		);
+
 		// The dispatch origin of this call must be Administrator.
 		assert_noop!(
 			TestRiskManager::set_liquidation_threshold(alice_origin(), DOT, Rate::one()),
@@ -101,3 +216,521 @@ fn set_threshold_should_work() {
 		);
 	});
 }
+
+#[test]
+fn liquidation_threshold_migration_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		// Emulate the storage before migration: a single global liquidation threshold.
+		let module_prefix = LiquidationThreshold::<Test>::module_prefix();
+		let storage_prefix = LiquidationThreshold::<Test>::storage_prefix();
+		[DOT, ETH, BTC, KSM]
+			.iter()
+			.for_each(|pool_id| LiquidationThreshold::<Test>::remove(pool_id));
+		put_storage_value(
+			module_prefix,
+			storage_prefix,
+			&[],
+			Rate::saturating_from_rational(5, 100),
+		);
+
+		migrations::migrate_liquidation_threshold_to_map::<Test>();
+
+		[DOT, ETH, BTC, KSM].iter().for_each(|&pool_id| {
+			assert_eq!(
+				TestRiskManager::liquidation_threshold(pool_id),
+				Rate::saturating_from_rational(5, 100)
+			)
+		});
+		assert_eq!(get_storage_value::<Rate>(module_prefix, storage_prefix, &[]), None);
+
+		// The second run does nothing.
+		migrations::migrate_liquidation_threshold_to_map::<Test>();
+		assert_eq!(
+			TestRiskManager::liquidation_threshold(DOT),
+			Rate::saturating_from_rational(5, 100)
+		);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_loan_should_work() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 100 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 110))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 110))
+		.user_balance(BOB, METH, 1_100 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 100 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			// Alice's collateral: 110 MDOT * 0.9 = 99$, Alice's borrow: 100$.
+			// The loan is less than PartialLiquidationMinSum, so the liquidation is complete.
+			assert_eq!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Ok((100 * DOLLARS, vec![DOT], false))
+			);
+			let expected_event = Event::TestRiskManager(crate::Event::LiquidateUnsafeLoan(
+				ALICE,
+				ETH,
+				100 * DOLLARS,
+				vec![DOT],
+				false,
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// The loan is fully repaid from the liquidation pools.
+			assert_eq!(TestPools::pool_user_data(ETH, ALICE).borrowed, Balance::zero());
+			assert_eq!(TestPools::pools(ETH).borrowed, Balance::zero());
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestPools::pools_account_id()),
+				1_100 * DOLLARS
+			);
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
+				900 * DOLLARS
+			);
+
+			// 100$ of the loan + 5% liquidation fee = 105$ of collateral is seized.
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), units(MDOT, 5));
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestPools::pools_account_id()),
+				units(DOT, 5)
+			);
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
+				units(DOT, 105)
+			);
+			assert_eq!(TestRiskManager::user_liquidation_attempts(ALICE), u8::zero());
+		});
+}
+
+#[test]
+fn liquidate_unsafe_loan_should_ignore_pools_not_used_by_borrower() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 100 * DOLLARS)
+		.pool_borrow_underlying(BTC, units(BTC, 100))
+		.pool_balance(DOT, units(DOT, 110))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 110))
+		.user_balance(BOB, METH, 1_100 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 100 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			// The borrow rate in BTC pool exceeds the maximum borrow rate, so the interest can't
+			// be accrued there.
+			minterest_model::MinterestModelParams::<Test>::insert(
+				BTC,
+				minterest_model::MinterestModelData {
+					kink: Rate::one(),
+					base_rate_per_block: Rate::saturating_from_rational(1, 100),
+					multiplier_per_block: Rate::zero(),
+					jump_multiplier_per_block: Rate::zero(),
+				},
+			);
+			System::set_block_number(2);
+			assert_noop!(
+				TestController::accrue_interest_rate(BTC),
+				controller::Error::<Test>::BorrowRateTooHigh
+			);
+
+			// Alice doesn't use BTC pool, so her loan is still liquidated.
+			assert_eq!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Ok((100 * DOLLARS, vec![DOT], false))
+			);
+		});
+}
+
+#[test]
+fn partial_liquidation_should_work() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 190_000 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 200_000))
+		.pool_balance(ETH, 10_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, ONE_MILL)
+		.user_balance(ALICE, MDOT, units(MDOT, 200_000))
+		.user_balance(BOB, METH, 200_000 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 190_000 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			// Alice's collateral: 200_000 MDOT * 0.9 = 180_000$, Alice's borrow: 190_000$.
+			// Shortfall: 10_000$ * (1 + 0.03 liquidation threshold) = 10_300$ to repay.
+			assert_eq!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Ok((10_300 * DOLLARS, vec![DOT], true))
+			);
+			assert_eq!(TestPools::pool_user_data(ETH, ALICE).borrowed, 179_700 * DOLLARS);
+			// 10_300$ + 5% liquidation fee = 10_815$ of collateral is seized.
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), units(MDOT, 189_185));
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
+				units(DOT, 10_815)
+			);
+			assert_eq!(TestRiskManager::user_liquidation_attempts(ALICE), 1_u8);
+
+			// The maximum number of partial liquidations is reached, the loan is liquidated in full.
+			UserLiquidationAttempts::<Test>::insert(ALICE, 3_u8);
+			// Alice's collateral: 189_185 MDOT * 0.9 = 170_266.5$, Alice's borrow: 179_700$.
+			assert_eq!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Ok((179_700 * DOLLARS, vec![DOT], false))
+			);
+			assert_eq!(TestPools::pool_user_data(ETH, ALICE).borrowed, Balance::zero());
+			// 179_700$ + 5% liquidation fee = 188_685$ of collateral is seized.
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), units(MDOT, 500));
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
+				units(DOT, 199_500)
+			);
+			assert_eq!(TestRiskManager::user_liquidation_attempts(ALICE), u8::zero());
+		});
+}
+
+#[test]
+fn liquidate_unsafe_loan_should_fail() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 80 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 100))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 100))
+		.user_balance(BOB, METH, 1_080 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 80 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			// The dispatch origin of this call must be _None_.
+			assert_noop!(TestRiskManager::liquidate(alice_origin(), ALICE, ETH), BadOrigin);
+
+			// Alice's collateral: 100 MDOT * 0.9 = 90$, Alice's borrow: 80$.
+			assert_noop!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Error::<Test>::SolventUserLoan
+			);
+			assert_noop!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, MDOT),
+				Error::<Test>::NotValidUnderlyingAssetId
+			);
+			assert_noop!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, KSM),
+				Error::<Test>::PoolNotFound
+			);
+
+			// Alice's collateral: 100 MDOT * 0.9 * 0.5 = 45$, Alice's borrow: 80$.
+			MockPriceSource::set_underlying_price(DOT, Price::saturating_from_rational(5, 10));
+			// Alice has no borrow in DOT pool.
+			assert_noop!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, DOT),
+				Error::<Test>::ZeroBorrowBalance
+			);
+		});
+}
+
+#[test]
+fn bad_debt_should_be_written_off_and_covered() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 80 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 100))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 100))
+		.user_balance(BOB, METH, 1_080 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 80 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			LiquidationFee::<Test>::insert(ETH, Rate::zero());
+			// Alice's collateral: 100 MDOT * 0.5 = 50$, Alice's borrow: 80$.
+			MockPriceSource::set_underlying_price(DOT, Price::saturating_from_rational(5, 10));
+			// Alice's loan is recorded in the debt of the isolated DOT collateral.
+			controller::IsolationModeUserDebt::<Test>::insert(ALICE, ETH, (DOT, 80 * DOLLARS));
+			controller::IsolationModeTotalDebt::<Test>::insert(DOT, 100 * DOLLARS);
+
+			// All collateral is seized, 50$ of the loan is repaid and 30$ is written off.
+			assert_eq!(
+				TestRiskManager::liquidate_unsafe_loan(ALICE, ETH),
+				Ok((50 * DOLLARS, vec![DOT], false))
+			);
+			let expected_event = Event::TestRiskManager(crate::Event::BadDebtWrittenOff(ALICE, ETH, 30 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(MDOT, &ALICE), Balance::zero());
+			assert_eq!(TestPools::pool_user_data(ETH, ALICE).borrowed, Balance::zero());
+			assert_eq!(TestPools::pools(ETH).borrowed, Balance::zero());
+			assert_eq!(TestPools::pool_bad_debt(ETH), 30 * DOLLARS);
+			// Both the repaid and the written off parts of the loan are released from the debt of
+			// the isolated collateral.
+			assert_eq!(TestController::isolation_mode_total_debt(DOT), 20 * DOLLARS);
+			assert_eq!(TestController::isolation_mode_user_debt(ALICE, ETH), None);
+			// The written off loan is absorbed by the exchange rate right away.
+			// exchange_rate = (1_050 + 0 - 0) / 1_080
+			assert_eq!(
+				TestPools::get_exchange_rate(ETH),
+				Ok(Rate::saturating_from_rational(1_050, 1_080))
+			);
+
+			// Cover 10$ of the bad debt from the liquidation pool.
+			assert_noop!(
+				TestRiskManager::cover_bad_debt_from_liquidation_pool(alice_origin(), ETH, 10 * DOLLARS),
+				BadOrigin
+			);
+			assert_noop!(
+				TestRiskManager::cover_bad_debt_from_liquidation_pool(admin_origin(), ETH, 31 * DOLLARS),
+				Error::<Test>::CoverAmountTooBig
+			);
+			assert_noop!(
+				TestRiskManager::cover_bad_debt_from_liquidation_pool(admin_origin(), KSM, 10 * DOLLARS),
+				Error::<Test>::PoolNotFound
+			);
+			assert_ok!(TestRiskManager::cover_bad_debt_from_liquidation_pool(
+				admin_origin(),
+				ETH,
+				10 * DOLLARS
+			));
+			let expected_event =
+				Event::TestRiskManager(crate::Event::BadDebtCoveredFromLiquidationPool(ETH, 10 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestPools::pool_bad_debt(ETH), 20 * DOLLARS);
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
+				940 * DOLLARS
+			);
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestPools::pools_account_id()),
+				1_060 * DOLLARS
+			);
+			// exchange_rate = (1_060 + 0 - 0) / 1_080
+			assert_eq!(
+				TestPools::get_exchange_rate(ETH),
+				Ok(Rate::saturating_from_rational(1_060, 1_080))
+			);
+
+			// Cover 10$ of the bad debt from the protocol interest.
+			TestPools::set_pool_protocol_interest(ETH, 15 * DOLLARS);
+			assert_ok!(TestRiskManager::cover_bad_debt_from_protocol_interest(
+				admin_origin(),
+				ETH,
+				10 * DOLLARS
+			));
+			assert_eq!(TestPools::pool_bad_debt(ETH), 10 * DOLLARS);
+			assert_eq!(TestPools::pools(ETH).protocol_interest, 5 * DOLLARS);
+			assert_noop!(
+				TestRiskManager::cover_bad_debt_from_protocol_interest(admin_origin(), ETH, 10 * DOLLARS),
+				Error::<Test>::NotEnoughProtocolInterest
+			);
+			// exchange_rate = (1_060 + 0 - 5) / 1_080
+			assert_eq!(
+				TestPools::get_exchange_rate(ETH),
+				Ok(Rate::saturating_from_rational(1_055, 1_080))
+			);
+
+			// The rest of the bad debt is socialized, the exchange rate stays the same.
+			assert_ok!(TestRiskManager::socialize_bad_debt(admin_origin(), ETH));
+			let expected_event = Event::TestRiskManager(crate::Event::BadDebtSocialized(ETH, 10 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestPools::pool_bad_debt(ETH), Balance::zero());
+			assert_eq!(
+				TestPools::get_exchange_rate(ETH),
+				Ok(Rate::saturating_from_rational(1_055, 1_080))
+			);
+			assert_noop!(
+				TestRiskManager::socialize_bad_debt(admin_origin(), ETH),
+				Error::<Test>::ZeroBadDebt
+			);
+		});
+}
+
+#[test]
+fn set_auction_params_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let params = AuctionParams {
+			start_premium: Rate::saturating_from_rational(1, 10),
+			min_price_ratio: Rate::saturating_from_rational(9, 10),
+			duration: 10,
+			max_restarts: 1,
+		};
+		assert_ok!(TestRiskManager::set_auction_params(admin_origin(), DOT, Some(params)));
+		assert_eq!(TestRiskManager::auction_params(DOT), Some(params));
+		let expected_event = Event::TestRiskManager(crate::Event::AuctionParamsUpdated(DOT, Some(params)));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+
+		// Auctions can be disabled.
+		assert_ok!(TestRiskManager::set_auction_params(admin_origin(), DOT, None));
+		assert_eq!(TestRiskManager::auction_params(DOT), None);
+
+		// Duration can't be zero.
+		assert_noop!(
+			TestRiskManager::set_auction_params(admin_origin(), DOT, Some(AuctionParams { duration: 0, ..params })),
+			Error::<Test>::InvalidAuctionParams
+		);
+		// Minimum price can't be zero.
+		assert_noop!(
+			TestRiskManager::set_auction_params(
+				admin_origin(),
+				DOT,
+				Some(AuctionParams {
+					min_price_ratio: Rate::zero(),
+					..params
+				})
+			),
+			Error::<Test>::InvalidAuctionParams
+		);
+		// Minimum price can't be greater than the start price.
+		assert_noop!(
+			TestRiskManager::set_auction_params(
+				admin_origin(),
+				DOT,
+				Some(AuctionParams {
+					min_price_ratio: Rate::saturating_from_rational(12, 10),
+					..params
+				})
+			),
+			Error::<Test>::InvalidAuctionParams
+		);
+		assert_noop!(
+			TestRiskManager::set_auction_params(admin_origin(), MDOT, Some(params)),
+			Error::<Test>::NotValidUnderlyingAssetId
+		);
+		assert_noop!(
+			TestRiskManager::set_auction_params(alice_origin(), DOT, Some(params)),
+			BadOrigin
+		);
+	});
+}
+
+#[test]
+fn collateral_auction_should_work() {
+	ExternalityBuilder::default()
+		.pool_borrow_underlying(DOT, Balance::zero())
+		.pool_borrow_underlying(ETH, 100 * DOLLARS)
+		.pool_balance(DOT, units(DOT, 110))
+		.pool_balance(ETH, 1_000 * DOLLARS)
+		.liquidation_pool_balance(ETH, 1_000 * DOLLARS)
+		.user_balance(ALICE, MDOT, units(MDOT, 110))
+		.user_balance(BOB, METH, 1_100 * DOLLARS)
+		.user_balance(BOB, ETH, 1_000 * DOLLARS)
+		.pool_user_data(DOT, ALICE, Balance::zero(), Rate::zero(), true)
+		.pool_user_data(ETH, ALICE, 100 * DOLLARS, Rate::one(), false)
+		.build()
+		.execute_with(|| {
+			assert_ok!(TestRiskManager::set_auction_params(
+				admin_origin(),
+				DOT,
+				Some(AuctionParams {
+					start_premium: Rate::saturating_from_rational(1, 10),
+					min_price_ratio: Rate::saturating_from_rational(9, 10),
+					duration: 10,
+					max_restarts: 1,
+				})
+			));
+
+			// 105 DOT of the seized collateral are put up for auction.
+			assert_ok!(TestRiskManager::liquidate_unsafe_loan(ALICE, ETH));
+			let expected_event = Event::TestRiskManager(crate::Event::AuctionStarted(
+				0,
+				DOT,
+				ETH,
+				units(DOT, 105),
+				Price::saturating_from_rational(11, 10),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestRiskManager::next_auction_id(), 1);
+			// The lot is kept apart from the liquidation pool.
+			assert_eq!(Currencies::free_balance(DOT, &AuctionAccountId::get()), units(DOT, 105));
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
+				Balance::zero()
+			);
+
+			assert_noop!(
+				TestRiskManager::bid(bob_origin(), 1, units(DOT, 10)),
+				Error::<Test>::AuctionNotFound
+			);
+			assert_noop!(
+				TestRiskManager::bid(bob_origin(), 0, Balance::zero()),
+				Error::<Test>::ZeroBalanceTransaction
+			);
+			assert_noop!(
+				TestRiskManager::restart_auction(bob_origin(), 0),
+				Error::<Test>::AuctionNotExpired
+			);
+
+			// The auction has expired.
+			System::set_block_number(11);
+			assert_noop!(
+				TestRiskManager::bid(bob_origin(), 0, units(DOT, 10)),
+				Error::<Test>::AuctionExpired
+			);
+			assert_ok!(TestRiskManager::restart_auction(bob_origin(), 0));
+			let expected_event = Event::TestRiskManager(crate::Event::AuctionRestarted(
+				0,
+				Price::saturating_from_rational(11, 10),
+			));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+
+			// price = 1.1 - (1.1 - 0.9) * 5 / 10 = 1.0
+			System::set_block_number(16);
+			assert_ok!(TestRiskManager::bid(bob_origin(), 0, units(DOT, 50)));
+			let expected_event = Event::TestRiskManager(crate::Event::AuctionBid(0, BOB, units(DOT, 50), 50 * DOLLARS));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(Currencies::free_balance(DOT, &BOB), units(DOT, 50));
+			assert_eq!(Currencies::free_balance(ETH, &BOB), 950 * DOLLARS);
+			assert_eq!(
+				Currencies::free_balance(ETH, &TestLiquidationPools::pools_account_id()),
+				950 * DOLLARS
+			);
+			assert_eq!(Currencies::free_balance(DOT, &AuctionAccountId::get()), units(DOT, 55));
+			assert_eq!(TestRiskManager::auctions(0).map(|x| x.amount), Some(units(DOT, 55)));
+
+			// The maximum number of restarts is reached, the rest of the lot is returned
+			// to the liquidation pool.
+			System::set_block_number(21);
+			assert_ok!(TestRiskManager::restart_auction(bob_origin(), 0));
+			let expected_event = Event::TestRiskManager(crate::Event::AuctionClosed(0));
+			assert!(System::events().iter().any(|record| record.event == expected_event));
+			assert_eq!(TestRiskManager::auctions(0), None);
+			assert_eq!(Currencies::free_balance(DOT, &AuctionAccountId::get()), Balance::zero());
+			assert_eq!(
+				Currencies::free_balance(DOT, &TestLiquidationPools::pools_account_id()),
+				units(DOT, 55)
+			);
+		});
+}
+
+#[test]
+fn auction_payment_should_be_rounded_up() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		let auction = CollateralAuction {
+			collateral_pool_id: DOT,
+			debt_pool_id: BTC,
+			amount: units(DOT, 1),
+			start_price: Price::one(),
+			min_price: Price::one(),
+			start_block: 1,
+			end_block: 11,
+			restarts: 0,
+		};
+		// 1 DOT = 1 BTC
+		assert_eq!(
+			TestRiskManager::get_auction_payment(&auction, units(DOT, 1), Price::one()),
+			Ok(units(BTC, 1))
+		);
+		// 10^-10 DOT costs 10^-10 BTC, which is less than the smallest unit of BTC.
+		assert_eq!(TestRiskManager::get_auction_payment(&auction, 1, Price::one()), Ok(1));
+		// 1.5 * 10^-8 DOT = 150 units costs 1.5 units of BTC.
+		assert_eq!(TestRiskManager::get_auction_payment(&auction, 150, Price::one()), Ok(2));
+		assert_eq!(
+			TestRiskManager::get_auction_payment(&auction, Balance::zero(), Price::one()),
+			Ok(Balance::zero())
+		);
+	});
+}
@@ -0,0 +1,152 @@
+// This file is part of Minterest.
+
+// Copyright (C) 2021 Minterest finance.
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for risk_manager
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2021-07-28, STEPS: [50, ], REPEAT: 20, LOW RANGE: [], HIGH RANGE: []
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=risk_manager
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./pallets/risk-manager/src/weights.rs
+// --template=./templates/weight-template-for-pallet.hbs
+
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+#![allow(clippy::unnecessary_cast)]
+
+use frame_support::{traits::Get, weights::{Weight, constants::RocksDbWeight}};
+use sp_std::marker::PhantomData;
+
+/// Weight functions needed for risk_manager.
+pub trait WeightInfo {
+	fn liquidate() -> Weight;
+	fn cover_bad_debt_from_liquidation_pool() -> Weight;
+	fn cover_bad_debt_from_protocol_interest() -> Weight;
+	fn socialize_bad_debt() -> Weight;
+	fn set_liquidation_fee() -> Weight;
+	fn set_liquidation_threshold() -> Weight;
+	fn set_auction_params() -> Weight;
+	fn bid() -> Weight;
+	fn restart_auction() -> Weight;
+}
+
+/// Weights for risk_manager using the Minterest node and recommended hardware.
+pub struct MinterestWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for MinterestWeight<T> {
+	fn liquidate() -> Weight {
+		(1_418_372_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(42 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+	}
+	fn cover_bad_debt_from_liquidation_pool() -> Weight {
+		(64_327_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn cover_bad_debt_from_protocol_interest() -> Weight {
+		(38_119_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn socialize_bad_debt() -> Weight {
+		(30_861_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_fee() -> Weight {
+		(24_318_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_threshold() -> Weight {
+		(29_471_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_auction_params() -> Weight {
+		(27_204_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(142_716_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn restart_auction() -> Weight {
+		(81_930_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn liquidate() -> Weight {
+		(1_418_372_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(42 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(21 as Weight))
+	}
+	fn cover_bad_debt_from_liquidation_pool() -> Weight {
+		(64_327_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn cover_bad_debt_from_protocol_interest() -> Weight {
+		(38_119_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn socialize_bad_debt() -> Weight {
+		(30_861_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_fee() -> Weight {
+		(24_318_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_threshold() -> Weight {
+		(29_471_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_auction_params() -> Weight {
+		(27_204_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(142_716_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn restart_auction() -> Weight {
+		(81_930_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+}
@@ -0,0 +1,271 @@
+//! # Timelock Module
+//!
+//! ## Overview
+//!
+//! Timelock module delays the changes of the protocol risk parameters, so that integrators and
+//! users are warned before, for example, collateral factors drop. A parameter change is a call
+//! to one of the protocol pallets that is queued together with the block at which it is executed.
+//! The execution block must be at least `MinDelay` blocks in the future. Queued changes are
+//! dispatched with the root origin in `on_initialize` of their execution block. The changes that
+//! do not fit into `MaximumWeight` are postponed to the next block, ahead of the changes queued
+//! for it. If that exceeds `MaxChangesPerBlock`, the newest changes of the block are moved on to
+//! the following blocks. Events are emitted when a change is queued, executed or cancelled.
+//!
+//! The setters of the risk parameters are expected to accept the root origin only, so that all
+//! the regular changes go through the queue. An emergency origin may still lower the risk or
+//! pause the operations without a delay.
+//!
+//! ## Interface
+//!
+//! ### Dispatchable Functions
+//!
+//! - `queue_change` - Queue a parameter change to be executed at the given block.
+//! - `cancel_change` - Cancel a queued parameter change.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use codec::{Decode, Encode};
+use frame_support::{
+	pallet_prelude::*,
+	traits::{Filter, UnfilteredDispatchable},
+	transactional,
+	weights::GetDispatchInfo,
+};
+use frame_system::pallet_prelude::*;
+pub use module::*;
+use sp_runtime::{
+	traits::{One, Saturating},
+	RuntimeDebug,
+};
+use sp_std::{boxed::Box, prelude::Vec};
+pub use weights::WeightInfo;
+
+#[cfg(test)]
+mod mock;
+#[cfg(test)]
+mod tests;
+pub mod weights;
+
+/// Identifier of a queued parameter change.
+pub type ChangeId = u32;
+
+/// A parameter change waiting for its execution block.
+#[derive(Encode, Decode, Clone, PartialEq, Eq, RuntimeDebug)]
+pub struct PendingChange<Call, BlockNumber> {
+	/// The call that changes the parameter. It is dispatched with the root origin.
+	pub call: Call,
+	/// The block at which the change is executed.
+	pub execution_block: BlockNumber,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		/// The overarching event type.
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// The overarching call type.
+		type Call: Parameter + UnfilteredDispatchable<Origin = Self::Origin> + GetDispatchInfo;
+
+		/// The calls that are allowed to be queued.
+		type CallFilter: Filter<<Self as Config>::Call>;
+
+		/// The origin which may queue parameter changes.
+		type QueueOrigin: EnsureOrigin<Self::Origin>;
+
+		/// The origin which may cancel queued parameter changes.
+		type CancelOrigin: EnsureOrigin<Self::Origin>;
+
+		#[pallet::constant]
+		/// The minimum number of blocks between queueing a change and executing it.
+		type MinDelay: Get<Self::BlockNumber>;
+
+		#[pallet::constant]
+		/// The maximum number of changes queued for the same block.
+		type MaxChangesPerBlock: Get<u32>;
+
+		#[pallet::constant]
+		/// The maximum weight that may be used by the queued changes in a block.
+		type MaximumWeight: Get<Weight>;
+
+		/// Weight information for the extrinsics.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The call is not a parameter change that can be queued.
+		CallNotAllowed,
+		/// The execution block is less than `MinDelay` blocks in the future.
+		ExecutionBlockTooEarly,
+		/// The number of changes queued for the execution block has reached `MaxChangesPerBlock`.
+		TooManyChangesInBlock,
+		/// There is no queued change with the given id.
+		ChangeNotFound,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// The parameter change is queued: \[change_id, execution_block\]
+		ChangeQueued(ChangeId, T::BlockNumber),
+		/// The queued parameter change is executed: \[change_id, result\]
+		ChangeExecuted(ChangeId, DispatchResult),
+		/// The queued parameter change is cancelled: \[change_id\]
+		ChangeCancelled(ChangeId),
+	}
+
+	/// The id of the next queued change.
+	#[pallet::storage]
+	#[pallet::getter(fn next_change_id)]
+	pub(crate) type NextChangeId<T: Config> = StorageValue<_, ChangeId, ValueQuery>;
+
+	/// The parameter changes waiting for their execution block.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_changes)]
+	pub type PendingChanges<T: Config> =
+		StorageMap<_, Twox64Concat, ChangeId, PendingChange<<T as Config>::Call, T::BlockNumber>, OptionQuery>;
+
+	/// The ids of the changes to be executed at the block.
+	#[pallet::storage]
+	#[pallet::getter(fn agenda)]
+	pub type Agenda<T: Config> = StorageMap<_, Twox64Concat, T::BlockNumber, Vec<ChangeId>, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(PhantomData<T>);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Executes the changes queued for the current block while their weight fits into
+		/// `MaximumWeight`. The remaining changes are postponed to the following blocks.
+		fn on_initialize(now: T::BlockNumber) -> Weight {
+			let limit = T::MaximumWeight::get();
+			let change_weight = T::WeightInfo::on_initialize(1).saturating_sub(T::WeightInfo::on_initialize(0));
+			let mut weight = T::WeightInfo::on_initialize(0);
+			let mut postponed: Vec<ChangeId> = Vec::new();
+
+			for (order, change_id) in Agenda::<T>::take(now).into_iter().enumerate() {
+				if let Some(change) = PendingChanges::<T>::get(change_id) {
+					let call_weight = change_weight.saturating_add(change.call.get_dispatch_info().weight);
+					// The first change is always executed, so that a single heavy change can not
+					// stall the agenda.
+					if order > 0 && weight.saturating_add(call_weight) > limit {
+						postponed.push(change_id);
+						continue;
+					}
+					weight = weight.saturating_add(call_weight);
+					PendingChanges::<T>::remove(change_id);
+					let result = change
+						.call
+						.dispatch_bypass_filter(frame_system::RawOrigin::Root.into())
+						.map(|_| ())
+						.map_err(|e| e.error);
+					Self::deposit_event(Event::ChangeExecuted(change_id, result));
+				}
+			}
+
+			weight.saturating_add(Self::postpone(now, postponed))
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Queue a parameter change.
+		///
+		/// - `call`: the call that changes the parameter.
+		/// - `execution_block`: the block at which the call is dispatched with the root origin.
+		///
+		/// The dispatch origin of this call must be 'QueueOrigin'.
+		#[pallet::weight(T::WeightInfo::queue_change())]
+		#[transactional]
+		pub fn queue_change(
+			origin: OriginFor<T>,
+			call: Box<<T as Config>::Call>,
+			execution_block: T::BlockNumber,
+		) -> DispatchResultWithPostInfo {
+			T::QueueOrigin::ensure_origin(origin)?;
+			ensure!(T::CallFilter::filter(&call), Error::<T>::CallNotAllowed);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(
+				execution_block > now && execution_block >= now.saturating_add(T::MinDelay::get()),
+				Error::<T>::ExecutionBlockTooEarly
+			);
+
+			let change_id = NextChangeId::<T>::get();
+			Agenda::<T>::try_mutate(execution_block, |change_ids| -> DispatchResult {
+				ensure!(
+					(change_ids.len() as u32) < T::MaxChangesPerBlock::get(),
+					Error::<T>::TooManyChangesInBlock
+				);
+				change_ids.push(change_id);
+				Ok(())
+			})?;
+			PendingChanges::<T>::insert(
+				change_id,
+				PendingChange {
+					call: *call,
+					execution_block,
+				},
+			);
+			NextChangeId::<T>::put(change_id.wrapping_add(1));
+
+			Self::deposit_event(Event::ChangeQueued(change_id, execution_block));
+			Ok(().into())
+		}
+
+		/// Cancel a queued parameter change.
+		///
+		/// - `change_id`: the id of the queued change.
+		///
+		/// The dispatch origin of this call must be 'CancelOrigin'.
+		#[pallet::weight(T::WeightInfo::cancel_change(T::MaxChangesPerBlock::get()))]
+		#[transactional]
+		pub fn cancel_change(origin: OriginFor<T>, change_id: ChangeId) -> DispatchResultWithPostInfo {
+			T::CancelOrigin::ensure_origin(origin)?;
+
+			let change = PendingChanges::<T>::take(change_id).ok_or(Error::<T>::ChangeNotFound)?;
+			Agenda::<T>::mutate(change.execution_block, |change_ids| {
+				change_ids.retain(|&id| id != change_id)
+			});
+
+			Self::deposit_event(Event::ChangeCancelled(change_id));
+			Ok(().into())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Moves the postponed changes to the front of the agenda of the block after `now`, since they
+	/// are older than the changes queued for it. The changes that don't fit into
+	/// `MaxChangesPerBlock` are moved on to the following blocks in the same way, so the changes
+	/// keep their order and no agenda grows over the limit.
+	fn postpone(now: T::BlockNumber, mut postponed: Vec<ChangeId>) -> Weight {
+		let max_changes = (T::MaxChangesPerBlock::get() as usize).max(1);
+		let mut weight: Weight = 0;
+		let mut block = now;
+		while !postponed.is_empty() {
+			block = block.saturating_add(One::one());
+			let moved = postponed.len();
+			let mut change_ids = postponed;
+			change_ids.append(&mut Agenda::<T>::get(block));
+			postponed = change_ids.split_off(max_changes.min(change_ids.len()));
+
+			let moved = moved.min(change_ids.len());
+			for &change_id in change_ids.iter().take(moved) {
+				PendingChanges::<T>::mutate(change_id, |change| {
+					if let Some(change) = change {
+						change.execution_block = block;
+					}
+				});
+			}
+			weight = weight.saturating_add(T::DbWeight::get().reads_writes(moved as Weight + 1, moved as Weight + 1));
+			Agenda::<T>::insert(block, change_ids);
+		}
+		weight
+	}
+}
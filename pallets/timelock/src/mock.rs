@@ -0,0 +1,87 @@
+//! Mocks for the timelock module.
+use super::*;
+use crate as timelock;
+use frame_support::{construct_runtime, ord_parameter_types, parameter_types};
+use frame_system::EnsureSignedBy;
+use minterest_primitives::Balance;
+use sp_core::H256;
+use sp_runtime::{
+	testing::Header,
+	traits::{BlakeTwo256, IdentityLookup},
+};
+pub use test_helper::*;
+
+pub type Block = sp_runtime::generic::Block<Header, UncheckedExtrinsic>;
+pub type UncheckedExtrinsic = sp_runtime::generic::UncheckedExtrinsic<u32, Call, u32, ()>;
+
+// Configure a mock runtime to test the pallet.
+construct_runtime!(
+	pub enum Test where
+		Block = Block,
+		NodeBlock = Block,
+		UncheckedExtrinsic = UncheckedExtrinsic,
+	{
+		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
+		Timelock: timelock::{Pallet, Storage, Call, Event<T>},
+	}
+);
+
+mock_impl_system_config!(Test);
+
+ord_parameter_types! {
+	pub const ZeroAdmin: AccountId = 0;
+}
+
+parameter_types! {
+	pub const MinDelay: u64 = 10;
+	pub const MaxChangesPerBlock: u32 = 2;
+	// Enough for one `set_storage` change, but not for two.
+	pub const MaximumWeight: Weight = 500_000_000;
+}
+
+/// Only `set_storage` calls can be queued in the mock.
+pub struct SetStorageCalls;
+impl Filter<Call> for SetStorageCalls {
+	fn filter(call: &Call) -> bool {
+		matches!(call, Call::System(frame_system::Call::set_storage(..)))
+	}
+}
+
+impl timelock::Config for Test {
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = SetStorageCalls;
+	type QueueOrigin = EnsureSignedBy<ZeroAdmin, AccountId>;
+	type CancelOrigin = EnsureSignedBy<ZeroAdmin, AccountId>;
+	type MinDelay = MinDelay;
+	type MaxChangesPerBlock = MaxChangesPerBlock;
+	type MaximumWeight = MaximumWeight;
+	type WeightInfo = ();
+}
+
+pub const KEY: &[u8] = b"timelock/test";
+
+/// The call that writes `value` under the test storage key.
+pub fn set_storage_call(value: u8) -> Box<Call> {
+	Box::new(Call::System(frame_system::Call::set_storage(vec![(
+		KEY.to_vec(),
+		vec![value],
+	)])))
+}
+
+pub struct ExternalityBuilder;
+
+impl Default for ExternalityBuilder {
+	fn default() -> Self {
+		Self
+	}
+}
+
+impl ExternalityBuilder {
+	pub fn build(self) -> sp_io::TestExternalities {
+		let storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+		let mut ext: sp_io::TestExternalities = storage.into();
+		ext.execute_with(|| System::set_block_number(1));
+		ext
+	}
+}
@@ -0,0 +1,178 @@
+//! Tests for the timelock module.
+
+use super::*;
+use mock::{Event, *};
+
+use frame_support::{assert_noop, assert_ok, error::BadOrigin, storage::unhashed};
+
+#[test]
+fn queue_change_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			Timelock::queue_change(alice_origin(), set_storage_call(1), 11),
+			BadOrigin
+		);
+		assert_noop!(
+			Timelock::queue_change(
+				admin_origin(),
+				Box::new(Call::System(frame_system::Call::remark(vec![]))),
+				11
+			),
+			Error::<Test>::CallNotAllowed
+		);
+		// The execution block must be at least `MinDelay` = 10 blocks in the future.
+		assert_noop!(
+			Timelock::queue_change(admin_origin(), set_storage_call(1), 10),
+			Error::<Test>::ExecutionBlockTooEarly
+		);
+
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(1), 11));
+		let expected_event = Event::Timelock(crate::Event::ChangeQueued(0, 11));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(
+			Timelock::pending_changes(0),
+			Some(PendingChange {
+				call: *set_storage_call(1),
+				execution_block: 11,
+			})
+		);
+		assert_eq!(Timelock::agenda(11), vec![0]);
+		assert_eq!(Timelock::next_change_id(), 1);
+
+		// At most `MaxChangesPerBlock` = 2 changes can be executed in the same block.
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(2), 11));
+		assert_noop!(
+			Timelock::queue_change(admin_origin(), set_storage_call(3), 11),
+			Error::<Test>::TooManyChangesInBlock
+		);
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(3), 12));
+		assert_eq!(Timelock::agenda(11), vec![0, 1]);
+		assert_eq!(Timelock::agenda(12), vec![2]);
+	});
+}
+
+#[test]
+fn queued_changes_should_be_executed_at_execution_block() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(1), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(2), 12));
+
+		Timelock::on_initialize(10);
+		assert_eq!(unhashed::get_raw(KEY), None);
+
+		Timelock::on_initialize(11);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![1]));
+		let expected_event = Event::Timelock(crate::Event::ChangeExecuted(0, Ok(())));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(Timelock::pending_changes(0), None);
+		assert!(Timelock::agenda(11).is_empty());
+		assert!(Timelock::pending_changes(1).is_some());
+
+		Timelock::on_initialize(12);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![2]));
+		assert_eq!(Timelock::pending_changes(1), None);
+	});
+}
+
+#[test]
+fn changes_over_maximum_weight_should_be_postponed() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(1), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(2), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(3), 12));
+
+		// Only one change fits into `MaximumWeight`, the other one is postponed to the next block.
+		assert!(Timelock::on_initialize(11) <= MaximumWeight::get());
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![1]));
+		assert_eq!(Timelock::pending_changes(0), None);
+		assert_eq!(
+			Timelock::pending_changes(1),
+			Some(PendingChange {
+				call: *set_storage_call(2),
+				execution_block: 12,
+			})
+		);
+		assert!(Timelock::agenda(11).is_empty());
+		assert_eq!(Timelock::agenda(12), vec![1, 2]);
+
+		// The postponed change is executed first and can still be cancelled.
+		Timelock::on_initialize(12);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![2]));
+		assert_eq!(Timelock::agenda(13), vec![2]);
+		assert_ok!(Timelock::cancel_change(admin_origin(), 2));
+		assert!(Timelock::agenda(13).is_empty());
+
+		Timelock::on_initialize(13);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![2]));
+	});
+}
+
+#[test]
+fn postponed_changes_should_not_overfill_agenda() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(1), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(2), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(3), 12));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(4), 12));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(5), 13));
+
+		// The postponed change goes first, the newest change of the full block 12 moves on to
+		// block 13.
+		Timelock::on_initialize(11);
+		assert_eq!(Timelock::agenda(12), vec![1, 2]);
+		assert_eq!(Timelock::agenda(13), vec![3, 4]);
+		assert_eq!(
+			Timelock::pending_changes(1).map(|change| change.execution_block),
+			Some(12)
+		);
+		assert_eq!(
+			Timelock::pending_changes(2).map(|change| change.execution_block),
+			Some(12)
+		);
+		assert_eq!(
+			Timelock::pending_changes(3).map(|change| change.execution_block),
+			Some(13)
+		);
+
+		// The moved change can be cancelled at its new execution block.
+		assert_ok!(Timelock::cancel_change(admin_origin(), 3));
+		assert_eq!(Timelock::agenda(13), vec![4]);
+
+		Timelock::on_initialize(12);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![2]));
+		assert_eq!(Timelock::agenda(13), vec![2, 4]);
+	});
+}
+
+#[test]
+fn cancel_change_should_work() {
+	ExternalityBuilder::default().build().execute_with(|| {
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(1), 11));
+		assert_ok!(Timelock::queue_change(admin_origin(), set_storage_call(2), 11));
+
+		assert_noop!(Timelock::cancel_change(alice_origin(), 0), BadOrigin);
+		assert_noop!(
+			Timelock::cancel_change(admin_origin(), 5),
+			Error::<Test>::ChangeNotFound
+		);
+
+		assert_ok!(Timelock::cancel_change(admin_origin(), 0));
+		let expected_event = Event::Timelock(crate::Event::ChangeCancelled(0));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert_eq!(Timelock::pending_changes(0), None);
+		assert_eq!(Timelock::agenda(11), vec![1]);
+		assert_noop!(
+			Timelock::cancel_change(admin_origin(), 0),
+			Error::<Test>::ChangeNotFound
+		);
+
+		// Only the remaining change is executed.
+		Timelock::on_initialize(11);
+		assert_eq!(unhashed::get_raw(KEY), Some(vec![2]));
+		let expected_event = Event::Timelock(crate::Event::ChangeExecuted(1, Ok(())));
+		assert!(System::events().iter().any(|record| record.event == expected_event));
+		assert!(!System::events()
+			.iter()
+			.any(|record| record.event == Event::Timelock(crate::Event::ChangeExecuted(0, Ok(())))));
+	});
+}
@@ -1,7 +1,7 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::upper_case_acronyms)]
 
-use minterest_primitives::{Balance, CurrencyId, Operation, Price, Rate};
+use minterest_primitives::{Balance, CurrencyId, DataProviderId, Operation, Price, Rate};
 use sp_runtime::{DispatchError, DispatchResult};
 use sp_std::{collections::btree_set::BTreeSet, result::Result, vec::Vec};
 
@@ -22,6 +22,15 @@ pub trait Borrowing<AccountId> {
 		repay_amount: Balance,
 		account_borrows: Balance,
 	) -> DispatchResult;
+
+	/// Updates the state of the core as a consequence of writing off an uncovered part of the
+	/// borrow. The written off amount is moved from the pool borrows to the pool bad debt.
+	fn update_state_on_bad_debt(
+		who: &AccountId,
+		underlying_asset: CurrencyId,
+		bad_debt_amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult;
 }
 
 /// An abstraction of pools basic functionalities.
@@ -44,6 +53,9 @@ pub trait LiquidityPoolStorageProvider<AccountId, Pool> {
 	/// Sets the total interest in the pool.
 	fn set_pool_protocol_interest(pool_id: CurrencyId, new_pool_protocol_interest: Balance);
 
+	/// Sets the total bad debt of the pool.
+	fn set_pool_bad_debt(pool_id: CurrencyId, new_pool_bad_debt: Balance);
+
 	/// Gets pool associated data.
 	fn get_pool_data(pool_id: CurrencyId) -> Pool;
 
@@ -60,6 +72,9 @@ pub trait LiquidityPoolStorageProvider<AccountId, Pool> {
 	/// Gets current total amount of protocol interest of the underlying held in this pool.
 	fn get_pool_protocol_interest(pool_id: CurrencyId) -> Balance;
 
+	/// Gets the total amount of written off borrows of the pool that are not covered yet.
+	fn get_pool_bad_debt(pool_id: CurrencyId) -> Balance;
+
 	/// Check if pool exists.
 	fn pool_exists(underlying_asset: &CurrencyId) -> bool;
 
@@ -128,6 +143,13 @@ pub trait PricesManager<CurrencyId> {
 	/// Get price underlying token in USD.
 	fn get_underlying_price(currency_id: CurrencyId) -> Option<Price>;
 
+	/// Get the last known price of underlying token in USD: the current price if there is one,
+	/// otherwise the last price accepted from the source, however old it is. Only meant for the
+	/// checks that must keep working while the price feed is stale, such as the supply cap.
+	fn get_last_underlying_price(currency_id: CurrencyId) -> Option<Price> {
+		Self::get_underlying_price(currency_id)
+	}
+
 	/// Locks price when get valid price from source.
 	fn lock_price(currency_id: CurrencyId);
 
@@ -135,25 +157,84 @@ pub trait PricesManager<CurrencyId> {
 	fn unlock_price(currency_id: CurrencyId);
 }
 
+/// Provides the values fed to the oracle instances of the runtime.
+pub trait OracleDataProvider<Key, TimestampedValue> {
+	/// Returns the latest value of the key from the oracle selected by the data provider id.
+	/// Returns `None` if the data provider is not an oracle.
+	fn get_no_op(provider_id: DataProviderId, key: &Key) -> Option<TimestampedValue>;
+}
+
 /// An abstraction of DEXs basic functionalities.
 pub trait DEXManager<AccountId, CurrencyId, Balance> {
-	//TODO: Add function description
+	/// Calculates the amounts of every currency along the `path` received for the exact
+	/// `supply_amount` of the first currency. Doesn't change the state.
+	fn get_target_amounts(path: &[CurrencyId], supply_amount: Balance) -> Result<Vec<Balance>, DispatchError>;
+
+	/// Calculates the amounts of every currency along the `path` required to receive the exact
+	/// `target_amount` of the last currency. Doesn't change the state.
+	fn get_supply_amounts(path: &[CurrencyId], target_amount: Balance) -> Result<Vec<Balance>, DispatchError>;
+
+	/// Swaps the exact `supply_amount` of the first currency of the `path` for the last one,
+	/// trading through every pair of the path in turn.
+	/// Fails if less than `min_target_amount` would be received.
+	///
+	/// Returns the actual amount of target currency received.
+	fn swap_with_exact_supply_by_path(
+		who: &AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Swaps the first currency of the `path` for the exact `target_amount` of the last one,
+	/// trading through every pair of the path in turn.
+	/// Fails if more than `max_supply_amount` would be spent.
+	///
+	/// Returns the actual amount of supply currency spent.
+	fn swap_with_exact_target_by_path(
+		who: &AccountId,
+		path: &[CurrencyId],
+		max_supply_amount: Balance,
+		target_amount: Balance,
+	) -> Result<Balance, DispatchError>;
+
+	/// Swaps the exact `supply_amount` of supply currency for target currency.
+	/// Fails if less than `min_target_amount` would be received.
+	///
+	/// Returns the actual amount of target currency received.
 	fn swap_with_exact_supply(
 		who: &AccountId,
 		target_currency_id: CurrencyId,
 		supply_currency_id: CurrencyId,
 		supply_amount: Balance,
 		min_target_amount: Balance,
-	) -> Result<Balance, DispatchError>;
-
-	//TODO: Add function description
+	) -> Result<Balance, DispatchError> {
+		Self::swap_with_exact_supply_by_path(
+			who,
+			&[supply_currency_id, target_currency_id],
+			supply_amount,
+			min_target_amount,
+		)
+	}
+
+	/// Swaps supply currency for the exact `target_amount` of target currency.
+	/// Fails if more than `max_supply_amount` would be spent.
+	///
+	/// Returns the actual amount of supply currency spent.
 	fn swap_with_exact_target(
 		who: &AccountId,
 		supply_currency_id: CurrencyId,
 		target_currency_id: CurrencyId,
 		max_supply_amount: Balance,
 		target_amount: Balance,
-	) -> Result<Balance, DispatchError>;
+	) -> Result<Balance, DispatchError> {
+		Self::swap_with_exact_target_by_path(
+			who,
+			&[supply_currency_id, target_currency_id],
+			max_supply_amount,
+			target_amount,
+		)
+	}
 }
 
 /// An abstraction of controller basic functionalities.
@@ -188,14 +269,71 @@ pub trait ControllerManager<AccountId> {
 	/// Checks if a specific operation is allowed on a pool.
 	fn is_operation_allowed(pool_id: CurrencyId, operation: Operation) -> bool;
 
+	/// Pauses a specific operation on a pool.
+	fn pause_operation(pool_id: CurrencyId, operation: Operation);
+
+	/// Resumes a specific operation on a pool.
+	fn resume_operation(pool_id: CurrencyId, operation: Operation);
+
 	/// Checks if the account should be allowed to redeem tokens in the given pool.
 	fn redeem_allowed(underlying_asset_id: CurrencyId, redeemer: &AccountId, redeem_amount: Balance) -> DispatchResult;
 
+	/// Checks if the underlying asset can be deposited into the given pool.
+	fn deposit_allowed(underlying_asset_id: CurrencyId, deposit_amount: Balance) -> DispatchResult;
+
+	/// Checks if the account should be allowed to enable the pool as collateral.
+	fn collateral_allowed(pool_id: CurrencyId, who: &AccountId) -> DispatchResult;
+
+	/// Records the borrow in the total debt of the isolated collateral of the borrower, if any.
+	fn increase_isolation_mode_debt(
+		who: &AccountId,
+		underlying_asset_id: CurrencyId,
+		amount: Balance,
+	) -> DispatchResult;
+
+	/// Removes the repaid or written off share of the loan from the total debt of the isolated
+	/// collateral it was recorded against, if any. Doesn't depend on the current prices.
+	/// - `amount`: the repaid or written off amount of the underlying asset.
+	/// - `account_borrows`: the borrow balance of the account before the repay.
+	fn decrease_isolation_mode_debt(
+		who: &AccountId,
+		underlying_asset_id: CurrencyId,
+		amount: Balance,
+		account_borrows: Balance,
+	) -> DispatchResult;
+
 	/// Checks if the account should be allowed to borrow the underlying asset of the given pool.
 	fn borrow_allowed(underlying_asset_id: CurrencyId, who: &AccountId, borrow_amount: Balance) -> DispatchResult;
 
+	/// Switches the account to the e-mode category or out of e-mode, if `None` is passed.
+	fn set_user_emode(who: &AccountId, category_id: Option<u8>) -> DispatchResult;
+
+	/// Checks if the liquidator should be allowed to liquidate the borrower's loan.
+	fn liquidate_borrow_allowed(
+		repay_pool_id: CurrencyId,
+		collateral_pool_id: CurrencyId,
+		borrower: &AccountId,
+		repay_amount: Balance,
+	) -> DispatchResult;
+
+	/// Calculates the number of wrapped tokens of the collateral pool to be seized,
+	/// given an amount of underlying assets repaid in the borrowed pool.
+	fn liquidate_calculate_seize_tokens(
+		repay_pool_id: CurrencyId,
+		collateral_pool_id: CurrencyId,
+		repay_amount: Balance,
+		liquidation_fee: Rate,
+	) -> Result<Balance, DispatchError>;
+
 	/// Return minimum protocol interest needed to transfer it to liquidation pool
 	fn get_protocol_interest_threshold(pool_id: CurrencyId) -> Balance;
+
+	/// Return collateral factor of the pool
+	fn get_collateral_factor(pool_id: CurrencyId) -> Rate;
+
+	/// Return liquidation threshold of the e-mode category of the account for the pool, if the
+	/// account opted in to the category of the pool
+	fn get_user_emode_liquidation_threshold(who: &AccountId, pool_id: CurrencyId) -> Option<Rate>;
 }
 
 pub trait MntManager<AccountId> {
@@ -241,6 +379,38 @@ pub trait MntManager<AccountId> {
 	fn get_pool_mnt_borrow_and_supply_rates(pool_id: CurrencyId) -> Result<(Price, Price), DispatchError>;
 }
 
+/// An abstraction of minterest-protocol basic functionalities used during liquidation.
+pub trait MinterestProtocolManager<AccountId> {
+	/// Borrows are repaid by another user (possibly the borrower).
+	///
+	/// - `who`: the account paying off the borrow.
+	/// - `borrower`: the account with the debt being payed off.
+	/// - `underlying_asset`: the currency ID of the underlying asset to repay.
+	/// - `repay_amount`: the amount of the underlying asset to repay.
+	/// - `all_assets`: repay the whole borrow of the `borrower`.
+	///
+	/// Returns the amount of the underlying asset that was repaid.
+	/// Note: this function should be used after `accrue_interest_rate`.
+	fn do_repay_fresh(
+		who: &AccountId,
+		borrower: &AccountId,
+		underlying_asset: CurrencyId,
+		repay_amount: Balance,
+		all_assets: bool,
+	) -> Result<Balance, DispatchError>;
+
+	/// Seizes the collateral of the borrower in favor of the liquidation pools.
+	/// Burns the borrower's wrapped tokens equivalent to `seize_underlying` and transfers
+	/// underlying assets from the liquidity pools to the liquidation pools.
+	///
+	/// - `borrower`: the account whose collateral is seized.
+	/// - `underlying_asset`: the currency ID of the collateral pool.
+	/// - `seize_underlying`: the amount of the underlying asset to seize.
+	///
+	/// Note: this function should be used after `accrue_interest_rate`.
+	fn do_seize(borrower: &AccountId, underlying_asset: CurrencyId, seize_underlying: Balance) -> DispatchResult;
+}
+
 /// An abstraction of minterest-model basic functionalities.
 pub trait MinterestModelManager {
 	/// This is a part of a pool creation flow
@@ -300,13 +470,18 @@ pub trait CurrencyConverter {
 	fn underlying_to_wrapped(underlying_amount: Balance, exchange_rate: Rate) -> Result<Balance, DispatchError>;
 
 	/// Converts a specified number of underlying assets into USD.
-	/// The calculation is based on the current oracle price.
+	/// The calculation is based on the current oracle price and the decimals of the asset.
 	///
+	/// - `pool_id`: the underlying asset to be converted into USD.
 	/// - `underlying_amount`: the amount of underlying assets to be converted into USD.
 	/// - `oracle_price`: market value of the underlying asset in USD.
 	///
-	/// Returns `underlying_amount * oracle_price`
-	fn underlying_to_usd(underlying_amount: Balance, oracle_price: Price) -> Result<Balance, DispatchError>;
+	/// Returns `underlying_amount * oracle_price` with `USD_DECIMALS` decimals.
+	fn underlying_to_usd(
+		pool_id: CurrencyId,
+		underlying_amount: Balance,
+		oracle_price: Price,
+	) -> Result<Balance, DispatchError>;
 
 	/// Converts a specified number of wrapped tokens into underlying assets.
 	/// The calculation is based on the exchange rate.
@@ -318,38 +493,53 @@ pub trait CurrencyConverter {
 	fn wrapped_to_underlying(wrapped_amount: Balance, exchange_rate: Rate) -> Result<Balance, DispatchError>;
 
 	/// Converts a specified number of wrapped tokens into USD.
-	/// The calculation is based on the exchange rate and the oracle price.
+	/// The calculation is based on the exchange rate, the oracle price and the decimals of the
+	/// underlying asset.
 	///
+	/// - `pool_id`: the underlying asset of the wrapped tokens.
 	/// - `wrapped_amount`: the amount of wrapped tokens to be converted to USD.
 	/// - `exchange_rate`: exchange rate between a wrapped tokens and the underlying assets.
 	/// - `oracle_price`: market value of the underlying asset in USD.
 	///
-	/// Returns `wrapped_amount * exchange_rate * oracle_price`
+	/// Returns `wrapped_amount * exchange_rate * oracle_price` with `USD_DECIMALS` decimals.
 	/// Note: first call `accrue_interest` if you want to exchange at a fresh exchange rate.
 	fn wrapped_to_usd(
+		pool_id: CurrencyId,
 		wrapped_amount: Balance,
 		exchange_rate: Rate,
 		oracle_price: Price,
 	) -> Result<Balance, DispatchError>;
 
 	/// Converts a specified number of USD into underlying assets.
-	/// The calculation is based on the current oracle price.
+	/// The calculation is based on the current oracle price and the decimals of the asset.
 	///
+	/// - `pool_id`: the underlying asset to be received.
 	/// - `usd_amount`: the amount of USD to be converted to underlying assets.
 	/// - `oracle_price`: market value of the underlying asset in USD.
 	///
-	/// Returns `usd_amount / oracle_price`
-	fn usd_to_underlying(usd_amount: Balance, oracle_price: Price) -> Result<Balance, DispatchError>;
+	/// Returns `usd_amount / oracle_price` with the decimals of the underlying asset.
+	fn usd_to_underlying(
+		pool_id: CurrencyId,
+		usd_amount: Balance,
+		oracle_price: Price,
+	) -> Result<Balance, DispatchError>;
 
 	/// Converts a specified amount of USD into wrapped tokens.
-	/// The calculation is based on the exchange rate and the oracle price.
+	/// The calculation is based on the exchange rate, the oracle price and the decimals of the
+	/// underlying asset.
 	///
+	/// - `pool_id`: the underlying asset of the wrapped tokens.
 	/// - `usd_amount`: the amount of USD to be converted into wrapped tokens.
 	/// - `exchange_rate`: exchange rate between a wrapped tokens and the underlying assets.
 	/// - `oracle_price`: market value of the underlying asset in USD.
 	///
 	/// Returns `usd_amount / oracle_price / exchange_rate `
-	fn usd_to_wrapped(usd_amount: Balance, exchange_rate: Rate, oracle_price: Price) -> Result<Balance, DispatchError>;
+	fn usd_to_wrapped(
+		pool_id: CurrencyId,
+		usd_amount: Balance,
+		exchange_rate: Rate,
+		oracle_price: Price,
+	) -> Result<Balance, DispatchError>;
 }
 
 /// Provides functionality to manage the number of attempts to partially liquidation a user's loan.
@@ -359,7 +549,6 @@ pub trait UserLiquidationAttemptsManager<AccountId> {
 
 	/// Mutates user liquidation attempts depending on user operation.
 	/// If the user makes a deposit to the collateral pool, then attempts are set to zero.
-	/// TODO: a liquidation handler will be added in the future
 	fn mutate_depending_operation(pool_id: CurrencyId, who: &AccountId, operation: Operation);
 }
 
@@ -372,4 +561,7 @@ pub trait RiskManagerStorageProvider {
 	/// Removes parameter values `liquidation_fee` and `liquidation_threshold` in the
 	/// risk-manager pallet.
 	fn remove_pool(pool_id: CurrencyId);
+
+	/// Return the liquidation fee for the pool.
+	fn get_liquidation_fee(pool_id: CurrencyId) -> Rate;
 }
@@ -1,5 +1,8 @@
 use crate::*;
-use sp_runtime::{traits::CheckedMul, DispatchError, FixedPointNumber};
+use sp_runtime::{
+	traits::{CheckedMul, One},
+	DispatchError, FixedPointNumber,
+};
 
 /// Performs mathematical calculations.
 ///
@@ -17,10 +20,33 @@ pub fn sum_with_mult_result(
 	Ok(value)
 }
 
+/// Raises a fixed-point number to the power of an integer using exponentiation by squaring.
+/// Takes `O(log(exp))` multiplications, so the rounding error doesn't grow with the exponent
+/// as it does when multiplying `exp` times.
+///
+/// returns `value = base ^ exp` or `None` in case of overflow.
+pub fn checked_pow(base: Rate, exp: usize) -> Option<Rate> {
+	let mut result = Rate::one();
+	let mut base = base;
+	let mut exp = exp;
+	while exp > 0 {
+		if exp & 1 == 1 {
+			result = result.checked_mul(&base)?;
+		}
+		exp >>= 1;
+		if exp > 0 {
+			base = base.checked_mul(&base)?;
+		}
+	}
+	Some(result)
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
+	use crate::constants::time::BLOCKS_PER_YEAR;
 	use frame_support::assert_err;
+	use sp_core::U256;
 
 	#[test]
 	fn sum_with_mult_result_should_work() {
@@ -47,4 +73,134 @@ mod tests {
 			DispatchError::Other("Overflow Error")
 		);
 	}
+
+	#[test]
+	fn checked_pow_should_work() {
+		assert_eq!(checked_pow(Rate::saturating_from_rational(3, 2), 0), Some(Rate::one()));
+		assert_eq!(
+			checked_pow(Rate::saturating_from_rational(3, 2), 1),
+			Some(Rate::saturating_from_rational(3, 2))
+		);
+		// 1.5 ^ 5 = 7.59375
+		assert_eq!(
+			checked_pow(Rate::saturating_from_rational(3, 2), 5),
+			Some(Rate::saturating_from_rational(759_375, 100_000))
+		);
+		assert_eq!(checked_pow(Rate::from_inner(0), 10), Some(Rate::from_inner(0)));
+		assert_eq!(checked_pow(Rate::one(), usize::MAX), Some(Rate::one()));
+
+		// Overflow in calculation: 2 ^ 128
+		assert_eq!(checked_pow(Rate::saturating_from_integer(2), 128), None);
+	}
+
+	/// Reference implementation: multiplies `exp` times, as if the interest was compounded on
+	/// every block.
+	fn per_block_pow(base: Rate, exp: usize) -> Rate {
+		(0..exp).fold(Rate::one(), |acc, _| acc.checked_mul(&base).unwrap())
+	}
+
+	#[test]
+	fn checked_pow_should_match_per_block_compounding() {
+		// Deterministic pseudo-random generator (xorshift), so that the failures are reproducible.
+		let mut seed = 0x2545_f491_4f6c_dd1d_u64;
+		let mut next = || {
+			seed ^= seed << 13;
+			seed ^= seed >> 7;
+			seed ^= seed << 17;
+			seed
+		};
+
+		for _ in 0..200 {
+			// Rate per block in range [0, 0.00001), block delta in range [0, 5_000).
+			let rate_per_block = Rate::from_inner(next() as u128 % 10_000_000_000_000);
+			let block_delta = (next() % 5_000) as usize;
+			let base = Rate::one() + rate_per_block;
+
+			let expected = per_block_pow(base, block_delta).into_inner();
+			let actual = checked_pow(base, block_delta).unwrap().into_inner();
+
+			// Both implementations round down on each multiplication, the reference one
+			// accumulates at most one unit of rounding error per block.
+			let tolerance = block_delta as u128 * 2 + 1;
+			assert!(
+				actual.max(expected) - actual.min(expected) <= tolerance,
+				"rate_per_block: {:?}, block_delta: {}, expected: {}, actual: {}",
+				rate_per_block,
+				block_delta,
+				expected,
+				actual
+			);
+		}
+	}
+
+	/// High-precision reference: exponentiation by squaring with 36 decimals instead of 18, so
+	/// that its own rounding error is negligible. Returns the result with 18 decimals.
+	/// Doesn't overflow as long as `base ^ exp` stays below ~300.
+	fn precise_pow(base: Rate, exp: usize) -> u128 {
+		let precision = U256::exp10(36);
+		let mut result = precision;
+		let mut base = U256::from(base.into_inner()) * U256::exp10(18);
+		let mut exp = exp;
+		while exp > 0 {
+			if exp & 1 == 1 {
+				result = result * base / precision;
+			}
+			exp >>= 1;
+			if exp > 0 {
+				base = base * base / precision;
+			}
+		}
+		(result / U256::exp10(18)).as_u128()
+	}
+
+	/// Checks that `actual` differs from `expected` by at most `2 * exp + 64` units of the 18th
+	/// decimal per started unit of the value, i.e. that the relative error is at most
+	/// `(2 * exp + 64) * 10^-18`. Each squaring in `checked_pow` at most doubles the relative error
+	/// and adds one unit of rounding, so after `log2(exp)` squarings the error is below `exp` units.
+	fn assert_pow_precision(rate_per_block: Rate, exp: usize, expected: u128, actual: u128) {
+		let tolerance = (expected / Rate::accuracy() + 1) * (2 * exp as u128 + 64);
+		assert!(
+			actual.max(expected) - actual.min(expected) <= tolerance,
+			"rate_per_block: {:?}, block_delta: {}, expected: {}, actual: {}, tolerance: {}",
+			rate_per_block,
+			exp,
+			expected,
+			actual,
+			tolerance
+		);
+	}
+
+	#[test]
+	fn checked_pow_should_match_per_block_compounding_over_a_year() {
+		let blocks_per_year = BLOCKS_PER_YEAR as usize;
+
+		// 10% APR compounded on every block for a year: (1 + 0.1 / 5_256_000) ^ 5_256_000 ~ e^0.1.
+		let rate_per_block = Rate::saturating_from_rational(1, 10 * BLOCKS_PER_YEAR);
+		let base = Rate::one() + rate_per_block;
+		let expected = per_block_pow(base, blocks_per_year).into_inner();
+		let actual = checked_pow(base, blocks_per_year).unwrap().into_inner();
+		assert_pow_precision(rate_per_block, blocks_per_year, expected, actual);
+		assert_eq!(actual / 10_u128.pow(12), 1_105_170);
+
+		// Deterministic pseudo-random generator (xorshift), so that the failures are reproducible.
+		let mut seed = 0x9e37_79b9_7f4a_7c15_u64;
+		let mut next = || {
+			seed ^= seed << 13;
+			seed ^= seed >> 7;
+			seed ^= seed << 17;
+			seed
+		};
+
+		for _ in 0..200 {
+			// APR in range [0, 300%), block delta in range [0, BLOCKS_PER_YEAR).
+			let apr = Rate::from_inner(next() as u128 % (3 * Rate::accuracy()));
+			let rate_per_block = Rate::from_inner(apr.into_inner() / BLOCKS_PER_YEAR);
+			let block_delta = (next() % BLOCKS_PER_YEAR as u64) as usize;
+			let base = Rate::one() + rate_per_block;
+
+			let expected = precise_pow(base, block_delta);
+			let actual = checked_pow(base, block_delta).unwrap().into_inner();
+			assert_pow_precision(rate_per_block, block_delta, expected, actual);
+		}
+	}
 }
@@ -25,6 +25,9 @@ pub mod currency {
 	pub const DOLLARS: Balance = 1_000_000_000_000_000_000;
 	pub const CENTS: Balance = DOLLARS / 100;
 	pub const MILLICENTS: Balance = CENTS / 1000;
+
+	/// The number of decimals of the amounts in USD.
+	pub const USD_DECIMALS: u32 = 18;
 }
 
 pub mod liquidation {
@@ -41,6 +44,28 @@ pub mod liquidation {
 
 	/// Maximum liquidation fee = 0.5 (50%)
 	pub const MAX_LIQUIDATION_FEE: Rate = Rate::from_inner(500_000_000_000_000_000);
+
+	/// The maximum portion of a borrow that a liquidator can repay in a single liquidation = 0.5 (50%)
+	pub const CLOSE_FACTOR: Rate = Rate::from_inner(500_000_000_000_000_000);
+
+	/// The maximum price deviation from the oracle price accepted by the DEX when balancing
+	/// liquidation pools = 0.05 (5%)
+	pub const MAX_BALANCING_SLIPPAGE: Rate = Rate::from_inner(50_000_000_000_000_000);
+}
+
+pub mod dex {
+	use crate::Rate;
+
+	/// The fee charged by the DEX on the supply amount of every swap = 0.003 (0.3%)
+	pub const SWAP_FEE: Rate = Rate::from_inner(3_000_000_000_000_000);
+}
+
+pub mod oracle {
+	use crate::Rate;
+
+	/// The maximum relative difference between the two middle oracle prices that are averaged
+	/// into the aggregated price = 0.05 (5%)
+	pub const MAX_ORACLE_DEVIATION: Rate = Rate::from_inner(50_000_000_000_000_000);
 }
 
 pub mod fee {
@@ -69,6 +94,7 @@ pub mod fee {
 }
 
 pub const MAX_BORROW_CAP: Balance = 1_000_000_000_000_000_000_000_000;
+pub const MAX_SUPPLY_CAP: Balance = 1_000_000_000_000_000_000_000_000;
 pub const PROTOCOL_INTEREST_TRANSFER_THRESHOLD: Balance = 1_000_000_000_000_000_000_000;
 
 /// Initial exchange rate: 100%
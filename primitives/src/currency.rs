@@ -1,7 +1,9 @@
+use crate::{constants::currency::USD_DECIMALS, Balance, Price};
 use codec::{Decode, Encode};
 #[cfg(feature = "std")]
 use serde::{Deserialize, Serialize};
-use sp_runtime::RuntimeDebug;
+use sp_runtime::{FixedPointNumber, RuntimeDebug};
+use sp_std::cmp::Ordering;
 use sp_std::convert::TryFrom;
 use sp_std::{prelude::Vec, vec};
 #[allow(unused_imports)]
@@ -28,12 +30,33 @@ macro_rules! create_currency_id {
             }
         }
 
+		impl TokenSymbol {
+			/// The ticker of the token, as it is quoted by the exchanges.
+			pub fn symbol(&self) -> &'static str {
+				match self {
+					$(TokenSymbol::$symbol => stringify!($symbol),)*
+				}
+			}
+		}
+
 		impl GetDecimals for CurrencyId {
 			fn decimals(&self) -> u32 {
 				match self {
 					$(CurrencyId::Native(TokenSymbol::$symbol) => $deci,)*
 					$(CurrencyId::UnderlyingAsset(TokenSymbol::$symbol) => $deci,)*
 					$(CurrencyId::WrappedToken(TokenSymbol::$symbol) => $deci,)*
+					// Initial shares of a liquidity pool are issued 1:1 with the first currency.
+					CurrencyId::DexShare(symbol_0, _) => match symbol_0 {
+						$(TokenSymbol::$symbol => $deci,)*
+					},
+				}
+			}
+		}
+
+		impl From<TokenSymbol> for CurrencyId {
+			fn from(symbol: TokenSymbol) -> Self {
+				match symbol {
+					$(TokenSymbol::$symbol => $symbol,)*
 				}
 			}
 		}
@@ -81,6 +104,8 @@ create_currency_id! {
 		MBTC("Bitcoin", 8, WrappedToken) = 6,
 		ETH("Ethereum", 18, UnderlyingAsset) = 7,
 		METH("Ethereum", 18, WrappedToken) = 8,
+		USDT("Tether USD", 6, UnderlyingAsset) = 9,
+		MUSDT("Tether USD", 6, WrappedToken) = 10,
 	}
 }
 
@@ -91,6 +116,8 @@ pub enum CurrencyId {
 	Native(TokenSymbol),
 	UnderlyingAsset(TokenSymbol),
 	WrappedToken(TokenSymbol),
+	/// Liquidity pool share token of the DEX trading pair, symbols are sorted.
+	DexShare(TokenSymbol, TokenSymbol),
 }
 
 impl CurrencyId {
@@ -98,6 +125,8 @@ impl CurrencyId {
 		matches!(self, CurrencyId::Native(_))
 	}
 
+	/// DEX shares are not underlying assets: they have no oracle price and can't be lent or
+	/// used as collateral.
 	pub fn is_supported_underlying_asset(&self) -> bool {
 		matches!(self, CurrencyId::UnderlyingAsset(_))
 	}
@@ -106,6 +135,34 @@ impl CurrencyId {
 		matches!(self, CurrencyId::WrappedToken(_))
 	}
 
+	pub fn is_dex_share_currency_id(&self) -> bool {
+		matches!(self, CurrencyId::DexShare(_, _))
+	}
+
+	/// Returns the share token of the trading pair. Only the native currency and underlying
+	/// assets can be traded on the DEX.
+	pub fn join_dex_share_currency_id(currency_id_0: Self, currency_id_1: Self) -> Option<Self> {
+		let tradable_symbol = |currency_id: Self| match currency_id {
+			CurrencyId::Native(symbol) | CurrencyId::UnderlyingAsset(symbol) => Some(symbol),
+			_ => None,
+		};
+		let symbol_0 = tradable_symbol(currency_id_0)?;
+		let symbol_1 = tradable_symbol(currency_id_1)?;
+		match currency_id_0.cmp(&currency_id_1) {
+			Ordering::Less => Some(CurrencyId::DexShare(symbol_0, symbol_1)),
+			Ordering::Greater => Some(CurrencyId::DexShare(symbol_1, symbol_0)),
+			Ordering::Equal => None,
+		}
+	}
+
+	/// Returns the sorted trading pair of the share token.
+	pub fn split_dex_share_currency_id(&self) -> Option<(Self, Self)> {
+		match self {
+			CurrencyId::DexShare(symbol_0, symbol_1) => Some(((*symbol_0).into(), (*symbol_1).into())),
+			_ => None,
+		}
+	}
+
 	pub fn underlying_asset(&self) -> Option<CurrencyId> {
 		match (self.is_supported_wrapped_asset(), self) {
 			(true, CurrencyId::WrappedToken(currency_id)) => Some(CurrencyId::UnderlyingAsset(
@@ -115,6 +172,44 @@ impl CurrencyId {
 		}
 	}
 
+	/// Converts the amount of the currency to the `USD_DECIMALS` decimals of the USD amounts,
+	/// so that the amounts of currencies with different decimals can be compared.
+	pub fn normalize_amount(&self, amount: Balance) -> Option<Balance> {
+		let decimals = self.decimals();
+		if decimals <= USD_DECIMALS {
+			amount.checked_mul(10_u128.checked_pow(USD_DECIMALS - decimals)?)
+		} else {
+			amount.checked_div(10_u128.checked_pow(decimals - USD_DECIMALS)?)
+		}
+	}
+
+	/// Converts the amount with `USD_DECIMALS` decimals back to the decimals of the currency.
+	pub fn denormalize_amount(&self, amount: Balance) -> Option<Balance> {
+		let decimals = self.decimals();
+		if decimals <= USD_DECIMALS {
+			amount.checked_div(10_u128.checked_pow(USD_DECIMALS - decimals)?)
+		} else {
+			amount.checked_mul(10_u128.checked_pow(decimals - USD_DECIMALS)?)
+		}
+	}
+
+	/// Converts the price of one token of the currency to the price of its smallest unit
+	/// with `USD_DECIMALS` decimals: `normalized_price * amount` is the USD value of the amount.
+	pub fn normalize_price(&self, price: Price) -> Option<Price> {
+		let decimals = self.decimals();
+		if decimals <= USD_DECIMALS {
+			price
+				.into_inner()
+				.checked_mul(10_u128.checked_pow(USD_DECIMALS - decimals)?)
+				.map(Price::from_inner)
+		} else {
+			price
+				.into_inner()
+				.checked_div(10_u128.checked_pow(decimals - USD_DECIMALS)?)
+				.map(Price::from_inner)
+		}
+	}
+
 	pub fn wrapped_asset(&self) -> Option<CurrencyId> {
 		match (self.is_supported_underlying_asset(), self) {
 			(true, CurrencyId::UnderlyingAsset(currency_id)) => Some(CurrencyId::WrappedToken(
@@ -155,6 +250,69 @@ mod tests {
 	fn get_decimal_should_work() {
 		assert_eq!(MNT.decimals(), 18);
 		assert_eq!(DOT.decimals(), 10);
+		assert_eq!(MUSDT.decimals(), 6);
+		assert_eq!(CurrencyId::DexShare(TokenSymbol::BTC, TokenSymbol::ETH).decimals(), 8);
+	}
+
+	#[test]
+	fn normalize_amount_should_work() {
+		// 1 BTC = 10^8, 1 DOT = 10^10, 1 ETH = 10^18
+		assert_eq!(BTC.normalize_amount(100_000_000), Some(1_000_000_000_000_000_000));
+		assert_eq!(DOT.normalize_amount(10_000_000_000), Some(1_000_000_000_000_000_000));
+		assert_eq!(
+			ETH.normalize_amount(1_000_000_000_000_000_000),
+			Some(1_000_000_000_000_000_000)
+		);
+		assert_eq!(BTC.normalize_amount(Balance::MAX), None);
+
+		assert_eq!(BTC.denormalize_amount(1_000_000_000_000_000_000), Some(100_000_000));
+		assert_eq!(
+			ETH.denormalize_amount(1_000_000_000_000_000_000),
+			Some(1_000_000_000_000_000_000)
+		);
+		// The remainder below the smallest unit of the currency is discarded.
+		assert_eq!(BTC.denormalize_amount(1_999_999_999), Some(1));
+
+		// 10^8 (1 BTC) * 4 * 10^14 = 40_000$ with 18 decimals
+		assert_eq!(
+			BTC.normalize_price(Price::saturating_from_integer(40_000)),
+			Some(Price::from_inner(400_000_000_000_000_000_000_000_000_000_000))
+		);
+		assert_eq!(ETH.normalize_price(Price::one()), Some(Price::one()));
+	}
+
+	#[test]
+	fn dex_share_currency_id_should_work() {
+		let dot_eth = CurrencyId::DexShare(TokenSymbol::DOT, TokenSymbol::ETH);
+		assert_eq!(CurrencyId::join_dex_share_currency_id(DOT, ETH), Some(dot_eth));
+		assert_eq!(CurrencyId::join_dex_share_currency_id(ETH, DOT), Some(dot_eth));
+		assert_eq!(
+			CurrencyId::join_dex_share_currency_id(ETH, MNT),
+			Some(CurrencyId::DexShare(TokenSymbol::MNT, TokenSymbol::ETH))
+		);
+		assert_eq!(CurrencyId::join_dex_share_currency_id(DOT, DOT), None);
+		assert_eq!(CurrencyId::join_dex_share_currency_id(DOT, MDOT), None);
+		assert_eq!(CurrencyId::join_dex_share_currency_id(DOT, dot_eth), None);
+
+		assert_eq!(dot_eth.split_dex_share_currency_id(), Some((DOT, ETH)));
+		assert_eq!(DOT.split_dex_share_currency_id(), None);
+
+		assert!(dot_eth.is_dex_share_currency_id());
+		assert!(!dot_eth.is_supported_underlying_asset());
+		assert!(!dot_eth.is_supported_wrapped_asset());
+		assert!(!dot_eth.is_native_currency_id());
+		assert_eq!(dot_eth.wrapped_asset(), None);
+	}
+
+	#[test]
+	fn dex_share_currency_id_encoding_should_work() {
+		let dot_eth = CurrencyId::DexShare(TokenSymbol::DOT, TokenSymbol::ETH);
+		assert_eq!(dot_eth.encode(), vec![3, 1, 7]);
+		assert_eq!(CurrencyId::decode(&mut &[3, 1, 7][..]), Ok(dot_eth));
+		assert_eq!(
+			serde_json::to_string(&dot_eth).unwrap(),
+			r#"{"dexShare":["DOT","ETH"]}"#
+		);
 	}
 
 	#[test]
@@ -176,11 +334,11 @@ mod tests {
 		assert_eq!(CurrencyId::get_enabled_tokens_in_protocol(Native), vec![MNT]);
 		assert_eq!(
 			CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset),
-			vec![DOT, KSM, BTC, ETH]
+			vec![DOT, KSM, BTC, ETH, USDT]
 		);
 		assert_eq!(
 			CurrencyId::get_enabled_tokens_in_protocol(WrappedToken),
-			vec![MDOT, MKSM, MBTC, METH]
+			vec![MDOT, MKSM, MBTC, METH, MUSDT]
 		);
 	}
 }
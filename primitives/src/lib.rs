@@ -87,8 +87,14 @@ pub enum Operation {
 #[derive(Encode, Decode, Eq, PartialEq, Copy, Clone, RuntimeDebug, PartialOrd, Ord)]
 #[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
 pub enum DataProviderId {
+	/// The median of the prices of all oracle instances.
 	Aggregated = 0,
+	/// The oracle instance run by the Minterest operators.
 	Minterest = 1,
+	/// The time-weighted average prices of the dex.
+	Dex = 2,
+	/// The oracle instance run by the external operators.
+	External = 3,
 }
 
 /// Error which may occur while executing the off-chain code.
@@ -98,6 +104,9 @@ pub enum OffchainErr {
 	NotValidator,
 	CheckFail,
 	PoolsBalancingError,
+	NoSigningKey,
+	PriceFetchError,
+	SubmitTransaction,
 }
 
 impl sp_std::fmt::Debug for OffchainErr {
@@ -107,6 +116,9 @@ impl sp_std::fmt::Debug for OffchainErr {
 			OffchainErr::NotValidator => write!(fmt, "Not validator"),
 			OffchainErr::CheckFail => write!(fmt, "Check fail"),
 			OffchainErr::PoolsBalancingError => write!(fmt, "Pools balancing error"),
+			OffchainErr::NoSigningKey => write!(fmt, "No signing key in the keystore"),
+			OffchainErr::PriceFetchError => write!(fmt, "Failed to fetch prices"),
+			OffchainErr::SubmitTransaction => write!(fmt, "Failed to submit transaction"),
 		}
 	}
 }
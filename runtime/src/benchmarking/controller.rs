@@ -1,6 +1,14 @@
-use crate::{Balance, Operation, Rate, Runtime, DOT};
+use super::utils::{set_balance, SEED};
+use crate::{
+	AccountId, Balance, Controller, Currencies, EnabledUnderlyingAssetsIds, KeeperReward, KeeperRewardMinAccrualAge,
+	MntTokenAccountId, Operation, Rate, Runtime, System, DOLLARS, DOT, MNT,
+};
+use controller::{EModeCategory, InterestAccrualMode, UsageParams};
+use frame_benchmarking::account;
+use frame_support::traits::Hooks;
 use frame_system::RawOrigin;
 use orml_benchmarking::{runtime_benchmarks, Zero};
+use orml_traits::MultiCurrency;
 use sp_runtime::traits::One;
 
 runtime_benchmarks! {
@@ -13,6 +21,18 @@ runtime_benchmarks! {
 		Operation::Deposit
 	)
 
+	// The operation is already paused, so the existing expiry is compared with the new one.
+	pause_operation_until {
+		let until = System::block_number() + 100;
+		Controller::pause_operation_until(RawOrigin::Root.into(), DOT, Operation::Deposit, until - 50)?;
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Operation::Deposit,
+		until
+	)
+	verify { assert_eq!(Controller::paused_until(DOT, Operation::Deposit), Some(until)) }
+
 	resume_operation {
 	}: _(
 		RawOrigin::Root,
@@ -46,12 +66,114 @@ runtime_benchmarks! {
 		Some(0u128)
 	)
 
+	set_supply_cap {}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(0u128)
+	)
+
+	set_usage_params {}: _(
+		RawOrigin::Root,
+		DOT,
+		UsageParams {
+			can_be_collateral: true,
+			can_be_borrowed: false,
+			borrowable_in_isolation: false,
+			isolation_debt_ceiling: Some(0u128),
+		}
+	)
+
+	set_interest_accrual_mode {
+		System::set_block_number(10);
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		InterestAccrualMode::Compound
+	)
+	verify { assert_eq!(Controller::controller_params(DOT).interest_accrual_mode, InterestAccrualMode::Compound) }
+
+	switch_borrow_rate_clamping {}: _(
+		RawOrigin::Root,
+		DOT,
+		true
+	)
+	verify { assert!(Controller::borrow_rate_clamping(DOT)) }
+
+	accrue_interest {
+		let keeper: AccountId = account("keeper", 0, SEED);
+		set_balance(MNT, &MntTokenAccountId::get(), 1_000_000 * DOLLARS)?;
+		System::set_block_number(KeeperRewardMinAccrualAge::get() + 1);
+	}: _(
+		RawOrigin::Signed(keeper.clone()),
+		DOT
+	)
+	verify { assert_eq!(Currencies::free_balance(MNT, &keeper), KeeperReward::get()) }
+
+	accrue_interest_all {
+		let n in 1 .. 5;
+		let keeper: AccountId = account("keeper", 0, SEED);
+		set_balance(MNT, &MntTokenAccountId::get(), 1_000_000 * DOLLARS)?;
+		System::set_block_number(KeeperRewardMinAccrualAge::get() + 1);
+		// The interest in these pools is already accrued in the current block, so they are skipped.
+		for pool_id in EnabledUnderlyingAssetsIds::get().into_iter().skip(n as usize) {
+			Controller::accrue_interest(RawOrigin::Signed(keeper.clone()).into(), pool_id)?;
+		}
+	}: _(RawOrigin::Signed(keeper.clone()))
+	verify { assert_eq!(Currencies::free_balance(MNT, &keeper), KeeperReward::get() * 5) }
+
+	set_emode_category {}: _(
+		RawOrigin::Root,
+		1,
+		EModeCategory {
+			collateral_factor: Rate::one(),
+			liquidation_threshold: Rate::zero(),
+		}
+	)
+
+	set_pool_emode_category {
+		Controller::set_emode_category(
+			RawOrigin::Root.into(),
+			1,
+			EModeCategory {
+				collateral_factor: Rate::one(),
+				liquidation_threshold: Rate::zero(),
+			},
+		)?;
+	}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(1)
+	)
+	verify { assert_eq!(Controller::pool_emode_category(DOT), Some(1)) }
+
 	set_protocol_interest_threshold {}: _(
 		RawOrigin::Root,
 		DOT,
 		Balance::zero()
 	)
 
+	pause_all {}: _(
+		RawOrigin::Root,
+		Some(System::block_number() + 100)
+	)
+	verify { assert_eq!(Controller::paused_until(DOT, Operation::Deposit), Some(System::block_number() + 100)) }
+
+	resume_all {
+		Controller::pause_all(RawOrigin::Root.into(), None)?;
+	}: _(RawOrigin::Root)
+	verify { assert!(!Controller::pause_keepers(DOT).deposit_paused) }
+
+	// Every pause expires in the same block.
+	on_initialize {
+		let n in 1 .. 5;
+		let now = System::block_number() + 100;
+		for &operation in controller::ALL_OPERATIONS.iter().take(n as usize) {
+			Controller::pause_operation_until(RawOrigin::Root.into(), DOT, operation, now)?;
+		}
+	}: {
+		Controller::on_initialize(now);
+	}
+	verify { assert!(!Controller::pause_keepers(DOT).deposit_paused) }
 }
 
 #[cfg(test)]
@@ -67,6 +189,13 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn test_pause_operation_until() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_pause_operation_until());
+		})
+	}
+
 	#[test]
 	fn test_resume_operation() {
 		test_externalities().execute_with(|| {
@@ -102,10 +231,87 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn test_set_supply_cap() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_supply_cap());
+		})
+	}
+
+	#[test]
+	fn test_set_usage_params() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_usage_params());
+		})
+	}
+
+	#[test]
+	fn test_set_interest_accrual_mode() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_interest_accrual_mode());
+		})
+	}
+
+	#[test]
+	fn test_switch_borrow_rate_clamping() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_switch_borrow_rate_clamping());
+		})
+	}
+
+	#[test]
+	fn test_accrue_interest() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_accrue_interest());
+		})
+	}
+
+	#[test]
+	fn test_accrue_interest_all() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_accrue_interest_all());
+		})
+	}
+
+	#[test]
+	fn test_set_emode_category() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_emode_category());
+		})
+	}
+
+	#[test]
+	fn test_set_pool_emode_category() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_pool_emode_category());
+		})
+	}
+
 	#[test]
 	fn test_set_protocol_interest_threshold() {
 		test_externalities().execute_with(|| {
 			assert_ok!(test_benchmark_set_protocol_interest_threshold());
 		})
 	}
+
+	#[test]
+	fn test_pause_all() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_pause_all());
+		})
+	}
+
+	#[test]
+	fn test_resume_all() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_resume_all());
+		})
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		})
+	}
 }
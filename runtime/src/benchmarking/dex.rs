@@ -0,0 +1,90 @@
+use super::utils::{add_dex_liquidity, set_balance, SEED};
+use crate::{AccountId, Currencies, CurrencyId, Runtime, System, TwapWindow, BTC, DOLLARS, DOT, ETH, KSM};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use minterest_primitives::currency::TokenSymbol;
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrency;
+use sp_std::prelude::*;
+
+const DOT_ETH: CurrencyId = CurrencyId::DexShare(TokenSymbol::DOT, TokenSymbol::ETH);
+
+// The calls that change the reserves also close the averaging windows that have lasted long
+// enough, which is the worst case.
+fn close_averaging_windows() {
+	System::set_block_number(System::block_number() + TwapWindow::get());
+}
+
+runtime_benchmarks! {
+	{ Runtime, dex }
+
+	add_liquidity {
+		add_dex_liquidity(DOT, 100_000 * DOLLARS, ETH, 50_000 * DOLLARS)?;
+		close_averaging_windows();
+		let who: AccountId = account("alice", 0, SEED);
+		set_balance(DOT, &who, 20_000 * DOLLARS)?;
+		set_balance(ETH, &who, 10_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(who.clone()), DOT, ETH, 20_000 * DOLLARS, 10_000 * DOLLARS)
+	verify { assert_eq!(Currencies::free_balance(DOT_ETH, &who), 20_000 * DOLLARS) }
+
+	remove_liquidity {
+		add_dex_liquidity(DOT, 100_000 * DOLLARS, ETH, 50_000 * DOLLARS)?;
+		close_averaging_windows();
+		let who: AccountId = account("provider", 0, SEED);
+	}: _(RawOrigin::Signed(who.clone()), DOT, ETH, 50_000 * DOLLARS)
+	verify { assert_eq!(Currencies::free_balance(DOT_ETH, &who), 50_000 * DOLLARS - dex::MINIMUM_LIQUIDITY) }
+
+	// The longest allowed path is the worst case.
+	swap_with_exact_supply {
+		add_dex_liquidity(DOT, 100_000 * DOLLARS, ETH, 50_000 * DOLLARS)?;
+		add_dex_liquidity(ETH, 50_000 * DOLLARS, KSM, 50_000 * DOLLARS)?;
+		close_averaging_windows();
+		let who: AccountId = account("alice", 0, SEED);
+		set_balance(DOT, &who, 10_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(who.clone()), vec![DOT, ETH, KSM], 10_000 * DOLLARS, 4_000 * DOLLARS)
+	verify { assert!(Currencies::free_balance(KSM, &who) >= 4_000 * DOLLARS) }
+
+	swap_with_exact_target {
+		add_dex_liquidity(DOT, 100_000 * DOLLARS, ETH, 50_000 * DOLLARS)?;
+		add_dex_liquidity(ETH, 50_000 * DOLLARS, KSM, 50_000 * DOLLARS)?;
+		close_averaging_windows();
+		let who: AccountId = account("alice", 0, SEED);
+		set_balance(DOT, &who, 15_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(who.clone()), vec![DOT, ETH, KSM], 15_000 * DOLLARS, 5_000 * DOLLARS)
+	verify { assert_eq!(Currencies::free_balance(KSM, &who), 5_000 * DOLLARS) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::test_externalities;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_add_liquidity() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_add_liquidity());
+		})
+	}
+
+	#[test]
+	fn test_remove_liquidity() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_remove_liquidity());
+		})
+	}
+
+	#[test]
+	fn test_swap_with_exact_supply() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_swap_with_exact_supply());
+		})
+	}
+
+	#[test]
+	fn test_swap_with_exact_target() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_swap_with_exact_target());
+		})
+	}
+}
@@ -1,5 +1,5 @@
-use super::utils::set_balance;
-use crate::{AccountId, DexPalletId, LiquidationPools, LiquidationPoolsPalletId, Rate, Runtime, DOLLARS, DOT, ETH};
+use super::utils::{add_dex_liquidity, set_balance};
+use crate::{AccountId, LiquidationPools, LiquidationPoolsPalletId, Rate, Runtime, DOLLARS, DOT, ETH};
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
@@ -23,17 +23,13 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(who), DOT, 20_000)
 
 	balance_liquidation_pools {
-		set_balance(
-			ETH,
-			&DexPalletId::get().into_account(),
-			20_000 * DOLLARS,
-		)?;
+		add_dex_liquidity(DOT, 100_000 * DOLLARS, ETH, 100_000 * DOLLARS)?;
 		set_balance(
 			DOT,
 			&LiquidationPoolsPalletId::get().into_account(),
 			20_000 * DOLLARS,
 		)?;
-	}: _(RawOrigin::None, DOT, ETH, 10_000 * DOLLARS, 10_000 * DOLLARS)
+	}: _(RawOrigin::None, DOT, ETH, 12_000 * DOLLARS, 10_000 * DOLLARS)
 }
 
 #[cfg(test)]
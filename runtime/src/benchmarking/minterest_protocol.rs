@@ -1,21 +1,20 @@
 use super::utils::{
-	enable_is_collateral_mock, enable_whitelist_mode_and_add_member, prepare_for_mnt_distribution, set_balance, SEED,
+	enable_is_collateral_mock, enable_whitelist_mode_and_add_member, prepare_for_mnt_distribution, set_balance, units,
+	SEED,
 };
 use crate::{
-	AccountId, Balance, Currencies, EnabledUnderlyingAssetsIds, EnabledWrappedTokensId, LiquidityPools,
+	AccountId, Balance, Controller, Currencies, EnabledUnderlyingAssetsIds, EnabledWrappedTokensId, LiquidityPools,
 	LiquidityPoolsPalletId, MinterestProtocol, MntTokenPalletId, Origin, Rate, RiskManager, Runtime, System, Whitelist,
 	BTC, DOLLARS, DOT, ETH, KSM, MBTC, MDOT, MNT,
 };
+use controller::EModeCategory;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use liquidity_pools::Pool;
-use minterest_primitives::Operation;
 use minterest_protocol::PoolInitData;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
-use pallet_traits::{
-	LiquidityPoolStorageProvider, RiskManagerStorageProvider, UserLiquidationAttemptsManager, UserStorageProvider,
-};
+use pallet_traits::{LiquidityPoolStorageProvider, RiskManagerStorageProvider, UserStorageProvider};
 use sp_runtime::{
 	traits::{AccountIdConversion, One, Zero},
 	FixedPointNumber,
@@ -90,7 +89,7 @@ runtime_benchmarks! {
 		set_balance(DOT, &lender, 50_000 * DOLLARS)?;
 
 		// Set liquidation_attempts grater than zero to reset them.
-		RiskManager::mutate_depending_operation(DOT, &lender, Operation::Repay);
+		risk_manager::UserLiquidationAttempts::<Runtime>::insert(&lender, 1_u8);
 
 		System::set_block_number(10);
 
@@ -276,6 +275,24 @@ runtime_benchmarks! {
 	}: _(RawOrigin::Signed(borrower.clone()), DOT)
 	verify  { assert_eq!(LiquidityPools::pool_user_data(DOT, borrower).is_collateral, false) }
 
+	// The user has collaterals and borrows in all the pools of the category.
+	set_user_emode {
+		let borrower: AccountId = account("borrower", 0, SEED);
+		let lender: AccountId = account("lender", 0, SEED);
+		hypothetical_liquidity_setup(&borrower, &lender)?;
+		Controller::set_emode_category(
+			RawOrigin::Root.into(),
+			1,
+			EModeCategory { collateral_factor: Rate::one(), liquidation_threshold: Rate::zero() },
+		)?;
+		for pool_id in EnabledUnderlyingAssetsIds::get() {
+			Controller::set_pool_emode_category(RawOrigin::Root.into(), pool_id, Some(1))?;
+		}
+
+		enable_whitelist_mode_and_add_member(&borrower)?;
+	}: _(RawOrigin::Signed(borrower.clone()), Some(1))
+	verify  { assert_eq!(Controller::user_emode_category(borrower), Some(1)) }
+
 	claim_mnt {
 		let lender: AccountId = account("lender", 0, SEED);
 		let borrower: AccountId = account("borrower", 0, SEED);
@@ -330,6 +347,34 @@ runtime_benchmarks! {
 		assert_eq!(Currencies::free_balance(MNT, &borrower), 399_999_967_375_002_687_652)
 	}
 
+	liquidate_borrow {
+		prepare_for_mnt_distribution(vec![DOT, ETH])?;
+		let borrower: AccountId = account("borrower", 0, SEED);
+		let liquidator: AccountId = account("liquidator", 0, SEED);
+		Whitelist::add_member(RawOrigin::Root.into(), borrower.clone())?;
+		Whitelist::add_member(RawOrigin::Root.into(), liquidator.clone())?;
+		set_balance(DOT, &borrower, units(DOT, 10_000))?;
+		set_balance(ETH, &liquidator, 10_000 * DOLLARS)?;
+
+		MinterestProtocol::deposit_underlying(RawOrigin::Signed(borrower.clone()).into(), DOT, units(DOT, 10_000))?;
+		MinterestProtocol::enable_is_collateral(Origin::signed(borrower.clone()).into(), DOT)?;
+		MinterestProtocol::borrow(RawOrigin::Signed(borrower.clone()).into(), ETH, 8_000 * DOLLARS)?;
+
+		// Decrease the collateral factor to make the borrower's loan unsafe:
+		// collateral = 10_000 * 2 * 0.5 = 10_000$, borrow = 8_000 * 2 = 16_000$
+		controller::ControllerParams::<Runtime>::mutate(DOT, |data| {
+			data.collateral_factor = Rate::saturating_from_rational(1, 2)
+		});
+
+	}: _(RawOrigin::Signed(liquidator.clone()), borrower.clone(), ETH, DOT, 4_000 * DOLLARS)
+	verify {
+		assert_eq!(Currencies::free_balance(ETH, &liquidator), 6_000 * DOLLARS);
+		// seized = 4_000 * 2 * 1.05 / (2 * 1) = 4_200 MDOT
+		assert_eq!(Currencies::free_balance(MDOT, &liquidator), units(MDOT, 4_200));
+		assert_eq!(Currencies::free_balance(MDOT, &borrower), units(MDOT, 5_800));
+		assert_eq!(LiquidityPools::pool_user_data(ETH, borrower).borrowed, 4_000 * DOLLARS);
+	}
+
 }
 
 #[cfg(test)]
@@ -422,10 +467,24 @@ mod tests {
 		})
 	}
 
+	#[test]
+	fn test_set_user_emode() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_user_emode());
+		})
+	}
+
 	#[test]
 	fn test_claim_mnt() {
 		test_externalities().execute_with(|| {
 			assert_ok!(test_benchmark_claim_mnt());
 		})
 	}
+
+	#[test]
+	fn test_liquidate_borrow() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_liquidate_borrow());
+		})
+	}
 }
@@ -0,0 +1,34 @@
+use crate::{MaxPriceEndpoints, PriceFetcher, Runtime};
+
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use price_fetcher::MAX_ENDPOINT_LENGTH;
+use sp_std::{vec, vec::Vec};
+
+runtime_benchmarks! {
+	{ Runtime, price_fetcher }
+
+	set_endpoints {
+		let e in 1 .. MaxPriceEndpoints::get();
+
+		let endpoints: Vec<Vec<u8>> = vec![vec![b'a'; MAX_ENDPOINT_LENGTH]; e as usize];
+	}: _(
+		RawOrigin::Root,
+		endpoints
+	)
+	verify { assert_eq!(PriceFetcher::endpoints().len(), e as usize) }
+}
+
+#[cfg(test)]
+pub mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::test_externalities;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_set_endpoints() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_endpoints());
+		})
+	}
+}
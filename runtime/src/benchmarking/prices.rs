@@ -1,8 +1,13 @@
-use crate::{CurrencyId, EnabledUnderlyingAssetsIds, MinterestOracle, Origin, Price, Prices, Runtime, DOT};
+use super::utils::SEED;
+use crate::{
+	AccountId, CurrencyId, DataProviderId, EnabledUnderlyingAssetsIds, MinterestOracle, Origin, Price, Prices, Rate,
+	Runtime, Timestamp, DOT,
+};
 
+use frame_benchmarking::account;
 use frame_system::RawOrigin;
 use orml_benchmarking::runtime_benchmarks;
-use sp_runtime::traits::One;
+use sp_runtime::{traits::One, FixedPointNumber};
 use sp_std::vec;
 
 runtime_benchmarks! {
@@ -26,6 +31,35 @@ runtime_benchmarks! {
 		RawOrigin::Root,
 		DOT
 	)
+
+	set_price_source {}: _(
+		RawOrigin::Root,
+		DOT,
+		DataProviderId::Dex
+	)
+	verify { assert_eq!(Prices::price_source(DOT), Some(DataProviderId::Dex)) }
+
+	set_max_price_age {}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(60_000)
+	)
+	verify { assert_eq!(Prices::max_price_age(DOT), Some(60_000)) }
+
+	set_max_price_deviation {}: _(
+		RawOrigin::Root,
+		DOT,
+		Some(Rate::saturating_from_rational(1, 10))
+	)
+	verify { assert_eq!(Prices::max_price_deviation(DOT), Some(Rate::saturating_from_rational(1, 10))) }
+
+	report_stale_price_feed {
+		let caller: AccountId = account("caller", 0, SEED);
+		MinterestOracle::feed_values(RawOrigin::Root.into(), vec![(DOT, Price::one())])?;
+		Prices::set_max_price_age(Origin::root(), DOT, Some(60_000))?;
+		Timestamp::set_timestamp(Timestamp::now() + 60_001);
+	}: _(RawOrigin::Signed(caller), DOT)
+	verify { assert!(Prices::stale_feed(DOT)) }
 }
 
 #[cfg(test)]
@@ -47,4 +81,32 @@ pub mod tests {
 			assert_ok!(test_benchmark_unlock_price());
 		});
 	}
+
+	#[test]
+	fn test_set_price_source() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_price_source());
+		});
+	}
+
+	#[test]
+	fn test_set_max_price_age() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_price_age());
+		});
+	}
+
+	#[test]
+	fn test_set_max_price_deviation() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_max_price_deviation());
+		});
+	}
+
+	#[test]
+	fn test_report_stale_price_feed() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_report_stale_price_feed());
+		});
+	}
 }
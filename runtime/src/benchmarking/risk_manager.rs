@@ -0,0 +1,170 @@
+use super::utils::{prepare_for_mnt_distribution, set_balance, units, SEED};
+use crate::{
+	AccountId, Currencies, LiquidationPoolsPalletId, LiquidityPools, MinterestProtocol, Origin, Rate, RiskManager,
+	Runtime, System, Whitelist, DOLLARS, DOT, ETH,
+};
+use frame_benchmarking::account;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrency;
+use pallet_traits::LiquidityPoolStorageProvider;
+use risk_manager::AuctionParams;
+use sp_runtime::{traits::AccountIdConversion, FixedPointNumber};
+use sp_std::prelude::*;
+
+/// Creates a borrower with an unsafe loan in ETH pool backed by DOT collateral.
+fn unsafe_loan_setup(borrower: &AccountId) -> Result<(), &'static str> {
+	prepare_for_mnt_distribution(vec![DOT, ETH])?;
+	Whitelist::add_member(RawOrigin::Root.into(), borrower.clone())?;
+	set_balance(DOT, borrower, units(DOT, 10_000))?;
+	set_balance(ETH, &LiquidationPoolsPalletId::get().into_account(), 20_000 * DOLLARS)?;
+
+	MinterestProtocol::deposit_underlying(RawOrigin::Signed(borrower.clone()).into(), DOT, units(DOT, 10_000))?;
+	MinterestProtocol::enable_is_collateral(Origin::signed(borrower.clone()), DOT)?;
+	MinterestProtocol::borrow(RawOrigin::Signed(borrower.clone()).into(), ETH, 8_000 * DOLLARS)?;
+
+	// Decrease the collateral factor to make the borrower's loan unsafe:
+	// collateral = 10_000 * 2 * 0.5 = 10_000$, borrow = 8_000 * 2 = 16_000$
+	controller::ControllerParams::<Runtime>::mutate(DOT, |data| {
+		data.collateral_factor = Rate::saturating_from_rational(1, 2)
+	});
+	Ok(())
+}
+
+fn auction_params() -> AuctionParams<u32> {
+	AuctionParams {
+		start_premium: Rate::saturating_from_rational(1, 10),
+		min_price_ratio: Rate::saturating_from_rational(9, 10),
+		duration: 100,
+		max_restarts: 1,
+	}
+}
+
+/// Liquidates an unsafe loan and puts the seized DOT collateral up for auction.
+fn auction_setup() -> Result<(), &'static str> {
+	let borrower: AccountId = account("borrower", 0, SEED);
+	unsafe_loan_setup(&borrower)?;
+	RiskManager::set_auction_params(RawOrigin::Root.into(), DOT, Some(auction_params()))?;
+	RiskManager::liquidate(RawOrigin::None.into(), borrower, ETH)?;
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, risk_manager }
+
+	liquidate {
+		let borrower: AccountId = account("borrower", 0, SEED);
+		unsafe_loan_setup(&borrower)?;
+	}: _(RawOrigin::None, borrower.clone(), ETH)
+	verify { assert!(LiquidityPools::pool_user_data(ETH, borrower).borrowed < 8_000 * DOLLARS) }
+
+	cover_bad_debt_from_liquidation_pool {
+		LiquidityPools::set_pool_bad_debt(ETH, 1_000 * DOLLARS);
+		set_balance(ETH, &LiquidationPoolsPalletId::get().into_account(), 20_000 * DOLLARS)?;
+	}: _(RawOrigin::Root, ETH, 1_000 * DOLLARS)
+	verify { assert_eq!(LiquidityPools::pool_bad_debt(ETH), 0) }
+
+	cover_bad_debt_from_protocol_interest {
+		LiquidityPools::set_pool_bad_debt(ETH, 1_000 * DOLLARS);
+		LiquidityPools::set_pool_protocol_interest(ETH, 1_000 * DOLLARS);
+	}: _(RawOrigin::Root, ETH, 1_000 * DOLLARS)
+	verify { assert_eq!(LiquidityPools::pool_bad_debt(ETH), 0) }
+
+	socialize_bad_debt {
+		LiquidityPools::set_pool_bad_debt(ETH, 1_000 * DOLLARS);
+	}: _(RawOrigin::Root, ETH)
+	verify { assert_eq!(LiquidityPools::pool_bad_debt(ETH), 0) }
+
+	set_liquidation_fee {}: _(RawOrigin::Root, DOT, Rate::saturating_from_rational(5, 100))
+	verify { assert_eq!(RiskManager::liquidation_fee(DOT), Rate::saturating_from_rational(5, 100)) }
+
+	set_liquidation_threshold {}: _(RawOrigin::Root, DOT, Rate::saturating_from_rational(5, 100))
+	verify { assert_eq!(RiskManager::liquidation_threshold(DOT), Rate::saturating_from_rational(5, 100)) }
+
+	set_auction_params {}: _(RawOrigin::Root, DOT, Some(auction_params()))
+	verify { assert_eq!(RiskManager::auction_params(DOT), Some(auction_params())) }
+
+	bid {
+		auction_setup()?;
+		let bidder: AccountId = account("bidder", 0, SEED);
+		set_balance(ETH, &bidder, 10_000 * DOLLARS)?;
+	}: _(RawOrigin::Signed(bidder.clone()), 0, units(DOT, 100))
+	verify { assert_eq!(Currencies::free_balance(DOT, &bidder), units(DOT, 100)) }
+
+	restart_auction {
+		auction_setup()?;
+		let caller: AccountId = account("caller", 0, SEED);
+		System::set_block_number(System::block_number() + auction_params().duration);
+	}: _(RawOrigin::Signed(caller), 0)
+	verify { assert_eq!(RiskManager::auctions(0).map(|x| x.restarts), Some(1)) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::test_externalities;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_liquidate() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_liquidate());
+		})
+	}
+
+	#[test]
+	fn test_cover_bad_debt_from_liquidation_pool() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_cover_bad_debt_from_liquidation_pool());
+		})
+	}
+
+	#[test]
+	fn test_cover_bad_debt_from_protocol_interest() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_cover_bad_debt_from_protocol_interest());
+		})
+	}
+
+	#[test]
+	fn test_socialize_bad_debt() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_socialize_bad_debt());
+		})
+	}
+
+	#[test]
+	fn test_set_liquidation_fee() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquidation_fee());
+		})
+	}
+
+	#[test]
+	fn test_set_liquidation_threshold() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_liquidation_threshold());
+		})
+	}
+
+	#[test]
+	fn test_set_auction_params() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_set_auction_params());
+		})
+	}
+
+	#[test]
+	fn test_bid() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_bid());
+		})
+	}
+
+	#[test]
+	fn test_restart_auction() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_restart_auction());
+		})
+	}
+}
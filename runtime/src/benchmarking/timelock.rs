@@ -0,0 +1,76 @@
+use crate::{Call, Runtime, System, Timelock, TimelockMaxChangesPerBlock, TimelockMinDelay, DOT};
+
+use frame_support::traits::Hooks;
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use sp_std::prelude::*;
+
+fn switch_borrow_rate_clamping_call() -> Box<Call> {
+	Box::new(Call::Controller(controller::Call::switch_borrow_rate_clamping(
+		DOT, true,
+	)))
+}
+
+runtime_benchmarks! {
+	{ Runtime, timelock }
+
+	queue_change {
+		let execution_block = System::block_number() + TimelockMinDelay::get();
+	}: _(
+		RawOrigin::Root,
+		switch_borrow_rate_clamping_call(),
+		execution_block
+	)
+	verify { assert_eq!(Timelock::agenda(execution_block), vec![0]) }
+
+	cancel_change {
+		let n in 1 .. TimelockMaxChangesPerBlock::get();
+		let execution_block = System::block_number() + TimelockMinDelay::get();
+		for _ in 0 .. n {
+			Timelock::queue_change(RawOrigin::Root.into(), switch_borrow_rate_clamping_call(), execution_block)?;
+		}
+	}: _(
+		RawOrigin::Root,
+		0
+	)
+	verify { assert_eq!(Timelock::agenda(execution_block).len(), (n - 1) as usize) }
+
+	on_initialize {
+		let n in 1 .. TimelockMaxChangesPerBlock::get();
+		let execution_block = System::block_number() + TimelockMinDelay::get();
+		for _ in 0 .. n {
+			Timelock::queue_change(RawOrigin::Root.into(), switch_borrow_rate_clamping_call(), execution_block)?;
+		}
+	}: {
+		Timelock::on_initialize(execution_block);
+	}
+	verify { assert!(Timelock::pending_changes(0).is_none()) }
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::test_externalities;
+	use frame_support::assert_ok;
+
+	#[test]
+	fn test_queue_change() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_queue_change());
+		})
+	}
+
+	#[test]
+	fn test_cancel_change() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_cancel_change());
+		})
+	}
+
+	#[test]
+	fn test_on_initialize() {
+		test_externalities().execute_with(|| {
+			assert_ok!(test_benchmark_on_initialize());
+		})
+	}
+}
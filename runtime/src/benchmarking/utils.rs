@@ -1,14 +1,15 @@
 #![allow(unused_imports)]
 
 use crate::{
-	AccountId, Balance, Currencies, CurrencyId, LiquidityPools, MinterestProtocol, MntTokenPalletId, Origin, Rate,
-	Runtime, Vec, Whitelist, BTC, DOLLARS, DOT, ETH, KSM, MNT,
+	AccountId, Balance, Currencies, CurrencyId, Dex, LiquidityPools, MinterestProtocol, MntTokenPalletId, Origin, Rate,
+	Runtime, Vec, Whitelist, BTC, DOLLARS, DOT, ETH, KSM, MNT, USDT,
 };
 
 use frame_benchmarking::account;
 use frame_support::pallet_prelude::DispatchResultWithPostInfo;
 use frame_system::{pallet_prelude::OriginFor, RawOrigin};
 use liquidity_pools::Pool;
+use minterest_primitives::currency::GetDecimals;
 use orml_traits::MultiCurrency;
 use pallet_traits::LiquidityPoolStorageProvider;
 use sp_runtime::{
@@ -22,6 +23,11 @@ pub fn lookup_of_account(who: AccountId) -> <<Runtime as frame_system::Config>::
 	<Runtime as frame_system::Config>::Lookup::unlookup(who)
 }
 
+/// Converts whole tokens into the smallest units of the currency, according to its decimals.
+pub fn units(currency_id: CurrencyId, amount: Balance) -> Balance {
+	amount * 10_u128.pow(currency_id.decimals())
+}
+
 pub fn set_balance(currency_id: CurrencyId, who: &AccountId, balance: Balance) -> DispatchResultWithPostInfo {
 	<Currencies as MultiCurrency<_>>::deposit(currency_id, &who, balance)?;
 	Ok(().into())
@@ -41,6 +47,24 @@ pub fn enable_whitelist_mode_and_add_member(who: &AccountId) -> DispatchResultWi
 	Ok(().into())
 }
 
+pub(crate) fn add_dex_liquidity(
+	currency_id_a: CurrencyId,
+	amount_a: Balance,
+	currency_id_b: CurrencyId,
+	amount_b: Balance,
+) -> DispatchResultWithPostInfo {
+	let provider: AccountId = account("provider", 0, SEED);
+	set_balance(currency_id_a, &provider, amount_a)?;
+	set_balance(currency_id_b, &provider, amount_b)?;
+	Dex::add_liquidity(
+		RawOrigin::Signed(provider).into(),
+		currency_id_a,
+		currency_id_b,
+		amount_a,
+		amount_b,
+	)
+}
+
 pub(crate) fn create_pools(pools: &Vec<CurrencyId>) {
 	pools.into_iter().for_each(|pool_id| {
 		LiquidityPools::set_pool_data(
@@ -70,7 +94,7 @@ pub(crate) fn prepare_for_mnt_distribution(pools: Vec<CurrencyId>) -> Result<(),
 #[cfg(test)]
 pub mod tests {
 	use super::*;
-	use controller::{ControllerData, PauseKeeper};
+	use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 	use frame_support::traits::GenesisBuild;
 	use liquidity_pools::Pool;
 	use minterest_model::MinterestModelData;
@@ -119,6 +143,14 @@ pub mod tests {
 						protocol_interest: Balance::zero(),
 					},
 				),
+				(
+					USDT,
+					Pool {
+						borrowed: Balance::zero(),
+						borrow_index: Rate::one(),
+						protocol_interest: Balance::zero(),
+					},
+				),
 			],
 			pool_user_data: vec![],
 		}
@@ -136,6 +168,8 @@ pub mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -147,6 +181,8 @@ pub mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -158,6 +194,8 @@ pub mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -169,6 +207,21 @@ pub mod tests {
 						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
+					},
+				),
+				(
+					USDT,
+					ControllerData {
+						last_interest_accrued_block: 0,
+						protocol_interest_factor: Rate::saturating_from_rational(1, 10),
+						max_borrow_rate: Rate::saturating_from_rational(5, 1000),
+						collateral_factor: Rate::saturating_from_rational(9, 10), // 90%
+						borrow_cap: None,
+						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			],
@@ -177,6 +230,7 @@ pub mod tests {
 				(DOT, PauseKeeper::all_unpaused()),
 				(KSM, PauseKeeper::all_unpaused()),
 				(BTC, PauseKeeper::all_unpaused()),
+				(USDT, PauseKeeper::all_unpaused()),
 			],
 		}
 		.assimilate_storage(&mut storage)
@@ -220,6 +274,15 @@ pub mod tests {
 						jump_multiplier_per_block: Rate::saturating_from_rational(207, 1_000_000_000), // 1.09 PerYear
 					},
 				),
+				(
+					USDT,
+					MinterestModelData {
+						kink: Rate::saturating_from_rational(8, 10), // 0.8 = 80 %
+						base_rate_per_block: Rate::zero(),
+						multiplier_per_block: Rate::saturating_from_rational(9, 1_000_000_000), // 0.047304 PerYear
+						jump_multiplier_per_block: Rate::saturating_from_rational(207, 1_000_000_000), // 1.09 PerYear
+					},
+				),
 			],
 			_phantom: Default::default(),
 		}
@@ -228,12 +291,19 @@ pub mod tests {
 
 		risk_manager::GenesisConfig::<Runtime> {
 			liquidation_fee: vec![
-				(DOT, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(ETH, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(BTC, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(KSM, FixedU128::saturating_from_rational(5, 100)), // 5%
+				(DOT, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(ETH, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(BTC, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(USDT, FixedU128::saturating_from_rational(5, 100)), // 5%
+				(KSM, FixedU128::saturating_from_rational(5, 100)),  // 5%
+			],
+			liquidation_threshold: vec![
+				(DOT, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(ETH, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(BTC, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(USDT, FixedU128::saturating_from_rational(3, 100)), // 3%
+				(KSM, FixedU128::saturating_from_rational(3, 100)),  // 3%
 			],
-			liquidation_threshold: FixedU128::saturating_from_rational(3, 100), // 3%
 			_phantom: Default::default(),
 		}
 		.assimilate_storage(&mut storage)
@@ -258,6 +328,7 @@ pub mod tests {
 				(ETH, 2 * DOLLARS),
 				(KSM, 2 * DOLLARS),
 				(BTC, 2 * DOLLARS),
+				(USDT, 2 * DOLLARS),
 			],
 			_phantom: Default::default(),
 		}
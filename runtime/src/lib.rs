@@ -15,30 +15,39 @@ mod rpc_tests;
 mod weights;
 mod weights_test;
 
+use codec::Encode;
 pub use controller_rpc_runtime_api::{
-	BalanceInfo, HypotheticalLiquidityData, PoolState, ProtocolTotalValue, UserData, UserPoolBalanceData,
+	BalanceInfo, HypotheticalLiquidityData, PoolState, PoolSupplyCapData, ProtocolTotalValue, UserData,
+	UserPoolBalanceData,
 };
 use frame_system::{EnsureOneOf, EnsureRoot};
 use minterest_primitives::constants::fee::WeightToFee;
 pub use minterest_primitives::{
 	constants::{
 		currency::DOLLARS,
-		liquidation::{MAX_LIQUIDATION_FEE, PARTIAL_LIQUIDATION_MAX_ATTEMPTS, PARTIAL_LIQUIDATION_MIN_SUM},
-		time::{BLOCKS_PER_YEAR, DAYS, SLOT_DURATION},
-		INITIAL_EXCHANGE_RATE, MAX_BORROW_CAP, PROTOCOL_INTEREST_TRANSFER_THRESHOLD, TOTAL_ALLOCATION,
+		dex::SWAP_FEE,
+		liquidation::{
+			CLOSE_FACTOR, MAX_BALANCING_SLIPPAGE, MAX_LIQUIDATION_FEE, PARTIAL_LIQUIDATION_MAX_ATTEMPTS,
+			PARTIAL_LIQUIDATION_MIN_SUM,
+		},
+		oracle::MAX_ORACLE_DEVIATION,
+		time::{BLOCKS_PER_YEAR, DAYS, HOURS, MINUTES, SLOT_DURATION},
+		INITIAL_EXCHANGE_RATE, MAX_BORROW_CAP, MAX_SUPPLY_CAP, PROTOCOL_INTEREST_TRANSFER_THRESHOLD, TOTAL_ALLOCATION,
 	},
 	currency::{
 		CurrencyType::{UnderlyingAsset, WrappedToken},
-		BTC, DOT, ETH, KSM, MBTC, MDOT, METH, MKSM, MNT,
+		BTC, DOT, ETH, KSM, MBTC, MDOT, METH, MKSM, MNT, MUSDT, USDT,
 	},
 	AccountId, AccountIndex, Amount, Balance, BlockNumber, CurrencyId, DataProviderId, DigestItem, Hash, Index,
 	Interest, Moment, Operation, Price, Rate, Signature, VestingBucket,
 };
 pub use mnt_token_rpc_runtime_api::MntBalanceInfo;
 use orml_currencies::BasicCurrencyAdapter;
-use orml_traits::{create_median_value_data_provider, parameter_type_with_key, DataFeeder, DataProviderExtended};
+use orml_traits::{parameter_type_with_key, DataFeeder, DataProvider, DataProviderExtended};
 use pallet_grandpa::{fg_primitives, AuthorityId as GrandpaId, AuthorityList as GrandpaAuthorityList};
-use pallet_traits::{ControllerManager, LiquidityPoolStorageProvider, MntManager, PricesManager, WhitelistManager};
+use pallet_traits::{
+	ControllerManager, LiquidityPoolStorageProvider, MntManager, OracleDataProvider, PricesManager, WhitelistManager,
+};
 use pallet_transaction_payment::{CurrencyAdapter, Multiplier, TargetedFeeAdjustment};
 use sp_api::impl_runtime_apis;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -49,11 +58,14 @@ use sp_core::{
 };
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, NumberFor, One, Zero},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, CheckedDiv, Extrinsic as ExtrinsicT,
+		NumberFor, One, SaturatedConversion, Saturating, StaticLookup, Verify, Zero,
+	},
 	transaction_validity::{TransactionPriority, TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, DispatchResult, FixedPointNumber,
 };
-use sp_std::{cmp::Ordering, convert::TryFrom, prelude::*};
+use sp_std::{cmp::Ordering, collections::btree_set::BTreeSet, convert::TryFrom, prelude::*};
 #[cfg(feature = "std")]
 use sp_version::NativeVersion;
 use sp_version::RuntimeVersion;
@@ -61,7 +73,7 @@ use sp_version::RuntimeVersion;
 // A few exports that help ease life for downstream crates.
 pub use frame_support::{
 	construct_runtime, debug, parameter_types,
-	traits::{KeyOwnerProofSystem, Randomness, SortedMembers},
+	traits::{Filter, KeyOwnerProofSystem, Randomness, SortedMembers},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		DispatchClass, IdentityFee, Weight,
@@ -123,6 +135,7 @@ parameter_types! {
 	pub const LiquidationPoolsPalletId: PalletId = PalletId(*b"min/lqdn");
 	pub const DexPalletId: PalletId = PalletId(*b"min/dexs");
 	pub const LiquidityPoolsPalletId: PalletId = PalletId(*b"min/lqdy");
+	pub const AuctionPalletId: PalletId = PalletId(*b"min/auct");
 }
 
 // Do not change the order of modules. Used for genesis block.
@@ -132,6 +145,7 @@ pub fn get_all_modules_accounts() -> Vec<AccountId> {
 		LiquidationPoolsPalletId::get().into_account(),
 		DexPalletId::get().into_account(),
 		LiquidityPoolsPalletId::get().into_account(),
+		AuctionPalletId::get().into_account(),
 	]
 }
 
@@ -295,6 +309,12 @@ type EnsureRootOrHalfMinterestCouncil = EnsureOneOf<
 	pallet_collective::EnsureProportionMoreThan<_1, _2, AccountId, MinterestCouncilInstance>,
 >;
 
+type EnsureRootOrOneThirdMinterestCouncil = EnsureOneOf<
+	AccountId,
+	EnsureRoot<AccountId>,
+	pallet_collective::EnsureProportionAtLeast<_1, _3, AccountId, MinterestCouncilInstance>,
+>;
+
 parameter_types! {
 	pub const MinterestCouncilMotionDuration: BlockNumber = 7 * DAYS;
 	pub const MinterestCouncilMaxProposals: u32 = 100;
@@ -344,6 +364,20 @@ impl pallet_membership::Config<OperatorMembershipInstanceMinterest> for Runtime
 	type WeightInfo = ();
 }
 
+type OperatorMembershipInstanceExternal = pallet_membership::Instance3;
+impl pallet_membership::Config<OperatorMembershipInstanceExternal> for Runtime {
+	type Event = Event;
+	type AddOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type RemoveOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type SwapOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type ResetOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type PrimeOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type MembershipInitialized = ();
+	type MembershipChanged = ExternalOracle;
+	type MaxMembers = MinterestCouncilMaxMembers;
+	type WeightInfo = ();
+}
+
 impl minterest_protocol::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
@@ -409,6 +443,10 @@ impl liquidity_pools::Config for Runtime {
 
 parameter_types! {
 	pub const MaxBorrowCap: Balance = MAX_BORROW_CAP;
+	pub const MaxSupplyCap: Balance = MAX_SUPPLY_CAP;
+	pub const CloseFactor: Rate = CLOSE_FACTOR;
+	pub const KeeperReward: Balance = DOLLARS;
+	pub const KeeperRewardMinAccrualAge: BlockNumber = DAYS;
 }
 
 impl controller::Config for Runtime {
@@ -418,15 +456,51 @@ impl controller::Config for Runtime {
 	type LiquidityPoolsManager = LiquidityPools;
 	type MinterestModelManager = MinterestModel;
 	type MaxBorrowCap = MaxBorrowCap;
+	type MaxSupplyCap = MaxSupplyCap;
+	type CloseFactor = CloseFactor;
 	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type RiskParameterOrigin = EnsureRoot<AccountId>;
+	type EmergencyOrigin = EnsureRootOrOneThirdMinterestCouncil;
+	type PauseGuardianOrigin = EnsureRootOrOneThirdMinterestCouncil;
 	type ControllerWeightInfo = weights::controller::WeightInfo<Runtime>;
 	type MntManager = MntToken;
+	type MntTokenAccountId = MntTokenAccountId;
+	type KeeperReward = KeeperReward;
+	type KeeperRewardMinAccrualAge = KeeperRewardMinAccrualAge;
+}
+
+parameter_types! {
+	pub const DexQuoteCurrencyId: CurrencyId = DOT;
+	pub StableCurrencyFixedPrice: Vec<(CurrencyId, Price)> = vec![(USDT, Price::one())];
+	// At most one price per block is kept, so the history covers at least the last hour.
+	pub const PriceHistoryDepth: u32 = HOURS;
+}
+
+/// Dex prices are quoted in DOT, which is priced by the oracle.
+type DexDataProvider = dex::TwapDataProvider<Runtime, DexQuoteCurrencyId, AggregatedDataProvider>;
+
+/// Stamps the dex price of the currency with the time its average price was last updated. The
+/// price of the quote currency itself comes from the oracles and keeps their timestamp.
+fn get_timestamped_dex_price(currency_id: &CurrencyId) -> Option<TimeStampedPrice> {
+	let quote_currency_id = DexQuoteCurrencyId::get();
+	let timestamp = if *currency_id == quote_currency_id {
+		<AggregatedDataProvider as DataProviderExtended<_, _>>::get_no_op(currency_id)?.timestamp
+	} else {
+		Dex::get_average_price_timestamp(*currency_id, quote_currency_id)?
+	};
+	DexDataProvider::get(currency_id).map(|value| TimeStampedPrice { value, timestamp })
 }
 
 impl module_prices::Config for Runtime {
 	type Event = Event;
 	type Source = AggregatedDataProvider;
+	type Oracles = OracleDataProviders;
+	type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
+	type DexSource = DexDataProvider;
 	type LockOrigin = EnsureRootOrTwoThirdsMinterestCouncil;
+	type Time = Timestamp;
+	type ControllerManager = Controller;
+	type PriceHistoryDepth = PriceHistoryDepth;
 	type WeightInfo = weights::prices::WeightInfo<Runtime>;
 }
 
@@ -437,7 +511,7 @@ parameter_types! {
 impl minterest_model::Config for Runtime {
 	type Event = Event;
 	type BlocksPerYear = BlocksPerYear;
-	type ModelUpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type ModelUpdateOrigin = EnsureRoot<AccountId>;
 	type WeightInfo = weights::minterest_model::WeightInfo<Runtime>;
 }
 
@@ -445,15 +519,26 @@ parameter_types! {
 	pub const PartialLiquidationMinSum: Balance = PARTIAL_LIQUIDATION_MIN_SUM;
 	pub const PartialLiquidationMaxAttempts: u8 = PARTIAL_LIQUIDATION_MAX_ATTEMPTS;
 	pub const MaxLiquidationFee: Rate = MAX_LIQUIDATION_FEE;
+	pub const RiskManagerPriority: TransactionPriority = TransactionPriority::max_value();
+	pub AuctionAccountId: AccountId = AuctionPalletId::get().into_account();
 }
 
 impl risk_manager::Config for Runtime {
 	type Event = Event;
-	type UserCollateral = LiquidityPools;
+	type UnsignedPriority = RiskManagerPriority;
+	type MultiCurrency = Currencies;
+	type PriceSource = Prices;
+	type LiquidityPoolsManager = LiquidityPools;
+	type LiquidationPoolsManager = LiquidationPools;
+	type ControllerManager = Controller;
+	type MinterestProtocolManager = MinterestProtocol;
 	type PartialLiquidationMinSum = PartialLiquidationMinSum;
 	type PartialLiquidationMaxAttempts = PartialLiquidationMaxAttempts;
 	type MaxLiquidationFee = MaxLiquidationFee;
 	type RiskManagerUpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type RiskParameterOrigin = EnsureRoot<AccountId>;
+	type AuctionAccountId = AuctionAccountId;
+	type RiskManagerWeightInfo = weights::risk_manager::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -479,9 +564,53 @@ where
 	type Extrinsic = UncheckedExtrinsic;
 }
 
+impl frame_system::offchain::SigningTypes for Runtime {
+	type Public = <Signature as Verify>::Signer;
+	type Signature = Signature;
+}
+
+impl<LocalCall> frame_system::offchain::CreateSignedTransaction<LocalCall> for Runtime
+where
+	Call: From<LocalCall>,
+{
+	fn create_transaction<C: frame_system::offchain::AppCrypto<Self::Public, Self::Signature>>(
+		call: Call,
+		public: <Signature as Verify>::Signer,
+		account: AccountId,
+		nonce: Index,
+	) -> Option<(Call, <UncheckedExtrinsic as ExtrinsicT>::SignaturePayload)> {
+		// The transaction is valid for at most `BlockHashCount` blocks.
+		let period = BlockHashCount::get()
+			.checked_next_power_of_two()
+			.map(|c| c / 2)
+			.unwrap_or(2) as u64;
+		let current_block = System::block_number().saturated_into::<u64>().saturating_sub(1);
+		let tip = 0;
+		let extra: SignedExtra = (
+			frame_system::CheckSpecVersion::<Runtime>::new(),
+			frame_system::CheckTxVersion::<Runtime>::new(),
+			frame_system::CheckGenesis::<Runtime>::new(),
+			frame_system::CheckEra::<Runtime>::from(generic::Era::mortal(period, current_block)),
+			frame_system::CheckNonce::<Runtime>::from(nonce),
+			frame_system::CheckWeight::<Runtime>::new(),
+			pallet_transaction_payment::ChargeTransactionPayment::<Runtime>::from(tip),
+		);
+		let raw_payload = SignedPayload::new(call, extra)
+			.map_err(|e| {
+				frame_support::log::warn!("Unable to create signed payload: {:?}", e);
+			})
+			.ok()?;
+		let signature = raw_payload.using_encoded(|payload| C::sign(payload, public))?;
+		let address = <Runtime as frame_system::Config>::Lookup::unlookup(account);
+		let (call, extra, _) = raw_payload.deconstruct();
+		Some((call, (address, signature, extra)))
+	}
+}
+
 parameter_types! {
 	pub LiquidationPoolAccountId: AccountId = LiquidationPoolsPalletId::get().into_account();
 	pub const LiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value() - 1;
+	pub const MaxBalancingSlippage: Rate = MAX_BALANCING_SLIPPAGE;
 }
 
 impl liquidation_pools::Config for Runtime {
@@ -491,9 +620,10 @@ impl liquidation_pools::Config for Runtime {
 	type LiquidationPoolAccountId = LiquidationPoolAccountId;
 	type PriceSource = Prices;
 	type LiquidationPoolsPalletId = LiquidationPoolsPalletId;
-	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type UpdateOrigin = EnsureRoot<AccountId>;
 	type LiquidityPoolsManager = LiquidityPools;
 	type Dex = Dex;
+	type MaxBalancingSlippage = MaxBalancingSlippage;
 	type LiquidationPoolsWeightInfo = weights::liquidation_pools::WeightInfo<Runtime>;
 }
 
@@ -502,13 +632,14 @@ parameter_types! {
 	pub const ExpiresIn: Moment = 1000 * 60 * 60; // 60 mins
 	pub ZeroAccountId: AccountId = AccountId::from([0u8; 32]);
 	pub const MaxHasDispatchedSize: u32 = 100;
+	pub const MaxOracleDeviation: Rate = MAX_ORACLE_DEVIATION;
 }
 
 pub type TimeStampedPrice = orml_oracle::TimestampedValue<Price, minterest_primitives::Moment>;
 type MinterestDataProvider = orml_oracle::Instance1;
 impl orml_oracle::Config<MinterestDataProvider> for Runtime {
 	type Event = Event;
-	type OnNewData = ();
+	type OnNewData = Prices;
 	type CombineData = orml_oracle::DefaultCombineData<Runtime, MinimumCount, ExpiresIn, MinterestDataProvider>;
 	type Time = Timestamp;
 	type OracleKey = CurrencyId;
@@ -519,13 +650,84 @@ impl orml_oracle::Config<MinterestDataProvider> for Runtime {
 	type MaxHasDispatchedSize = MaxHasDispatchedSize;
 }
 
-create_median_value_data_provider!(
-	AggregatedDataProvider,
-	CurrencyId,
-	Price,
-	TimeStampedPrice,
-	[MinterestOracle]
-);
+type ExternalDataProvider = orml_oracle::Instance2;
+impl orml_oracle::Config<ExternalDataProvider> for Runtime {
+	type Event = Event;
+	type OnNewData = Prices;
+	type CombineData = orml_oracle::DefaultCombineData<Runtime, MinimumCount, ExpiresIn, ExternalDataProvider>;
+	type Time = Timestamp;
+	type OracleKey = CurrencyId;
+	type OracleValue = Price;
+	type RootOperatorAccountId = ZeroAccountId;
+	type WeightInfo = ();
+	type Members = OperatorMembershipExternal;
+	type MaxHasDispatchedSize = MaxHasDispatchedSize;
+}
+
+parameter_types! {
+	pub const PriceFetchPeriod: BlockNumber = 10;
+	pub const MaxPriceEndpoints: u32 = 10;
+}
+
+impl price_fetcher::Config for Runtime {
+	type Event = Event;
+	type AuthorityId = price_fetcher::crypto::OracleAuthId;
+	type FetchPeriod = PriceFetchPeriod;
+	type MaxEndpoints = MaxPriceEndpoints;
+	type UpdateOrigin = EnsureRootOrHalfMinterestCouncil;
+	type WeightInfo = weights::price_fetcher::WeightInfo<Runtime>;
+}
+
+/// The median of the prices of all oracle instances. An even number of prices, such as the
+/// prices of both instances, is averaged only if the two middle prices differ by no more than
+/// `MaxOracleDeviation`, otherwise there is no price. So a single instance can't move the price
+/// on its own.
+pub struct AggregatedDataProvider;
+impl AggregatedDataProvider {
+	fn median(mut prices: Vec<TimeStampedPrice>) -> Option<TimeStampedPrice> {
+		prices.sort_by_key(|price| price.value);
+		let middle = prices.len() / 2;
+		if prices.len() % 2 == 1 {
+			return prices.get(middle).cloned();
+		}
+		let lower = prices.get(middle.checked_sub(1)?)?;
+		let upper = prices.get(middle)?;
+		// deviation = (upper - lower) / lower
+		let deviation = upper.value.saturating_sub(lower.value).checked_div(&lower.value)?;
+		if deviation > MaxOracleDeviation::get() {
+			return None;
+		}
+		Some(TimeStampedPrice {
+			value: Price::from_inner(lower.value.into_inner().saturating_add(upper.value.into_inner()) / 2),
+			timestamp: lower.timestamp.min(upper.timestamp),
+		})
+	}
+}
+
+impl DataProvider<CurrencyId, Price> for AggregatedDataProvider {
+	fn get(key: &CurrencyId) -> Option<Price> {
+		Self::get_no_op(key).map(|price| price.value)
+	}
+}
+
+impl DataProviderExtended<CurrencyId, TimeStampedPrice> for AggregatedDataProvider {
+	fn get_no_op(key: &CurrencyId) -> Option<TimeStampedPrice> {
+		let prices = vec![MinterestOracle::get_no_op(key), ExternalOracle::get_no_op(key)];
+		Self::median(prices.into_iter().flatten().collect())
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimeStampedPrice>)> {
+		MinterestOracle::get_all_values()
+			.into_iter()
+			.chain(ExternalOracle::get_all_values())
+			.map(|(key, _)| key)
+			.collect::<BTreeSet<_>>()
+			.into_iter()
+			.map(|key| (key, Self::get_no_op(&key)))
+			.collect()
+	}
+}
+
 // Aggregated data provider cannot feed.
 impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
 	fn feed_value(_: AccountId, _: CurrencyId, _: Price) -> DispatchResult {
@@ -533,15 +735,34 @@ impl DataFeeder<CurrencyId, Price, AccountId> for AggregatedDataProvider {
 	}
 }
 
+/// The oracle instances selected by the data provider id.
+pub struct OracleDataProviders;
+impl OracleDataProvider<CurrencyId, TimeStampedPrice> for OracleDataProviders {
+	fn get_no_op(provider_id: DataProviderId, key: &CurrencyId) -> Option<TimeStampedPrice> {
+		match provider_id {
+			DataProviderId::Aggregated => <AggregatedDataProvider as DataProviderExtended<_, _>>::get_no_op(key),
+			DataProviderId::Minterest => MinterestOracle::get_no_op(key),
+			DataProviderId::External => ExternalOracle::get_no_op(key),
+			DataProviderId::Dex => None,
+		}
+	}
+}
+
 parameter_types! {
-	pub DexAccountId: AccountId = DexPalletId::get().into_account();
+	pub const SwapFee: Rate = SWAP_FEE;
+	pub const TradingPathLimit: u32 = 3;
+	pub const TwapWindow: BlockNumber = 30 * MINUTES;
 }
 
 impl dex::Config for Runtime {
 	type Event = Event;
 	type MultiCurrency = Currencies;
 	type DexPalletId = DexPalletId;
-	type DexAccountId = DexAccountId;
+	type SwapFee = SwapFee;
+	type TradingPathLimit = TradingPathLimit;
+	type TwapWindow = TwapWindow;
+	type Time = Timestamp;
+	type DexWeightInfo = weights::dex::WeightInfo<Runtime>;
 }
 
 parameter_types! {
@@ -571,6 +792,60 @@ impl whitelist_module::Config for Runtime {
 	type WhitelistWeightInfo = weights::whitelist::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const TimelockMinDelay: BlockNumber = 2 * DAYS;
+	pub const TimelockMaxChangesPerBlock: u32 = 10;
+	pub TimelockMaximumWeight: Weight = Perbill::from_percent(10) * BlockWeights::get().max_block;
+}
+
+/// The risk parameter changes that can be queued in the timelock. These setters accept the root
+/// origin only, which the timelock dispatches with. The controller also lets the emergency origin
+/// lower caps and restrict the usage of pools without a delay.
+pub struct RiskParameterCalls;
+impl Filter<Call> for RiskParameterCalls {
+	fn filter(call: &Call) -> bool {
+		matches!(
+			call,
+			Call::Controller(
+				controller::Call::set_max_borrow_rate(..)
+					| controller::Call::set_collateral_factor(..)
+					| controller::Call::set_borrow_cap(..)
+					| controller::Call::set_supply_cap(..)
+					| controller::Call::set_usage_params(..)
+					| controller::Call::set_interest_accrual_mode(..)
+					| controller::Call::switch_borrow_rate_clamping(..)
+					| controller::Call::set_emode_category(..)
+					| controller::Call::set_pool_emode_category(..)
+			) | Call::MinterestModel(
+				minterest_model::Call::set_jump_multiplier(..)
+					| minterest_model::Call::set_base_rate(..)
+					| minterest_model::Call::set_multiplier(..)
+					| minterest_model::Call::set_kink(..)
+			) | Call::RiskManager(
+				risk_manager::Call::set_liquidation_fee(..)
+					| risk_manager::Call::set_liquidation_threshold(..)
+					| risk_manager::Call::set_auction_params(..)
+			) | Call::LiquidationPools(
+				liquidation_pools::Call::set_deviation_threshold(..)
+					| liquidation_pools::Call::set_balance_ratio(..)
+					| liquidation_pools::Call::set_max_ideal_balance(..)
+			)
+		)
+	}
+}
+
+impl timelock::Config for Runtime {
+	type Event = Event;
+	type Call = Call;
+	type CallFilter = RiskParameterCalls;
+	type QueueOrigin = EnsureRootOrHalfMinterestCouncil;
+	type CancelOrigin = EnsureRootOrHalfMinterestCouncil;
+	type MinDelay = TimelockMinDelay;
+	type MaxChangesPerBlock = TimelockMaxChangesPerBlock;
+	type MaximumWeight = TimelockMaximumWeight;
+	type WeightInfo = weights::timelock::WeightInfo<Runtime>;
+}
+
 // Create the runtime by composing the FRAME pallets that were previously configured.
 construct_runtime!(
 	pub enum Runtime where
@@ -598,21 +873,25 @@ construct_runtime!(
 
 		// Oracle and Prices
 		MinterestOracle: orml_oracle::<Instance1>::{Pallet, Storage, Call, Event<T>},
+		ExternalOracle: orml_oracle::<Instance2>::{Pallet, Storage, Call, Event<T>},
 		Prices: module_prices::{Pallet, Storage, Call, Event<T>, Config<T>},
+		PriceFetcher: price_fetcher::{Pallet, Storage, Call, Event<T>},
 
 		// OperatorMembership must be placed after Oracle or else will have race condition on initialization
 		OperatorMembershipMinterest: pallet_membership::<Instance2>::{Pallet, Call, Storage, Event<T>, Config<T>},
+		OperatorMembershipExternal: pallet_membership::<Instance3>::{Pallet, Call, Storage, Event<T>, Config<T>},
 
 		// Minterest pallets
 		MinterestProtocol: minterest_protocol::{Pallet, Call, Event<T>},
 		LiquidityPools: liquidity_pools::{Pallet, Storage, Call, Config<T>},
 		Controller: controller::{Pallet, Storage, Call, Event, Config<T>},
 		MinterestModel: minterest_model::{Pallet, Storage, Call, Event, Config<T>},
-		RiskManager: risk_manager::{Pallet, Storage, Call, Event<T>, Config<T>},
+		RiskManager: risk_manager::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned},
 		LiquidationPools: liquidation_pools::{Pallet, Storage, Call, Event<T>, Config<T>, ValidateUnsigned},
 		MntToken: mnt_token::{Pallet, Storage, Call, Event<T>, Config<T>},
 		Dex: dex::{Pallet, Storage, Call, Event<T>},
 		Whitelist: whitelist_module::{Pallet, Storage, Call, Event<T>, Config<T>},
+		Timelock: timelock::{Pallet, Storage, Call, Event<T>},
 		// Dev
 		Sudo: pallet_sudo::{Pallet, Call, Config<T>, Storage, Event<T>},
 	}
@@ -638,6 +917,8 @@ pub type SignedExtra = (
 	frame_system::CheckWeight<Runtime>,
 	pallet_transaction_payment::ChargeTransactionPayment<Runtime>,
 );
+/// The payload being signed in transactions.
+pub type SignedPayload = generic::SignedPayload<Call, SignedExtra>;
 /// Unchecked extrinsic type as expected by this runtime.
 pub type UncheckedExtrinsic = generic::UncheckedExtrinsic<Address, Call, Signature, SignedExtra>;
 /// Extrinsic type that has already been checked.
@@ -834,6 +1115,23 @@ impl_runtime_apis! {
 		fn get_user_total_supply_borrow_and_net_apy(account_id: AccountId) -> Option<(Interest, Interest, Interest)> {
 			Controller::get_user_total_supply_borrow_and_net_apy(account_id).ok()
 		}
+
+		fn get_pool_bad_debt(pool_id: CurrencyId) -> Option<BalanceInfo> {
+			if !LiquidityPools::pool_exists(&pool_id) {
+				return None;
+			}
+			Some(BalanceInfo{amount: LiquidityPools::pool_bad_debt(pool_id)})
+		}
+
+		fn get_pool_supply_cap(pool_id: CurrencyId) -> Option<PoolSupplyCapData> {
+			if !LiquidityPools::pool_exists(&pool_id) {
+				return None;
+			}
+			Some(PoolSupplyCapData {
+				pool_total_supply_in_usd: Controller::get_pool_total_supply_in_usd(pool_id)?,
+				supply_cap: Controller::controller_params(pool_id).supply_cap.map(|amount| BalanceInfo { amount }),
+			})
+		}
 	}
 
 	impl mnt_token_rpc_runtime_api::MntTokenRuntimeApi<Block, AccountId> for Runtime {
@@ -861,14 +1159,21 @@ impl_runtime_apis! {
 		fn get_value(provider_id: DataProviderId, key: CurrencyId) -> Option<TimeStampedPrice> {
 			match provider_id {
 				DataProviderId::Minterest => MinterestOracle::get_no_op(&key),
-				DataProviderId::Aggregated => <AggregatedDataProvider as DataProviderExtended<_, _>>::get_no_op(&key)
+				DataProviderId::External => ExternalOracle::get_no_op(&key),
+				DataProviderId::Aggregated => <AggregatedDataProvider as DataProviderExtended<_, _>>::get_no_op(&key),
+				DataProviderId::Dex => get_timestamped_dex_price(&key),
 			}
 		}
 
 		fn get_all_values(provider_id: DataProviderId) -> Vec<(CurrencyId, Option<TimeStampedPrice>)> {
 			match provider_id {
 				DataProviderId::Minterest => MinterestOracle::get_all_values(),
-				DataProviderId::Aggregated => <AggregatedDataProvider as DataProviderExtended<_, _>>::get_all_values()
+				DataProviderId::External => ExternalOracle::get_all_values(),
+				DataProviderId::Aggregated => <AggregatedDataProvider as DataProviderExtended<_, _>>::get_all_values(),
+				DataProviderId::Dex => CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+					.into_iter()
+					.map(|currency_id| (currency_id, get_timestamped_dex_price(&currency_id)))
+					.collect(),
 			}
 		}
 	}
@@ -888,6 +1193,18 @@ impl_runtime_apis! {
 		fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
 			Prices::get_all_freshest_prices()
 		}
+
+		fn get_stale_price_feeds() -> Vec<(CurrencyId, Option<Moment>)> {
+			Prices::get_stale_price_feeds()
+		}
+
+		fn get_price_history(
+			currency_id: CurrencyId,
+			from_block: BlockNumber,
+			to_block: BlockNumber,
+		) -> Vec<(BlockNumber, Price)> {
+			Prices::get_price_history(currency_id, from_block, to_block)
+		}
 	}
 
 	#[cfg(feature = "runtime-benchmarks")]
@@ -915,13 +1232,17 @@ impl_runtime_apis! {
 			let params = (&config, &whitelist);
 
 			add_benchmark!(params, batches, controller, benchmarking::controller);
+			add_benchmark!(params, batches, dex, benchmarking::dex);
 			add_benchmark!(params, batches, minterest_model, benchmarking::minterest_model);
 			add_benchmark!(params, batches, module_prices, benchmarking::prices);
+			add_benchmark!(params, batches, price_fetcher, benchmarking::price_fetcher);
 			add_benchmark!(params, batches, liquidation_pools, benchmarking::liquidation_pools);
 			add_benchmark!(params, batches, minterest_protocol, benchmarking::minterest_protocol);
 			add_benchmark!(params, batches, mnt_token, benchmarking::mnt_token);
+			add_benchmark!(params, batches, risk_manager, benchmarking::risk_manager);
 			add_benchmark!(params, batches, module_vesting, benchmarking::vesting);
 			add_benchmark!(params, batches, whitelist_module, benchmarking::whitelist);
+			add_benchmark!(params, batches, timelock, benchmarking::timelock);
 
 			if batches.is_empty() { return Err("Benchmark not found for this pallet.".into()) }
 			Ok(batches)
@@ -1,28 +1,32 @@
 use crate::{
-	AccountId, Balance, Block, Controller, Currencies, EnabledUnderlyingAssetsIds, LiquidationPools, LiquidityPools,
-	MinterestCouncilMembership, MinterestOracle, MinterestProtocol, MntToken, Prices, Rate, Runtime, System, UserData,
-	Whitelist, DOLLARS, PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+	AccountId, AggregatedDataProvider, Balance, Block, Controller, Currencies, EnabledUnderlyingAssetsIds,
+	ExternalOracle, LiquidationPools, LiquidityPools, MinterestCouncilMembership, MinterestOracle, MinterestProtocol,
+	MntToken, Prices, Rate, Runtime, System, Timestamp, UserData, Whitelist, DOLLARS,
+	PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
 };
-use controller::{ControllerData, PauseKeeper};
+use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 use controller_rpc_runtime_api::{
 	runtime_decl_for_ControllerRuntimeApi::ControllerRuntimeApi, BalanceInfo, HypotheticalLiquidityData, PoolState,
-	ProtocolTotalValue, UserPoolBalanceData,
+	PoolSupplyCapData, ProtocolTotalValue, UserPoolBalanceData,
 };
 use frame_support::pallet_prelude::{DispatchResultWithPostInfo, PhantomData};
 use frame_support::{assert_noop, assert_ok, pallet_prelude::GenesisBuild, parameter_types, traits::OnFinalize};
 use liquidation_pools::LiquidationPoolData;
 use liquidity_pools::{Pool, PoolUserData};
 use minterest_model::MinterestModelData;
-use minterest_primitives::{CurrencyId, Interest, Operation, Price};
+use minterest_primitives::{
+	constants::currency::USD_DECIMALS, currency::GetDecimals, BlockNumber, CurrencyId, Interest, Moment, Operation,
+	Price,
+};
 use mnt_token_rpc_runtime_api::runtime_decl_for_MntTokenRuntimeApi::MntTokenRuntimeApi;
-use orml_traits::MultiCurrency;
+use orml_traits::{DataProviderExtended, MultiCurrency};
 use pallet_traits::{LiquidityPoolStorageProvider, PoolsManager, PricesManager, UserCollateral};
 use prices_rpc_runtime_api::runtime_decl_for_PricesRuntimeApi::PricesRuntimeApi;
 use sp_runtime::{
 	traits::{One, Zero},
 	DispatchResult, FixedPointNumber,
 };
-use test_helper::{BTC, DOT, ETH, KSM, MDOT, MNT};
+use test_helper::{BTC, DOT, ETH, KSM, MDOT, MNT, USDT};
 use whitelist_rpc_runtime_api::runtime_decl_for_WhitelistRuntimeApi::WhitelistRuntimeApi;
 
 parameter_types! {
@@ -41,7 +45,7 @@ struct ExtBuilder {
 	pool_user_data: Vec<(CurrencyId, AccountId, PoolUserData)>,
 	minted_pools: Vec<(CurrencyId, Balance)>,
 	liquidation_fee: Vec<(CurrencyId, Rate)>,
-	liquidation_threshold: Rate,
+	liquidation_threshold: Vec<(CurrencyId, Rate)>,
 }
 
 impl Default for ExtBuilder {
@@ -79,7 +83,12 @@ impl Default for ExtBuilder {
 				(BTC, Rate::saturating_from_rational(5, 100)),
 				(KSM, Rate::saturating_from_rational(5, 100)),
 			],
-			liquidation_threshold: Rate::saturating_from_rational(3, 100),
+			liquidation_threshold: vec![
+				(DOT, Rate::saturating_from_rational(3, 100)),
+				(ETH, Rate::saturating_from_rational(3, 100)),
+				(BTC, Rate::saturating_from_rational(3, 100)),
+				(KSM, Rate::saturating_from_rational(3, 100)),
+			],
 		}
 	}
 }
@@ -158,6 +167,8 @@ impl ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -170,6 +181,8 @@ impl ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -182,6 +195,8 @@ impl ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -194,6 +209,8 @@ impl ExtBuilder {
 						collateral_factor: Rate::saturating_from_rational(9, 10),        // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			],
@@ -257,6 +274,13 @@ impl ExtBuilder {
 		.assimilate_storage(&mut t)
 		.unwrap();
 
+		pallet_membership::GenesisConfig::<Runtime, pallet_membership::Instance3> {
+			members: vec![ORACLE2::get().clone()],
+			phantom: Default::default(),
+		}
+		.assimilate_storage(&mut t)
+		.unwrap();
+
 		liquidation_pools::GenesisConfig::<Runtime> {
 			phantom: PhantomData,
 			liquidation_pools: vec![
@@ -378,6 +402,14 @@ fn pool_exists_rpc(underlying_asset_id: CurrencyId) -> bool {
 	<Runtime as ControllerRuntimeApi<Block, AccountId>>::pool_exists(underlying_asset_id)
 }
 
+fn get_pool_bad_debt_rpc(pool_id: CurrencyId) -> Option<BalanceInfo> {
+	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_pool_bad_debt(pool_id)
+}
+
+fn get_pool_supply_cap_rpc(pool_id: CurrencyId) -> Option<PoolSupplyCapData> {
+	<Runtime as ControllerRuntimeApi<Block, AccountId>>::get_pool_supply_cap(pool_id)
+}
+
 fn dollars(amount: u128) -> u128 {
 	amount.saturating_mul(Price::accuracy())
 }
@@ -402,10 +434,16 @@ fn origin_root() -> <Runtime as frame_system::Config>::Origin {
 	<Runtime as frame_system::Config>::Origin::root()
 }
 
+/// The amounts in the tests are expressed in `DOLLARS` for all assets, so the oracle price
+/// is quoted per `DOLLARS` of the asset rather than per one token of it.
+fn oracle_price(pool_id: CurrencyId, price: u128) -> Price {
+	Price::saturating_from_rational(price, 10_u128.pow(USD_DECIMALS - pool_id.decimals()))
+}
+
 fn set_oracle_price_for_all_pools(price: u128) -> DispatchResult {
 	let prices: Vec<(CurrencyId, Price)> = EnabledUnderlyingAssetsIds::get()
 		.into_iter()
-		.map(|pool_id| (pool_id, Price::saturating_from_integer(price)))
+		.map(|pool_id| (pool_id, oracle_price(pool_id, price)))
 		.collect();
 	MinterestOracle::on_finalize(System::block_number());
 	assert_ok!(MinterestOracle::feed_values(origin_of(ORACLE1::get().clone()), prices));
@@ -418,6 +456,12 @@ fn set_oracle_prices(prices: Vec<(CurrencyId, Price)>) -> DispatchResult {
 	Ok(())
 }
 
+fn set_external_oracle_prices(prices: Vec<(CurrencyId, Price)>) -> DispatchResult {
+	ExternalOracle::on_finalize(System::block_number());
+	assert_ok!(ExternalOracle::feed_values(origin_of(ORACLE2::get().clone()), prices));
+	Ok(())
+}
+
 fn get_all_locked_prices() -> Vec<(CurrencyId, Option<Price>)> {
 	<Runtime as PricesRuntimeApi<Block>>::get_all_locked_prices()
 }
@@ -426,6 +470,18 @@ fn get_all_freshest_prices() -> Vec<(CurrencyId, Option<Price>)> {
 	<Runtime as PricesRuntimeApi<Block>>::get_all_freshest_prices()
 }
 
+fn get_stale_price_feeds() -> Vec<(CurrencyId, Option<Moment>)> {
+	<Runtime as PricesRuntimeApi<Block>>::get_stale_price_feeds()
+}
+
+fn get_price_history(
+	currency_id: CurrencyId,
+	from_block: BlockNumber,
+	to_block: BlockNumber,
+) -> Vec<(BlockNumber, Price)> {
+	<Runtime as PricesRuntimeApi<Block>>::get_price_history(currency_id, from_block, to_block)
+}
+
 fn lock_price(currency_id: CurrencyId) -> DispatchResultWithPostInfo {
 	Prices::lock_price(origin_root(), currency_id)
 }
@@ -732,8 +788,8 @@ fn test_get_protocol_total_value_rpc() {
 		.build()
 		.execute_with(|| {
 			assert_ok!(set_oracle_prices(vec![
-				(DOT, Price::saturating_from_integer(2)),
-				(ETH, Price::saturating_from_integer(3)),
+				(DOT, oracle_price(DOT, 2)),
+				(ETH, oracle_price(ETH, 3)),
 			]));
 			assert_ok!(lock_price(DOT));
 			assert_ok!(lock_price(ETH));
@@ -1142,7 +1198,7 @@ fn test_free_balance_is_ok_after_repay_all_and_redeem_using_balance_rpc() {
 			let account_data_before_repay_all =
 				get_user_total_supply_and_borrow_balance_in_usd_rpc(BOB::get()).unwrap_or_default();
 
-			let oracle_price = Prices::get_underlying_price(DOT).unwrap();
+			let oracle_price = DOT.normalize_price(Prices::get_underlying_price(DOT).unwrap()).unwrap();
 
 			let bob_balance_before_repay_all = Currencies::free_balance(DOT, &BOB::get());
 
@@ -1181,7 +1237,7 @@ fn test_user_total_borrowed_difference_is_ok_before_and_after_repay_using_balanc
 			let account_data_before_repay =
 				get_user_total_supply_and_borrow_balance_in_usd_rpc(BOB::get()).unwrap_or_default();
 
-			let oracle_price = Prices::get_underlying_price(DOT).unwrap();
+			let oracle_price = DOT.normalize_price(Prices::get_underlying_price(DOT).unwrap()).unwrap();
 
 			assert_ok!(MinterestProtocol::repay(bob(), DOT, dollars(10_000)));
 			let account_data_after_repay =
@@ -1220,7 +1276,7 @@ fn test_user_total_borrowed_difference_is_ok_before_and_after_borrow_using_balan
 			let account_data_before_borrow =
 				get_user_total_supply_and_borrow_balance_in_usd_rpc(BOB::get()).unwrap_or_default();
 
-			let oracle_price = Prices::get_underlying_price(DOT).unwrap();
+			let oracle_price = DOT.normalize_price(Prices::get_underlying_price(DOT).unwrap()).unwrap();
 
 			assert_ok!(MinterestProtocol::borrow(bob(), DOT, dollars(30_000)));
 			let account_data_after_borrow =
@@ -1260,7 +1316,7 @@ fn test_user_total_borrowed_difference_is_ok_before_and_after_deposit_using_bala
 			let account_data_before_deposit =
 				get_user_total_supply_and_borrow_balance_in_usd_rpc(BOB::get()).unwrap_or_default();
 
-			let oracle_price = Prices::get_underlying_price(DOT).unwrap();
+			let oracle_price = DOT.normalize_price(Prices::get_underlying_price(DOT).unwrap()).unwrap();
 
 			assert_ok!(MinterestProtocol::deposit_underlying(bob(), DOT, dollars(30_000)));
 			let account_data_after_deposit =
@@ -1367,7 +1423,7 @@ fn get_user_total_collateral_rpc_should_work() {
 			// Change the price from 2 USD to 4 USD for DOT.
 			assert_ok!(MinterestOracle::feed_values(
 				origin_of(ORACLE1::get().clone()),
-				vec![(DOT, Rate::saturating_from_integer(4))]
+				vec![(DOT, oracle_price(DOT, 4))]
 			));
 			assert_ok!(Prices::unlock_price(origin_root(), DOT));
 
@@ -1439,26 +1495,30 @@ fn get_all_locked_prices_rpc_should_work() {
 
 		CurrencyId::get_enabled_tokens_in_protocol(minterest_primitives::currency::CurrencyType::UnderlyingAsset)
 			.into_iter()
+			.filter(|&pool_id| pool_id != USDT)
 			.for_each(|pool_id| {
 				assert_ok!(Prices::lock_price(origin_root(), pool_id));
 			});
 
 		// Check that locked prices are returned
-		// By default all price set to 10_000
+		// By default all price set to 10_000, the price of the stable currency can't be locked
 		let locked_prices = get_all_locked_prices();
-		for (_currency_id, price) in locked_prices {
-			assert_eq!(price, Some(Price::saturating_from_integer(10_000)));
+		for (currency_id, price) in locked_prices {
+			match currency_id {
+				USDT => assert_eq!(price, None),
+				_ => assert_eq!(price, Some(oracle_price(currency_id, 10_000))),
+			}
 		}
 		// Unlock price for DOT, check that None will be returned for this currency
 		assert_ok!(unlock_price(DOT));
 		let locked_prices = get_all_locked_prices();
 		for (currency_id, price) in locked_prices {
 			match currency_id {
-				DOT => {
+				DOT | USDT => {
 					assert_eq!(price, None);
 				}
 				ETH | BTC | KSM => {
-					assert_eq!(price, Some(Price::saturating_from_integer(10_000)));
+					assert_eq!(price, Some(oracle_price(currency_id, 10_000)));
 				}
 				_ => panic!("Unexpected token!"),
 			}
@@ -1473,12 +1533,86 @@ fn get_all_freshest_prices_rpc_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
 		assert_ok!(set_oracle_price_for_all_pools(10_000));
 		let fresh_prices = get_all_freshest_prices();
-		for (_currency_id, price) in fresh_prices {
-			assert_eq!(price, Some(Price::saturating_from_integer(10_000)));
+		for (currency_id, price) in fresh_prices {
+			match currency_id {
+				// The stable currency is always priced at 1 USD.
+				USDT => assert_eq!(price, Some(Price::one())),
+				_ => assert_eq!(price, Some(oracle_price(currency_id, 10_000))),
+			}
 		}
 	});
 }
 
+#[test]
+fn get_stale_price_feeds_rpc_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		Timestamp::set_timestamp(1_000);
+		assert_ok!(set_oracle_price_for_all_pools(10_000));
+		assert_ok!(Prices::set_max_price_age(origin_root(), DOT, Some(60_000)));
+		assert_eq!(get_stale_price_feeds(), vec![]);
+
+		// One minute later the DOT price is stale, other currencies have no maximum age.
+		Timestamp::set_timestamp(61_001);
+		assert_eq!(get_stale_price_feeds(), vec![(DOT, Some(1_000))]);
+		assert_eq!(Prices::get_underlying_price(DOT), None);
+		// The stale price stays the last known price.
+		assert_eq!(
+			Prices::get_last_underlying_price(DOT),
+			Some(Price::saturating_from_integer(10_000))
+		);
+		assert_eq!(
+			Prices::get_underlying_price(ETH),
+			Some(Price::saturating_from_integer(10_000))
+		);
+
+		assert_ok!(set_oracle_price_for_all_pools(10_000));
+		assert_eq!(get_stale_price_feeds(), vec![]);
+	});
+}
+
+#[test]
+fn aggregated_price_should_only_average_agreeing_oracles() {
+	ExtBuilder::default().build().execute_with(|| {
+		// Only one oracle instance has the price.
+		assert_ok!(set_oracle_prices(vec![(DOT, oracle_price(DOT, 100))]));
+		assert_eq!(Prices::get_underlying_price(DOT), Some(oracle_price(DOT, 100)));
+
+		// The prices of both instances are averaged.
+		assert_ok!(set_external_oracle_prices(vec![(DOT, oracle_price(DOT, 104))]));
+		assert_eq!(Prices::get_underlying_price(DOT), Some(oracle_price(DOT, 102)));
+
+		// The diverging prices leave the currency without a price rather than picking one of them.
+		assert_ok!(set_external_oracle_prices(vec![(DOT, oracle_price(DOT, 120))]));
+		assert_eq!(AggregatedDataProvider::get_no_op(&DOT), None);
+		assert_eq!(Prices::get_underlying_price(DOT), None);
+	});
+}
+
+#[test]
+fn get_price_history_rpc_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(10);
+		assert_ok!(set_oracle_price_for_all_pools(10_000));
+		System::set_block_number(20);
+		assert_ok!(set_oracle_price_for_all_pools(12_000));
+		System::set_block_number(30);
+		assert_ok!(set_oracle_price_for_all_pools(11_000));
+
+		assert_eq!(
+			get_price_history(DOT, 0, 100),
+			vec![
+				(10, oracle_price(DOT, 10_000)),
+				(20, oracle_price(DOT, 12_000)),
+				(30, oracle_price(DOT, 11_000))
+			]
+		);
+		assert_eq!(get_price_history(DOT, 15, 25), vec![(20, oracle_price(DOT, 12_000))]);
+		assert_eq!(get_price_history(DOT, 31, 100), vec![]);
+		// The fixed price of the stable currency is never accepted from the oracle.
+		assert_eq!(get_price_history(USDT, 0, 100), vec![]);
+	});
+}
+
 #[test]
 fn get_user_total_unclaimed_mnt_balance_should_work() {
 	ExtBuilder::default()
@@ -1638,6 +1772,54 @@ fn pool_exists_should_work() {
 	});
 }
 
+#[test]
+fn get_pool_bad_debt_rpc_should_work() {
+	ExtBuilder::default().pool_initial(DOT).build().execute_with(|| {
+		assert_eq!(
+			get_pool_bad_debt_rpc(DOT),
+			Some(BalanceInfo {
+				amount: Balance::zero()
+			})
+		);
+		LiquidityPools::set_pool_bad_debt(DOT, dollars(10));
+		assert_eq!(get_pool_bad_debt_rpc(DOT), Some(BalanceInfo { amount: dollars(10) }));
+		assert_eq!(get_pool_bad_debt_rpc(ETH), None);
+	});
+}
+
+#[test]
+fn get_pool_supply_cap_rpc_should_work() {
+	ExtBuilder::default().pool_initial(ETH).build().execute_with(|| {
+		// Set price = 2.00 USD for all pools.
+		assert_ok!(set_oracle_price_for_all_pools(2));
+		assert_ok!(MinterestProtocol::deposit_underlying(alice(), ETH, dollars(100_000)));
+		assert_eq!(
+			get_pool_supply_cap_rpc(ETH),
+			Some(PoolSupplyCapData {
+				pool_total_supply_in_usd: dollars(200_000),
+				supply_cap: None
+			})
+		);
+
+		assert_ok!(Controller::set_supply_cap(origin_root(), ETH, Some(dollars(250_000))));
+		assert_noop!(
+			MinterestProtocol::deposit_underlying(bob(), ETH, dollars(30_000)),
+			controller::Error::<Runtime>::SupplyCapReached
+		);
+		assert_ok!(MinterestProtocol::deposit_underlying(bob(), ETH, dollars(20_000)));
+		assert_eq!(
+			get_pool_supply_cap_rpc(ETH),
+			Some(PoolSupplyCapData {
+				pool_total_supply_in_usd: dollars(240_000),
+				supply_cap: Some(BalanceInfo {
+					amount: dollars(250_000)
+				})
+			})
+		);
+		assert_eq!(get_pool_supply_cap_rpc(DOT), None);
+	});
+}
+
 #[test]
 fn is_whitelist_member_should_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -26,9 +26,14 @@ use sp_std::marker::PhantomData;
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> controller::WeightInfo for WeightInfo<T> {
 	fn pause_operation() -> Weight {
-		(26_027_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+		(27_893_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn pause_operation_until() -> Weight {
+		(29_146_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 	fn resume_operation() -> Weight {
 		(25_834_000 as Weight)
@@ -55,9 +60,67 @@ impl<T: frame_system::Config> controller::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_supply_cap() -> Weight {
+		(28_614_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_usage_params() -> Weight {
+		(25_913_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_interest_accrual_mode() -> Weight {
+		(51_687_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn switch_borrow_rate_clamping() -> Weight {
+		(24_731_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn accrue_interest() -> Weight {
+		(58_912_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(10 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn accrue_interest_all(n: u32) -> Weight {
+		(2_517_000 as Weight)
+			// Standard Error: 12_000
+			.saturating_add((57_340_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((9 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((5 as Weight).saturating_mul(n as Weight)))
+	}
+	fn set_emode_category() -> Weight {
+		(22_365_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_pool_emode_category() -> Weight {
+		(26_108_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn set_protocol_interest_threshold() -> Weight {
 		(28_257_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn pause_all() -> Weight {
+		(203_160_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(50 as Weight))
+	}
+	fn resume_all() -> Weight {
+		(198_412_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(50 as Weight))
+	}
+	fn on_initialize(n: u32) -> Weight {
+		(2_874_000 as Weight)
+			// Standard Error: 2_000
+			.saturating_add((9_615_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(n as Weight)))
+	}
 }
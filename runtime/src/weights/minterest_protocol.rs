@@ -85,9 +85,19 @@ impl<T: frame_system::Config> minterest_protocol::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(31 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_user_emode() -> Weight {
+		(421_306_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(30 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	fn claim_mnt() -> Weight {
 		(1_174_850_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(35 as Weight))
 			.saturating_add(T::DbWeight::get().writes(15 as Weight))
 	}
+	fn liquidate_borrow() -> Weight {
+		(763_847_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(45 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
 }
@@ -1,10 +1,14 @@
 #![allow(clippy::unnecessary_cast)]
 
 pub mod controller;
+pub mod dex;
 pub mod liquidation_pools;
 pub mod minterest_model;
 pub mod minterest_protocol;
 pub mod mnt_token;
+pub mod price_fetcher;
 pub mod prices;
+pub mod risk_manager;
+pub mod timelock;
 pub mod vesting;
 pub mod whitelist;
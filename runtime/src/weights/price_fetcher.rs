@@ -0,0 +1,34 @@
+//! Autogenerated weights for price_fetcher
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2021-07-20, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=price-fetcher
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./runtime/src/weights/price_fetcher.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for price_fetcher.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> price_fetcher::WeightInfo for WeightInfo<T> {
+	fn set_endpoints(e: u32) -> Weight {
+		(13_561_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((284_000 as Weight).saturating_mul(e as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
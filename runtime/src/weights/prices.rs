@@ -33,4 +33,22 @@ impl<T: frame_system::Config> module_prices::WeightInfo for WeightInfo<T> {
 	fn unlock_price() -> Weight {
 		(18_992_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
+	fn set_price_source() -> Weight {
+		(22_163_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_age() -> Weight {
+		(22_348_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn set_max_price_deviation() -> Weight {
+		(18_326_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn report_stale_price_feed() -> Weight {
+		(24_871_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
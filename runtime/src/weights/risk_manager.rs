@@ -0,0 +1,69 @@
+//! Autogenerated weights for risk_manager
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2021-07-28, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=risk_manager
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./runtime/src/weights/risk_manager.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for risk_manager.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> risk_manager::WeightInfo for WeightInfo<T> {
+	fn liquidate() -> Weight {
+		(356_914_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(42 as Weight))
+			.saturating_add(T::DbWeight::get().writes(21 as Weight))
+	}
+	fn cover_bad_debt_from_liquidation_pool() -> Weight {
+		(45_108_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn cover_bad_debt_from_protocol_interest() -> Weight {
+		(27_514_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn socialize_bad_debt() -> Weight {
+		(21_370_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_fee() -> Weight {
+		(17_135_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_liquidation_threshold() -> Weight {
+		(20_892_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_auction_params() -> Weight {
+		(19_452_000 as Weight).saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn bid() -> Weight {
+		(101_338_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn restart_auction() -> Weight {
+		(58_267_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+}
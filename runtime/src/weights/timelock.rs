@@ -0,0 +1,49 @@
+//! Autogenerated weights for timelock
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 3.0.0
+//! DATE: 2021-07-27, STEPS: `[50, ]`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// ./target/release/minterest
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=timelock
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --output=./runtime/src/weights/timelock.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for timelock.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> timelock::WeightInfo for WeightInfo<T> {
+	fn queue_change() -> Weight {
+		(17_304_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn cancel_change(n: u32) -> Weight {
+		(13_286_000 as Weight)
+			// Standard Error: 1_000
+			.saturating_add((87_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn on_initialize(n: u32) -> Weight {
+		(1_503_000 as Weight)
+			// Standard Error: 3_000
+			.saturating_add((7_724_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(n as Weight)))
+	}
+}
@@ -1,4 +1,4 @@
-use controller::{ControllerData, PauseKeeper};
+use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 use hex_literal::hex;
 use liquidation_pools::LiquidationPoolData;
 use liquidity_pools::Pool;
@@ -8,9 +8,10 @@ use minterest_primitives::{VestingBucket, VestingScheduleJson};
 use node_minterest_runtime::{
 	get_all_modules_accounts, AccountId, AuraConfig, Balance, BalancesConfig, ControllerConfig, ExistentialDeposit,
 	GenesisConfig, GrandpaConfig, LiquidationPoolsConfig, LiquidityPoolsConfig, MinterestCouncilMembershipConfig,
-	MinterestModelConfig, MntTokenConfig, MntTokenPalletId, OperatorMembershipMinterestConfig, PricesConfig,
-	RiskManagerConfig, Signature, SudoConfig, SystemConfig, TokensConfig, VestingConfig, WhitelistConfig, BTC, DOLLARS,
-	DOT, ETH, KSM, MNT, PROTOCOL_INTEREST_TRANSFER_THRESHOLD, TOTAL_ALLOCATION, WASM_BINARY,
+	MinterestModelConfig, MntTokenConfig, MntTokenPalletId, OperatorMembershipExternalConfig,
+	OperatorMembershipMinterestConfig, PricesConfig, RiskManagerConfig, Signature, SudoConfig, SystemConfig,
+	TokensConfig, VestingConfig, WhitelistConfig, BTC, DOLLARS, DOT, ETH, KSM, MNT,
+	PROTOCOL_INTEREST_TRANSFER_THRESHOLD, TOTAL_ALLOCATION, USDT, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sc_telemetry::TelemetryEndpoints;
@@ -299,6 +300,8 @@ fn minterest_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -310,6 +313,8 @@ fn minterest_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -321,6 +326,8 @@ fn minterest_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -332,6 +339,8 @@ fn minterest_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			],
@@ -390,7 +399,12 @@ fn minterest_genesis(
 				(BTC, FixedU128::saturating_from_rational(5, 100)),
 				(KSM, FixedU128::saturating_from_rational(5, 100)),
 			],
-			liquidation_threshold: FixedU128::saturating_from_rational(103, 100),
+			liquidation_threshold: vec![
+				(DOT, FixedU128::saturating_from_rational(3, 100)),
+				(ETH, FixedU128::saturating_from_rational(3, 100)),
+				(BTC, FixedU128::saturating_from_rational(3, 100)),
+				(KSM, FixedU128::saturating_from_rational(3, 100)),
+			],
 			_phantom: Default::default(),
 		},
 		liquidation_pools: LiquidationPoolsConfig {
@@ -446,6 +460,10 @@ fn minterest_genesis(
 			phantom: Default::default(),
 		},
 		operator_membership_minterest: OperatorMembershipMinterestConfig {
+			members: vec![root_key.clone()],
+			phantom: Default::default(),
+		},
+		operator_membership_external: OperatorMembershipExternalConfig {
 			members: vec![root_key],
 			phantom: Default::default(),
 		},
@@ -515,6 +533,7 @@ fn testnet_genesis(
 						(x.clone(), ETH, INITIAL_BALANCE),
 						(x.clone(), KSM, INITIAL_BALANCE),
 						(x.clone(), BTC, INITIAL_BALANCE),
+						(x.clone(), USDT, INITIAL_BALANCE),
 					]
 				})
 				.collect(),
@@ -553,6 +572,14 @@ fn testnet_genesis(
 						protocol_interest: Balance::zero(),
 					},
 				),
+				(
+					USDT,
+					Pool {
+						borrowed: Balance::zero(),
+						borrow_index: FixedU128::one(),
+						protocol_interest: Balance::zero(),
+					},
+				),
 			],
 			pool_user_data: vec![],
 		},
@@ -567,6 +594,8 @@ fn testnet_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -578,6 +607,8 @@ fn testnet_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -589,6 +620,8 @@ fn testnet_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 				(
@@ -600,6 +633,21 @@ fn testnet_genesis(
 						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
 						borrow_cap: None,
 						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
+					},
+				),
+				(
+					USDT,
+					ControllerData {
+						last_interest_accrued_block: 0,
+						protocol_interest_factor: FixedU128::saturating_from_rational(1, 10),
+						max_borrow_rate: FixedU128::saturating_from_rational(5, 1000),
+						collateral_factor: FixedU128::saturating_from_rational(9, 10), // 90%
+						borrow_cap: None,
+						protocol_interest_threshold: PROTOCOL_INTEREST_TRANSFER_THRESHOLD,
+						supply_cap: None,
+						interest_accrual_mode: InterestAccrualMode::Simple,
 					},
 				),
 			],
@@ -608,6 +656,7 @@ fn testnet_genesis(
 				(DOT, PauseKeeper::all_unpaused()),
 				(KSM, PauseKeeper::all_unpaused()),
 				(BTC, PauseKeeper::all_unpaused()),
+				(USDT, PauseKeeper::all_unpaused()),
 			],
 		},
 		minterest_model: MinterestModelConfig {
@@ -648,17 +697,33 @@ fn testnet_genesis(
 						jump_multiplier_per_block: FixedU128::saturating_from_rational(207, 1_000_000_000), // 1.09 PerYear
 					},
 				),
+				(
+					USDT,
+					MinterestModelData {
+						kink: FixedU128::saturating_from_rational(8, 10), // 0.8 = 80 %
+						base_rate_per_block: FixedU128::zero(),
+						multiplier_per_block: FixedU128::saturating_from_rational(9, 1_000_000_000), // 0.047304 PerYear
+						jump_multiplier_per_block: FixedU128::saturating_from_rational(207, 1_000_000_000), // 1.09 PerYear
+					},
+				),
 			],
 			_phantom: Default::default(),
 		},
 		risk_manager: RiskManagerConfig {
 			liquidation_fee: vec![
-				(DOT, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(ETH, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(BTC, FixedU128::saturating_from_rational(5, 100)), // 5%
-				(KSM, FixedU128::saturating_from_rational(5, 100)), // 5%
+				(DOT, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(ETH, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(BTC, FixedU128::saturating_from_rational(5, 100)),  // 5%
+				(USDT, FixedU128::saturating_from_rational(5, 100)), // 5%
+				(KSM, FixedU128::saturating_from_rational(5, 100)),  // 5%
+			],
+			liquidation_threshold: vec![
+				(DOT, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(ETH, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(BTC, FixedU128::saturating_from_rational(3, 100)),  // 3%
+				(USDT, FixedU128::saturating_from_rational(3, 100)), // 3%
+				(KSM, FixedU128::saturating_from_rational(3, 100)),  // 3%
 			],
-			liquidation_threshold: FixedU128::saturating_from_rational(3, 100), // 3%
 			_phantom: Default::default(),
 		},
 		liquidation_pools: LiquidationPoolsConfig {
@@ -688,6 +753,14 @@ fn testnet_genesis(
 						max_ideal_balance: None,
 					},
 				),
+				(
+					USDT,
+					LiquidationPoolData {
+						deviation_threshold: FixedU128::saturating_from_rational(1, 10),
+						balance_ratio: FixedU128::saturating_from_rational(2, 10),
+						max_ideal_balance: None,
+					},
+				),
 				(
 					KSM,
 					LiquidationPoolData {
@@ -717,6 +790,10 @@ fn testnet_genesis(
 			members: endowed_accounts.clone(),
 			phantom: Default::default(),
 		},
+		operator_membership_external: OperatorMembershipExternalConfig {
+			members: endowed_accounts.clone(),
+			phantom: Default::default(),
+		},
 		mnt_token: MntTokenConfig {
 			mnt_claim_threshold: 0, // disable by default
 			minted_pools: vec![
@@ -724,6 +801,7 @@ fn testnet_genesis(
 				(ETH, 2 * DOLLARS),
 				(KSM, 2 * DOLLARS),
 				(BTC, 2 * DOLLARS),
+				(USDT, 2 * DOLLARS),
 			],
 			_phantom: Default::default(),
 		},
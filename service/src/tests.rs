@@ -30,6 +30,10 @@ fn get_all_modules_accounts_should_work() {
 		get_all_modules_accounts()[3],
 		node_minterest_runtime::LiquidityPoolsPalletId::get().into_account()
 	);
+	assert_eq!(
+		get_all_modules_accounts()[4],
+		node_minterest_runtime::AuctionPalletId::get().into_account()
+	);
 }
 
 // Checks for the existence of a json file with initial token allocations.
@@ -1,4 +1,4 @@
-pub use controller::{ControllerData, PauseKeeper};
+pub use controller::{ControllerData, InterestAccrualMode, PauseKeeper};
 use frame_support::{
 	construct_runtime, ord_parameter_types,
 	pallet_prelude::{GenesisBuild, TransactionPriority},
@@ -15,10 +15,11 @@ pub use test_helper::*;
 
 pub use minterest_primitives::{
 	currency::CurrencyType::{UnderlyingAsset, WrappedToken},
-	Balance, CurrencyId, Price, Rate,
+	Balance, CurrencyId, DataProviderId, Moment, Price, Rate,
 };
-use orml_traits::{parameter_type_with_key, DataFeeder, DataProvider};
-use pallet_traits::{PoolsManager, PricesManager};
+use orml_oracle::TimestampedValue;
+use orml_traits::{parameter_type_with_key, DataFeeder, DataProvider, DataProviderExtended};
+use pallet_traits::{OracleDataProvider, PoolsManager, PricesManager};
 use sp_runtime::{
 	testing::{Header, TestXt, H256},
 	traits::{AccountIdConversion, BlakeTwo256, IdentityLookup, One, Zero},
@@ -69,7 +70,11 @@ parameter_types! {
 
 thread_local! {
 	static UNDERLYING_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
+	static LAST_UNDERLYING_PRICE: RefCell<Option<Price>> = RefCell::new(Some(Price::one()));
 	static TWO: RefCell<Vec<u64>> = RefCell::new(vec![2]);
+	static PRICE_TIMESTAMP: RefCell<Moment> = RefCell::new(Moment::zero());
+	static FED_PRICES: RefCell<Vec<(CurrencyId, Price)>> = RefCell::new(vec![]);
+	static ORACLE_PRICES: RefCell<Vec<(DataProviderId, CurrencyId, Price)>> = RefCell::new(vec![]);
 }
 
 ord_parameter_types! {
@@ -113,8 +118,13 @@ mock_impl_prices_module_config!(TestRuntime, OneAlice);
 pub struct MockPriceSource;
 
 impl MockPriceSource {
+	/// Sets the current price. The last set price stays the last known price when the current
+	/// one is removed.
 	pub fn set_underlying_price(price: Option<Price>) {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut() = price);
+		if price.is_some() {
+			LAST_UNDERLYING_PRICE.with(|v| *v.borrow_mut() = price);
+		}
 	}
 }
 
@@ -123,6 +133,10 @@ impl PricesManager<CurrencyId> for MockPriceSource {
 		UNDERLYING_PRICE.with(|v| *v.borrow_mut())
 	}
 
+	fn get_last_underlying_price(_currency_id: CurrencyId) -> Option<Price> {
+		LAST_UNDERLYING_PRICE.with(|v| *v.borrow())
+	}
+
 	fn lock_price(_currency_id: CurrencyId) {}
 
 	fn unlock_price(_currency_id: CurrencyId) {}
@@ -134,6 +148,15 @@ impl PricesManager<CurrencyId> for MockPriceSource {
 pub struct MockDataProvider;
 impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	fn get(currency_id: &CurrencyId) -> Option<Price> {
+		let fed_price = FED_PRICES.with(|v| {
+			v.borrow()
+				.iter()
+				.find(|(fed_currency_id, _)| fed_currency_id == currency_id)
+				.map(|&(_, price)| price)
+		});
+		if fed_price.is_some() {
+			return fed_price;
+		}
 		match currency_id {
 			&MNT => Some(Price::zero()),
 			&BTC => Some(Price::saturating_from_integer(48_000)),
@@ -145,11 +168,76 @@ impl DataProvider<CurrencyId, Price> for MockDataProvider {
 	}
 }
 
+impl MockDataProvider {
+	/// Replaces the default price of the currency.
+	pub fn set_price(currency_id: CurrencyId, price: Price) {
+		FED_PRICES.with(|v| {
+			let mut prices = v.borrow_mut();
+			prices.retain(|(fed_currency_id, _)| *fed_currency_id != currency_id);
+			prices.push((currency_id, price));
+		});
+	}
+
+	pub fn set_price_timestamp(timestamp: Moment) {
+		PRICE_TIMESTAMP.with(|v| *v.borrow_mut() = timestamp);
+	}
+
+	/// Sets the price of the currency fed to the single oracle instance.
+	pub fn set_oracle_price(provider_id: DataProviderId, currency_id: CurrencyId, price: Price) {
+		ORACLE_PRICES.with(|v| {
+			let mut prices = v.borrow_mut();
+			prices.retain(|(fed_provider_id, fed_currency_id, _)| {
+				(*fed_provider_id, *fed_currency_id) != (provider_id, currency_id)
+			});
+			prices.push((provider_id, currency_id, price));
+		});
+	}
+}
+
+impl OracleDataProvider<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(provider_id: DataProviderId, currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		match provider_id {
+			DataProviderId::Aggregated => <Self as DataProviderExtended<_, _>>::get_no_op(currency_id),
+			DataProviderId::Minterest | DataProviderId::External => ORACLE_PRICES
+				.with(|v| {
+					v.borrow()
+						.iter()
+						.find(|(fed_provider_id, fed_currency_id, _)| {
+							*fed_provider_id == provider_id && fed_currency_id == currency_id
+						})
+						.map(|&(_, _, price)| price)
+				})
+				.map(|value| TimestampedValue {
+					value,
+					timestamp: PRICE_TIMESTAMP.with(|v| *v.borrow()),
+				}),
+			DataProviderId::Dex => None,
+		}
+	}
+}
+
+impl DataProviderExtended<CurrencyId, TimestampedValue<Price, Moment>> for MockDataProvider {
+	fn get_no_op(currency_id: &CurrencyId) -> Option<TimestampedValue<Price, Moment>> {
+		Self::get(currency_id).map(|value| TimestampedValue {
+			value,
+			timestamp: PRICE_TIMESTAMP.with(|v| *v.borrow()),
+		})
+	}
+
+	fn get_all_values() -> Vec<(CurrencyId, Option<TimestampedValue<Price, Moment>>)> {
+		CurrencyId::get_enabled_tokens_in_protocol(UnderlyingAsset)
+			.into_iter()
+			.map(|currency_id| (currency_id, Self::get_no_op(&currency_id)))
+			.collect()
+	}
+}
+
 impl DataFeeder<CurrencyId, Price, AccountId> for MockDataProvider {
 	fn feed_value(_: AccountId, _: CurrencyId, _: Price) -> sp_runtime::DispatchResult {
 		Ok(())
 	}
 }
+
 // -----------------------------------------------------------------------------------------
 // 									EXTERNALITY BUILDER
 // -----------------------------------------------------------------------------------------
@@ -218,15 +306,6 @@ impl ExtBuilderNew {
 		self
 	}
 
-	/// Set DEX balance
-	/// - 'currency_id': currency id
-	/// - 'balance': balance value
-	pub fn set_dex_balance(mut self, currency_id: CurrencyId, balance: Balance) -> Self {
-		self.endowed_accounts
-			.push((TestDex::dex_account_id(), currency_id, balance));
-		self
-	}
-
 	/// Initialize pool with default parameters:
 	/// borrowed: 0, borrow_index: 1, protocol_interest: 0
 	/// - 'pool_id': pool currency / id
@@ -387,6 +466,8 @@ impl ExtBuilderNew {
 				collateral_factor,
 				borrow_cap,
 				protocol_interest_threshold,
+				supply_cap: None,
+				interest_accrual_mode: InterestAccrualMode::Simple,
 			},
 		));
 		self
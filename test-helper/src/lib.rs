@@ -7,11 +7,15 @@
 
 pub mod offchain_ext;
 pub use currency_mock::*;
+pub use time_mock::*;
 pub use users_mock::*;
 
 pub mod currency_mock {
 	use frame_support::sp_runtime::FixedPointNumber;
-	use minterest_primitives::{currency::TokenSymbol, Balance, CurrencyId, Price};
+	use minterest_primitives::{
+		currency::{GetDecimals, TokenSymbol},
+		Balance, CurrencyId, Price,
+	};
 
 	pub const MNT: CurrencyId = CurrencyId::Native(TokenSymbol::MNT);
 	pub const DOT: CurrencyId = CurrencyId::UnderlyingAsset(TokenSymbol::DOT);
@@ -22,11 +26,17 @@ pub mod currency_mock {
 	pub const MBTC: CurrencyId = CurrencyId::WrappedToken(TokenSymbol::MBTC);
 	pub const ETH: CurrencyId = CurrencyId::UnderlyingAsset(TokenSymbol::ETH);
 	pub const METH: CurrencyId = CurrencyId::WrappedToken(TokenSymbol::METH);
+	pub const USDT: CurrencyId = CurrencyId::UnderlyingAsset(TokenSymbol::USDT);
+	pub const MUSDT: CurrencyId = CurrencyId::WrappedToken(TokenSymbol::MUSDT);
 
 	pub const DOLLARS: Balance = 1_000_000_000_000_000_000;
 	pub fn dollars(amount: u128) -> u128 {
 		amount.saturating_mul(Price::accuracy())
 	}
+	/// Converts whole tokens into the smallest units of the currency, according to its decimals.
+	pub fn units(currency_id: CurrencyId, amount: u128) -> Balance {
+		amount.saturating_mul(10_u128.saturating_pow(currency_id.decimals()))
+	}
 
 	pub const ONE_HUNDRED: Balance = 100 * DOLLARS;
 	pub const TEN_THOUSAND: Balance = 10_000 * DOLLARS;
@@ -45,6 +55,7 @@ pub mod users_mock {
 	pub const ALICE: AccountId = 1;
 	pub const BOB: AccountId = 2;
 	pub const CHARLIE: AccountId = 3;
+	pub const DAVE: AccountId = 4;
 
 	pub fn admin_origin<Origin: OriginTrait<AccountId = AccountId>>() -> Origin {
 		Origin::signed(ADMIN)
@@ -58,6 +69,35 @@ pub mod users_mock {
 	pub fn charlie_origin<Origin: OriginTrait<AccountId = AccountId>>() -> Origin {
 		Origin::signed(CHARLIE)
 	}
+	pub fn dave_origin<Origin: OriginTrait<AccountId = AccountId>>() -> Origin {
+		Origin::signed(DAVE)
+	}
+}
+
+pub mod time_mock {
+	use frame_support::traits::Time;
+	use minterest_primitives::Moment;
+	use std::cell::RefCell;
+
+	thread_local! {
+		static NOW: RefCell<Moment> = RefCell::new(0);
+	}
+
+	pub struct MockTime;
+
+	impl MockTime {
+		pub fn set_now(now: Moment) {
+			NOW.with(|v| *v.borrow_mut() = now);
+		}
+	}
+
+	impl Time for MockTime {
+		type Moment = Moment;
+
+		fn now() -> Moment {
+			NOW.with(|v| *v.borrow())
+		}
+	}
 }
 
 #[macro_export]
@@ -163,6 +203,7 @@ macro_rules! mock_impl_liquidation_pools_config {
 	($target:ty) => {
 		parameter_types! {
 			pub const MockLiquidityPoolsPriority: TransactionPriority = TransactionPriority::max_value() - 1;
+			pub const MaxBalancingSlippage: Rate = Rate::from_inner(50_000_000_000_000_000);
 		}
 
 		impl liquidation_pools::Config for $target {
@@ -175,6 +216,7 @@ macro_rules! mock_impl_liquidation_pools_config {
 			type LiquidityPoolsManager = liquidity_pools::Pallet<$target>;
 			type LiquidationPoolAccountId = LiquidationPoolAccountId;
 			type Dex = dex::Pallet<$target>;
+			type MaxBalancingSlippage = MaxBalancingSlippage;
 			type LiquidationPoolsWeightInfo = ();
 		}
 
@@ -196,6 +238,15 @@ macro_rules! mock_impl_controller_config {
 	($target:ty, $acc:ident) => {
 		parameter_types! {
 			pub const MaxBorrowCap: Balance = 1_000_000_000_000_000_000_000_000;
+			pub const MaxSupplyCap: Balance = 1_000_000_000_000_000_000_000_000;
+			pub const CloseFactor: Rate = Rate::from_inner(500_000_000_000_000_000);
+			pub const KeeperReward: Balance = 1_000_000_000_000_000_000;
+			pub const KeeperRewardMinAccrualAge: u64 = 100;
+		}
+
+		frame_support::ord_parameter_types! {
+			pub const ThreeCharlie: AccountId = 3;
+			pub const FourDave: AccountId = 4;
 		}
 
 		impl controller::Config for $target {
@@ -205,9 +256,17 @@ macro_rules! mock_impl_controller_config {
 			type LiquidityPoolsManager = liquidity_pools::Pallet<$target>;
 			type MinterestModelManager = minterest_model::Pallet<$target>;
 			type MaxBorrowCap = MaxBorrowCap;
+			type MaxSupplyCap = MaxSupplyCap;
+			type CloseFactor = CloseFactor;
 			type UpdateOrigin = EnsureSignedBy<$acc, AccountId>;
+			type RiskParameterOrigin = EnsureSignedBy<$acc, AccountId>;
+			type EmergencyOrigin = EnsureSignedBy<FourDave, AccountId>;
+			type PauseGuardianOrigin = EnsureSignedBy<ThreeCharlie, AccountId>;
 			type ControllerWeightInfo = ();
 			type MntManager = mnt_token::Pallet<$target>;
+			type MntTokenAccountId = MntTokenAccountId;
+			type KeeperReward = KeeperReward;
+			type KeeperRewardMinAccrualAge = KeeperRewardMinAccrualAge;
 		}
 	};
 }
@@ -233,14 +292,20 @@ macro_rules! mock_impl_dex_config {
 	($target:ty) => {
 		parameter_types! {
 			pub const DexPalletId: PalletId = PalletId(*b"min/dexs");
-			pub DexAccountId: AccountId = DexPalletId::get().into_account();
+			pub const SwapFee: Rate = Rate::from_inner(3_000_000_000_000_000);
+			pub const TradingPathLimit: u32 = 3;
+			pub const TwapWindow: u64 = 10;
 		}
 
 		impl dex::Config for $target {
 			type Event = Event;
 			type MultiCurrency = orml_currencies::Pallet<$target>;
 			type DexPalletId = DexPalletId;
-			type DexAccountId = DexAccountId;
+			type SwapFee = SwapFee;
+			type TradingPathLimit = TradingPathLimit;
+			type TwapWindow = TwapWindow;
+			type Time = MockTime;
+			type DexWeightInfo = ();
 		}
 	};
 }
@@ -272,15 +337,27 @@ macro_rules! mock_impl_risk_manager_config {
 			pub const PartialLiquidationMinSum: Balance = 100_000 * DOLLARS;
 			pub const PartialLiquidationMaxAttempts: u8 = 3_u8;
 			pub const MaxLiquidationFee: Rate = Rate::from_inner(500_000_000_000_000_000);
+			pub const MockRiskManagerPriority: TransactionPriority = TransactionPriority::max_value();
+			pub const AuctionPalletId: PalletId = PalletId(*b"min/auct");
+			pub AuctionAccountId: AccountId = AuctionPalletId::get().into_account();
 		}
 
 		impl risk_manager::Config for $target {
 			type Event = Event;
-			type UserCollateral = liquidity_pools::Pallet<$target>;
+			type UnsignedPriority = MockRiskManagerPriority;
+			type MultiCurrency = orml_currencies::Pallet<$target>;
+			type PriceSource = MockPriceSource;
+			type LiquidityPoolsManager = liquidity_pools::Pallet<$target>;
+			type LiquidationPoolsManager = liquidation_pools::Pallet<$target>;
+			type ControllerManager = controller::Pallet<$target>;
+			type MinterestProtocolManager = minterest_protocol::Pallet<$target>;
 			type PartialLiquidationMinSum = PartialLiquidationMinSum;
 			type PartialLiquidationMaxAttempts = PartialLiquidationMaxAttempts;
 			type MaxLiquidationFee = MaxLiquidationFee;
 			type RiskManagerUpdateOrigin = EnsureSignedBy<$acc, AccountId>;
+			type RiskParameterOrigin = EnsureSignedBy<$acc, AccountId>;
+			type AuctionAccountId = AuctionAccountId;
+			type RiskManagerWeightInfo = ();
 		}
 	};
 }
@@ -343,10 +420,22 @@ macro_rules! mock_impl_whitelist_module_config {
 #[macro_export]
 macro_rules! mock_impl_prices_module_config {
 	($target:ty, $acc:ident) => {
+		parameter_types! {
+			pub const DexQuoteCurrencyId: CurrencyId = DOT;
+			pub StableCurrencyFixedPrice: Vec<(CurrencyId, Price)> = vec![(USDT, Price::one())];
+			pub const PriceHistoryDepth: u32 = 3;
+		}
+
 		impl module_prices::Config for $target {
 			type Event = Event;
 			type Source = MockDataProvider;
+			type Oracles = MockDataProvider;
+			type StableCurrencyFixedPrice = StableCurrencyFixedPrice;
+			type DexSource = dex::TwapDataProvider<$target, DexQuoteCurrencyId, MockDataProvider>;
 			type LockOrigin = EnsureSignedBy<$acc, AccountId>;
+			type Time = MockTime;
+			type ControllerManager = controller::Pallet<$target>;
+			type PriceHistoryDepth = PriceHistoryDepth;
 			type WeightInfo = ();
 		}
 	};